@@ -1,6 +1,14 @@
 use crate::container::codefence::FencedCode;
+use crate::container::figure::CaptionKind;
+use crate::inline::citation::CitedReference;
 use crate::inline::footnote::Footnote;
+use crate::inline::link::wiki::WikiLink;
+use crate::inline::macros::normalize_macro;
+use crate::inline::math::Math;
+use crate::render::diagnostic::Diagnostic;
+use crate::utils::{from_v32, into_v32};
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 
 #[derive(Clone)]
 pub struct DocData {
@@ -8,10 +16,38 @@ pub struct DocData {
     pub link_references: HashMap<Vec<u32>, Vec<u32>>,  // (label, destination)
     pub footnote_references: HashMap<Vec<u32>, Footnote>,  // (label, footnote)
     footnote_reference_count: usize,
+    pub citations: HashMap<String, CitedReference>,  // (key, reference)
+    citation_count: usize,
     pub tooltip_count: usize,
     pub tooltip_enabled: usize,  // it's used to prevent tooltips inside another tooltip
+    pub in_tooltip_message: bool,  // the references in a tooltip message are copies of the others
+    pub in_link_text: usize,  // it's used to prevent autolinks inside another link
     pub has_toc: bool,
     pub has_collapsible_table: bool,
+    pub has_javascript_email: bool,  // `EmailObfuscation::Javascript`
+
+    // the number of figures, tables and code listings with captions
+    pub figure_count: usize,
+    pub table_count: usize,
+    pub listing_count: usize,
+    pub equation_count: usize,
+
+    // labels that `[[ref = ...]]` refers to: (normalized label, (id, text))
+    // e.g. `fig-arch` -> (`fig-arch`, `Figure 3`)
+    pub labels: HashMap<Vec<u32>, (Vec<u32>, Vec<u32>)>,
+
+    // `[[id = here]]` in a paragraph, whose text is the id itself
+    // `labels` comes first, because the id of a figure is also an inline `[[id]]`
+    pub inline_labels: HashMap<Vec<u32>, (Vec<u32>, Vec<u32>)>,
+
+    pub diagnostics: Vec<Diagnostic>,
+
+    // `[[[Page Name]]]`s, one for each title
+    pub wiki_links: Vec<WikiLink>,
+
+    // `IconMode::Sprite`: (id, `<symbol>`) of the icons that the document uses
+    pub icon_symbols: HashMap<Vec<u32>, Vec<u32>>,
+
     pub fenced_code_contents: HashMap<usize, Vec<u32>>  // HashMap<index, content>
 }
 
@@ -23,10 +59,24 @@ impl Default for DocData {
             link_references: HashMap::new(),
             footnote_references: HashMap::new(),
             footnote_reference_count: 0,
+            citations: HashMap::new(),
+            citation_count: 0,
             tooltip_count: 0,
             tooltip_enabled: 0,  // 0 if enabled
+            in_tooltip_message: false,
+            in_link_text: 0,
             has_toc: false,
             has_collapsible_table: false,
+            has_javascript_email: false,
+            figure_count: 0,
+            table_count: 0,
+            listing_count: 0,
+            equation_count: 0,
+            labels: HashMap::new(),
+            inline_labels: HashMap::new(),
+            diagnostics: vec![],
+            wiki_links: vec![],
+            icon_symbols: HashMap::new(),
             fenced_code_contents: HashMap::new()
         }
     }
@@ -37,10 +87,30 @@ impl DocData {
 
     pub fn add_footnote_inverse_index(&mut self, label: &Vec<u32>) -> usize {
         let footnote = self.footnote_references.get_mut(label).unwrap();
-        footnote.inverse_index.push(self.footnote_reference_count);
-        self.footnote_reference_count += 1;
 
-        self.footnote_reference_count - 1
+        add_inverse_index(std::iter::once(&mut footnote.inverse_index), &mut self.footnote_reference_count)
+    }
+
+    // like footnotes, each citation has its own inverse index, so that the references section can link back to the citations
+    pub fn add_citation_inverse_index(&mut self, keys: &[String]) -> usize {
+
+        for key in keys.iter() {
+            let new_index = self.citations.len();
+
+            self.citations.entry(key.clone()).or_insert(CitedReference {
+                index: new_index,
+                inverse_index: vec![]
+            });
+        }
+
+        // each reference is visited once, even with `[@a; @a]`
+        let references = self.citations.iter_mut().filter(
+            |(key, _)| keys.contains(key)
+        ).map(
+            |(_, reference)| &mut reference.inverse_index
+        );
+
+        add_inverse_index(references, &mut self.citation_count)
     }
 
     pub fn add_fenced_code_content(&mut self, fenced_code: &FencedCode) {
@@ -51,9 +121,73 @@ impl DocData {
 
     }
 
+    // captions are numbered from 1
+    pub fn add_caption(&mut self, kind: CaptionKind) -> usize {
+        let count = match kind {
+            CaptionKind::Figure => &mut self.figure_count,
+            CaptionKind::Table => &mut self.table_count,
+            CaptionKind::Listing => &mut self.listing_count
+        };
+
+        *count += 1;
+
+        *count
+    }
+
+    // numbered block math, from 1
+    pub fn add_equation(&mut self) -> usize {
+        self.equation_count += 1;
+
+        self.equation_count
+    }
+
+    // the first definition wins
+    pub fn add_label(&mut self, id: &[u32], text: &str) {
+        let label = normalize_macro(id);
+
+        match self.labels.entry(label) {
+            Entry::Occupied(entry) => {
+                self.diagnostics.push(Diagnostic::DuplicateLabel { label: from_v32(entry.key()) });
+            },
+            Entry::Vacant(entry) => {
+                entry.insert((id.to_vec(), into_v32(text)));
+            }
+        }
+
+    }
+
+    // a paragraph may be parsed more than once, so it doesn't report duplicates
+    pub fn add_inline_label(&mut self, id: &[u32]) {
+        self.inline_labels.entry(normalize_macro(id)).or_insert_with(|| (id.to_vec(), id.to_vec()));
+    }
+
+    pub fn add_math_errors(&mut self, math: &Math) {
+
+        for error in math.errors().iter() {
+            self.diagnostics.push(Diagnostic::MathError {
+                formula: math.source(),
+                message: error.message.clone(),
+                position: error.position
+            });
+        }
+
+    }
+
     pub fn add_tooltip(&mut self) -> usize {
         self.tooltip_count += 1;
         self.tooltip_count - 1
     }
 
-}
\ No newline at end of file
+}
+
+// the references get the same inverse index, which is `count`, and `count` is increased
+fn add_inverse_index<'a>(inverse_indexes: impl Iterator<Item = &'a mut Vec<usize>>, count: &mut usize) -> usize {
+
+    for inverse_index in inverse_indexes {
+        inverse_index.push(*count);
+    }
+
+    *count += 1;
+
+    *count - 1
+}
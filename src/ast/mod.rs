@@ -7,19 +7,21 @@ mod predicate;
 #[cfg(test)]
 mod testbench;
 
-use crate::inline::{
-    InlineNode,
-    footnote::{footnotes_to_html, Footnote}
-};
-use crate::{collapsible_table_javascript, tooltip_javascript};
+use crate::inline::citation::references_to_html;
+use crate::inline::footnote::{footnotes_to_html, sidenote_to_html, Footnote};
+use crate::inline::macros::tooltip::prefixed_tooltip_javascript;
+use crate::obfuscate::email_javascript;
+use crate::render::csp::{collapsible_table_listener, copy_button_listener, sidebar_listener};
 use crate::container::{
     codefence::html::copy_button_javascript,
-    sidebar::{sidebar_to_html, sidebar_javascript}
+    sidebar::{sidebar_to_html, sidebar_javascript},
+    table::macros::prefixed_collapsible_table_javascript
 };
-use crate::render::render_option::RenderOption;
+use crate::render::render_option::{FootnoteLayout, RenderOption};
 use crate::utils::into_v32;
 use doc_data::DocData;
 use node::Node;
+use std::collections::HashSet;
 
 #[derive(Clone)]
 pub struct AST {
@@ -39,26 +41,45 @@ impl AST {
             return;
         }
 
-        self.nodes.iter_mut().for_each(
-            |node| node.parse_inlines(&self.render_option, &mut self.doc_data)
-        );
+        for (index, node) in self.nodes.iter_mut().enumerate() {
+            node.parse_inlines(&self.render_option, &mut self.doc_data);
+            set_footnote_node_index(&mut self.doc_data, index);
+        }
+
+        // footnotes are parsed in the order of their first references, so that the references inside footnotes also have backlinks
+        // each footnote is parsed only once, even though footnotes reference each other
+        let mut parsed_footnotes = HashSet::new();
+
+        loop {
+            let mut unparsed_footnotes = self.doc_data.footnote_references.iter().filter(
+                |(label, Footnote { inverse_index, .. })| !inverse_index.is_empty() && !parsed_footnotes.contains(*label)
+            ).map(
+                |(label, Footnote { inverse_index, .. })| (label.clone(), inverse_index[0])
+            ).collect::<Vec<(Vec<u32>, usize)>>();
+
+            if unparsed_footnotes.is_empty() {
+                break;
+            }
+
+            unparsed_footnotes.sort_unstable_by_key(|(_, i)| *i);
 
-        // I couldn't find any better way to avoid the borrow checker
-        if !self.doc_data.footnote_references.is_empty() {
-            let mut doc_data_cloned = self.doc_data.clone();
-            let render_option_cloned = self.render_option.clone();
+            for (label, _) in unparsed_footnotes.into_iter() {
+                // I couldn't find any better way to avoid the borrow checker
+                let mut content = std::mem::take(&mut self.doc_data.footnote_references.get_mut(&label).unwrap().content);
 
-            let footnote_parsed = self.doc_data.footnote_references.iter().map(
-                |(label, Footnote { content, .. })| {
-                    let mut footnote_content = content.clone();
-                    footnote_content.parse_raw(&mut doc_data_cloned, &render_option_cloned);
-                    (label.clone(), footnote_content)
+                for node in content.iter_mut() {
+                    node.parse_inlines(&self.render_option, &mut self.doc_data);
                 }
-            ).collect::<Vec<(Vec<u32>, InlineNode)>>();
 
-            for (label, content) in footnote_parsed.into_iter() {
-                let footnote_reference = self.doc_data.footnote_references.get_mut(&label).unwrap();
-                footnote_reference.content = content;
+                let footnote = self.doc_data.footnote_references.get_mut(&label).unwrap();
+                footnote.content = content;
+
+                // footnotes first referenced in this footnote follow this footnote
+                if let Some(node_index) = footnote.node_index {
+                    set_footnote_node_index(&mut self.doc_data, node_index);
+                }
+
+                parsed_footnotes.insert(label);
             }
 
         }
@@ -71,10 +92,12 @@ impl AST {
 
     }
 
-    pub fn to_html(&mut self) -> Vec<u32> {
+    // `render_page` puts the toc and the sidebar wherever its template wants
+    // with `sidebar_slot`, the sidebar is not a part of the content
+    // the toc and the sidebar are empty unless their slots are set
+    pub fn to_html(&mut self, toc_slot: bool, sidebar_slot: bool) -> (Vec<u32>, Vec<u32>, Vec<u32>) {  // (content, toc, sidebar)
         self.parse_inlines();
         let mut buffer = Vec::with_capacity(self.nodes.len());
-        let class_prefix = &self.render_option.class_prefix;
 
         // 1. It renderes the toc_data into a raw html string
 
@@ -91,21 +114,72 @@ impl AST {
         let toc_rendered = toc_buffer.concat();
 
         // 2. It renders all the inner nodes to raw html strings
+        // depending on `footnote_layout`, footnotes are rendered between the nodes
+
+        let footnote_layout = self.render_option.footnote_layout;
+        let footnotes = self.doc_data.footnote_references.values().filter(
+            |footnote| footnote.node_index.is_some()
+        ).cloned().collect::<Vec<Footnote>>();
+        let top_level_header = self.nodes.iter().filter_map(
+            |node| match node {
+                Node::Header { level, .. } => Some(*level),
+                _ => None
+            }
+        ).min();
+        let mut section_start = 0;
+
+        for (index, node) in self.nodes.iter().enumerate() {
+
+            if let (FootnoteLayout::Section, Node::Header { level, .. }) = (footnote_layout, node) {
+
+                if Some(*level) == top_level_header && index > 0 {
+                    let section_footnotes = footnotes_in_range(&footnotes, section_start, index);
+                    buffer.push(footnotes_to_html(&section_footnotes, &toc_rendered, &self.render_option, &mut self.doc_data));
+                    section_start = index;
+                }
+
+            }
 
-        for node in self.nodes.iter() {
             node.to_html(&toc_rendered, &self.render_option, &mut self.doc_data, &mut buffer);
+
+            if let FootnoteLayout::Sidenote = footnote_layout {
+
+                for footnote in footnotes_in_range(&footnotes, index, index + 1).iter() {
+                    buffer.push(sidenote_to_html(footnote, &toc_rendered, &self.render_option, &mut self.doc_data));
+                }
+
+            }
+
         }
 
-        // 3. It renders footnote_references
+        // 3. It renders the rest of the footnotes, and the references of the citations
+
+        match footnote_layout {
+            FootnoteLayout::End => {
+                buffer.push(footnotes_to_html(&footnotes, &toc_rendered, &self.render_option, &mut self.doc_data));
+            },
+            FootnoteLayout::Section => {
+                let section_footnotes = footnotes_in_range(&footnotes, section_start, self.nodes.len());
+                buffer.push(footnotes_to_html(&section_footnotes, &toc_rendered, &self.render_option, &mut self.doc_data));
+            },
+            FootnoteLayout::Sidenote => {}
+        }
 
-        if !self.doc_data.footnote_references.is_empty() {
-            buffer.push(footnotes_to_html(&mut self.doc_data.footnote_references, &toc_rendered, class_prefix));
+        if !self.doc_data.citations.is_empty() {
+            buffer.push(references_to_html(&self.render_option, &self.doc_data));
         }
 
         // 4. It renders a sidebar if exists
 
+        let mut sidebar_rendered = vec![];
+
         if !self.sidebar.is_empty() {
-            buffer.push(sidebar_to_html(&self.sidebar, &toc_rendered, &self.render_option, &mut self.doc_data));
+            sidebar_rendered = sidebar_to_html(&self.sidebar, &toc_rendered, &self.render_option, &mut self.doc_data);
+
+            if !sidebar_slot {
+                buffer.push(std::mem::take(&mut sidebar_rendered));
+            }
+
         }
 
         // 5. It appends scripts if needed
@@ -113,29 +187,49 @@ impl AST {
         let enabel_js_for_sidebar = !self.sidebar.is_empty() && self.render_option.javascript_for_sidebar;
         let enable_js_for_tables = self.doc_data.has_collapsible_table && self.render_option.javascript_for_collapsible_tables;
         let enable_js_for_copy_buttons = !self.doc_data.fenced_code_contents.is_empty() && self.render_option.javascript_for_copy_buttons;
-        let enable_js_for_tooltips = self.doc_data.tooltip_count > 0 && self.render_option.javascript_for_collapsible_tables;
+        let enable_js_for_tooltips = self.doc_data.tooltip_count > 0 && self.render_option.javascript_for_tooltips;
+        let enable_js_for_emails = self.doc_data.has_javascript_email && self.render_option.javascript_for_emails;
 
-        if enable_js_for_copy_buttons || enable_js_for_tables || enable_js_for_tooltips || enabel_js_for_sidebar {
-            buffer.push(vec![60, 115, 99, 114, 105, 112, 116, 62]);  // into_v32("<script>")
+        if enable_js_for_copy_buttons || enable_js_for_tables || enable_js_for_tooltips || enabel_js_for_sidebar || enable_js_for_emails {
+            buffer.push(into_v32(&self.render_option.script_open_tag()));
 
             if self.render_option.xml {
                 buffer.push(vec![47, 42, 60, 33, 91, 67, 68, 65, 84, 65, 91, 42, 47]);  // into_v32("/*<![CDATA[*/")
             }
 
             if enable_js_for_tables {
-                buffer.push(into_v32(&collapsible_table_javascript()));
+                buffer.push(into_v32(&prefixed_collapsible_table_javascript(&self.render_option.class_prefix)));
+
+                if self.render_option.csp {
+                    buffer.push(into_v32(&collapsible_table_listener()));
+                }
+
             }
 
             if enable_js_for_copy_buttons {
                 buffer.push(into_v32(&copy_button_javascript(&self.doc_data.fenced_code_contents)));
+
+                if self.render_option.csp {
+                    buffer.push(into_v32(&copy_button_listener()));
+                }
+
             }
 
             if enable_js_for_tooltips {
-                buffer.push(into_v32(&tooltip_javascript()));
+                buffer.push(into_v32(&prefixed_tooltip_javascript(&self.render_option.class_prefix)));
             }
 
             if enabel_js_for_sidebar {
                 buffer.push(into_v32(&sidebar_javascript()));
+
+                if self.render_option.csp {
+                    buffer.push(into_v32(&sidebar_listener()));
+                }
+
+            }
+
+            if enable_js_for_emails {
+                buffer.push(into_v32(&email_javascript()));
             }
 
             // TODO: if self.doc_data.fenced_code_contents has `']]>'` inside, it wouldn't work
@@ -146,7 +240,45 @@ impl AST {
             buffer.push(vec![60, 47, 115, 99, 114, 105, 112, 116, 62]);  // into_v32("</script>")
         }
 
-        buffer.concat()
+        // 6. It resolves `[[ref]]` macros, now that all the labels are known
+
+        let content = self.resolve_cross_references(buffer.concat());
+
+        let toc_rendered = if toc_slot {
+            self.resolve_cross_references(toc_rendered)
+        } else {
+            vec![]
+        };
+
+        let sidebar_rendered = self.resolve_cross_references(sidebar_rendered);
+
+        (content, toc_rendered, sidebar_rendered)
+    }
+
+}
+
+// footnotes that are referenced but don't have `node_index` yet belong to `nodes[node_index]`
+fn set_footnote_node_index(doc_data: &mut DocData, node_index: usize) {
+
+    for footnote in doc_data.footnote_references.values_mut() {
+
+        if footnote.node_index.is_none() && !footnote.inverse_index.is_empty() {
+            footnote.node_index = Some(node_index);
+        }
+
     }
 
-}
\ No newline at end of file
+}
+
+// footnotes that belong to `nodes[start..end]`, in the order of their first references
+fn footnotes_in_range(footnotes: &[Footnote], start: usize, end: usize) -> Vec<Footnote> {
+    let mut result = footnotes.iter().filter(
+        |footnote| match footnote.node_index {
+            Some(index) => start <= index && index < end,
+            None => false
+        }
+    ).cloned().collect::<Vec<Footnote>>();
+
+    result.sort_unstable_by_key(|footnote| footnote.inverse_index[0]);
+    result
+}
@@ -2,12 +2,15 @@ use super::line::add_br_if_needed;
 use crate::RenderOption;
 use crate::ast::{doc_data::DocData, line::Line};
 use crate::inline::InlineNode;
-use crate::inline::macros::multiline::{MultiLineMacro, MultiLineMacroType};
+use crate::inline::macros::multiline::MultiLineMacro;
 use crate::container::{
     blockquote::Blockquote,
     codefence::FencedCode,
+    definition_list::DefinitionList,
+    figure::Figure,
     header::normalize_header,
     list::List,
+    math_block::MathBlock,
     table::Table,
 };
 use crate::utils::into_v32;
@@ -26,6 +29,9 @@ pub enum Node {
     Table(Table),
     List(List),
     Blockquote(Blockquote),
+    DefinitionList(DefinitionList),
+    Figure(Figure),
+    MathBlock(MathBlock),
     ThematicBreak,
     MultiLineMacro(MultiLineMacro),
     Empty
@@ -72,6 +78,7 @@ impl Node {
         copy_button: bool,
         id: &Option<Vec<u32>>,
         classes: &Vec<Vec<u32>>,
+        caption: &Option<Vec<u32>>,
         index: usize
     ) -> Node {
         Node::FencedCode(FencedCode::new(
@@ -82,6 +89,7 @@ impl Node {
             copy_button,
             id.clone(),
             classes.clone(),
+            caption.clone(),
             index
         ))
     }
@@ -98,38 +106,50 @@ impl Node {
         Node::Blockquote(Blockquote::from_lines(lines))
     }
 
-    pub fn new_macro(line: &Line, doc_data: &mut DocData) -> Node {
-        Node::MultiLineMacro(MultiLineMacro::from_line(line, doc_data))
+    pub fn new_macro(line: &Line, doc_data: &mut DocData, render_option: &RenderOption) -> Node {
+        Node::MultiLineMacro(MultiLineMacro::from_line(line, doc_data, render_option))
     }
 
-    pub fn new_math_ml(lines: &Vec<Line>) -> Node {
-        Node::MultiLineMacro(
-            MultiLineMacro {
-                macro_type: MultiLineMacroType::Math(
-                    lines.iter().map(
-                        |line| line.to_raw()
-                    ).collect::<Vec<Vec<u32>>>().join(&['\n' as u32][..])
-                ),
-                is_closing: false,
-            },
-        )
+    pub fn new_math_block(lines: &[Line], arguments: &[Vec<Vec<u32>>]) -> Node {
+        Node::MathBlock(MathBlock::new(
+            lines.iter().map(
+                |line| line.to_raw()
+            ).collect::<Vec<Vec<u32>>>().join(&['\n' as u32][..]),
+            arguments
+        ))
     }
 
     pub fn parse_inlines(&mut self, render_option: &RenderOption, doc_data: &mut DocData) {
 
         match self {
-            Node::Paragraph { content } | Node::Header { content, .. } => { content.parse_raw(doc_data, render_option); },
+            Node::Paragraph { content } => {
+                content.parse_raw(doc_data, render_option);
+
+                if let Some(mut figure) = Figure::from_paragraph(content) {
+                    figure.parse_inlines(doc_data, render_option);
+                    *self = Node::Figure(figure);
+                }
+
+            },
+            Node::Header { content, .. } => { content.parse_raw(doc_data, render_option); },
             Node::Table(table) => { table.parse_inlines(doc_data, render_option); },
             Node::List(list) => { list.parse_inlines(doc_data, render_option); },
             Node::Blockquote(blockquote) => { blockquote.parse_inlines(doc_data, render_option); },
-            Node::Empty | Node::ThematicBreak | Node::MultiLineMacro(_) => {},
+            Node::DefinitionList(definition_list) => { definition_list.parse_inlines(doc_data, render_option); },
+            Node::Figure(figure) => { figure.parse_inlines(doc_data, render_option); },
+            Node::MathBlock(math_block) => { math_block.parse_inlines(doc_data, render_option); },
+            Node::MultiLineMacro(multiline_macro) => { multiline_macro.macro_type.parse_inlines(doc_data, render_option); },
+            Node::Empty | Node::ThematicBreak => {},
 
             // TODO
             // this branch is ugly...
             // it doesn't `parse_inline` inside the `parse_inlines` function
             // but this is the only point where the `FencedCode` instances and `doc_data` meet
             // I should call this function when the fenced_codes are initialized, but `doc_data` doesn't exist at that timing
-            Node::FencedCode(fenced_code) => { doc_data.add_fenced_code_content(fenced_code); },
+            Node::FencedCode(fenced_code) => {
+                doc_data.add_fenced_code_content(fenced_code);
+                fenced_code.parse_inlines(doc_data, render_option);
+            },
         }
 
     }
@@ -153,7 +173,7 @@ impl Node {
                 );
             },
             Node::Table(table) => {
-                buffer.push(table.to_html(toc_rendered, class_prefix));
+                buffer.push(table.to_html(toc_rendered, render_option));
             }
             Node::List(list) => {
                 buffer.push(list.to_html(toc_rendered, class_prefix));
@@ -161,6 +181,15 @@ impl Node {
             Node::Blockquote(blockquote) => {
                 buffer.push(blockquote.to_html(toc_rendered, class_prefix));
             }
+            Node::DefinitionList(definition_list) => {
+                buffer.push(definition_list.to_html(toc_rendered, render_option, doc_data));
+            }
+            Node::Figure(figure) => {
+                buffer.push(figure.to_html(toc_rendered, class_prefix));
+            }
+            Node::MathBlock(math_block) => {
+                buffer.push(math_block.to_html(class_prefix));
+            }
             Node::MultiLineMacro(multiline_macro) => {
                 buffer.push(multiline_macro.to_html(toc_rendered, render_option, doc_data));
             }
@@ -187,7 +216,7 @@ impl Node {
                 );
             },
             Node::FencedCode(fenced_code) => {
-                buffer.push(fenced_code.to_html(class_prefix));
+                buffer.push(fenced_code.to_html(toc_rendered, render_option));
             }
             Node::Empty => {}
         }
@@ -1,17 +1,18 @@
 use super::{AST, doc_data::DocData, line::Line, node::Node};
 use crate::inline::{
-    footnote::{Footnote, predicate::is_valid_footnote_label},
-    InlineNode,
+    footnote::{Footnote, predicate::is_valid_footnote_label, read_footnote_lines},
     link::{normalize_link_label, predicate::read_link_reference},
-    macros::{get_macro_name, parse_arguments, predicate::read_macro, MACROS, multiline::{MultiLineMacro, MultiLineMacroType}},
+    macros::{get_macro_name, parse_arguments, predicate::read_macro, get_macro, multiline::{MultiLineMacro, MultiLineMacroType}},
 };
 use crate::container::{
+    blockquote::{read_alert_marker, remove_blockquote_marker},
     codefence::read_code_fence_info,
+    definition_list::DefinitionList,
     header::parse_header,
     table::{count_cells, count_delimiter_cells},
 };
 use crate::render::render_option::RenderOption;
-use crate::utils::{from_v32, into_v32};
+use crate::utils::{drop_while, from_v32, into_v32};
 use std::collections::HashSet;
 
 #[derive(PartialEq, Debug)]
@@ -26,6 +27,7 @@ pub enum ParseState {  // this enum is only used internally by `AST::from_lines`
         is_tilde_fence: bool,
         id: Option<Vec<u32>>,
         classes: Vec<Vec<u32>>,
+        caption: Option<Vec<u32>>,
         index: usize,  // index is used when making `copy to clipboard` buttons
     },
     IndentedCodeBlock,
@@ -36,6 +38,7 @@ pub enum ParseState {  // this enum is only used internally by `AST::from_lines`
     },
     Math {  // multiline [[math]] macro
         end_index: usize,
+        arguments: Vec<Vec<Vec<u32>>>,
     },
     Blockquote,
     List,
@@ -45,13 +48,22 @@ pub enum ParseState {  // this enum is only used internally by `AST::from_lines`
 impl AST {
 
     pub fn from_lines(lines: Vec<Line>, options: &RenderOption) -> AST {
+        AST::from_nested_lines(lines, options, DocData::default(), &mut 0, &mut 0)
+    }
+
+    // blocks inside another container (eg. definition lists) share `doc_data` and the counters with the outer document,
+    // so that the headers, the references and the indexes of tables and code blocks don't collide
+    pub fn from_nested_lines(
+        mut lines: Vec<Line>,
+        options: &RenderOption,
+        mut doc_data: DocData,
+        table_count: &mut usize,
+        fenced_code_count: &mut usize
+    ) -> AST {
         let mut curr_nodes = Vec::with_capacity(lines.len());
         let mut curr_lines = vec![];
         let mut curr_parse_state = ParseState::None;
-        let mut table_count = 0;
-        let mut fenced_code_count = 0;
         let mut sidebar = vec![];
-        let mut doc_data = DocData::default();
 
         let mut has_multiline_macro = false;
 
@@ -63,14 +75,14 @@ impl AST {
             match &curr_parse_state {
                 ParseState::CodeFence { code_fence_size, is_tilde_fence, .. } => {
                     if lines[index].is_code_fence_end() {
-                        let (end_code_fence_size, is_tilde_end_fence) = match read_code_fence_info(&lines[index], fenced_code_count) {
+                        let (end_code_fence_size, is_tilde_end_fence) = match read_code_fence_info(&lines[index], *fenced_code_count) {
                             ParseState::CodeFence { code_fence_size, is_tilde_fence, .. } => (code_fence_size, is_tilde_fence),
                             _ => unreachable!()
                         };
 
                         if end_code_fence_size >= *code_fence_size && is_tilde_end_fence == *is_tilde_fence {
                             add_curr_node_to_ast(&mut curr_nodes, &mut curr_lines, &mut curr_parse_state);
-                            fenced_code_count += 1;
+                            *fenced_code_count += 1;
                             index += 1;
                             continue;
                         }
@@ -95,7 +107,7 @@ impl AST {
                 ParseState::Paragraph | ParseState::None => {
                     if macro_closing_indexes.contains(&index) {
                         add_curr_node_to_ast(&mut curr_nodes, &mut curr_lines, &mut curr_parse_state);
-                        curr_nodes.push(Node::new_macro(&lines[index], &mut doc_data));
+                        curr_nodes.push(Node::new_macro(&lines[index], &mut doc_data, options));
                         macro_closing_indexes.remove(&index);
                         index += 1;
                         continue;
@@ -111,9 +123,22 @@ impl AST {
                         curr_lines.push(lines[index].try_sub_indent(4));
                     }
 
+                    else if options.dollar_math && lines[index].is_dollar_math_delimiter()
+                        && lines[(index + 1)..].iter().any(|line| line.is_dollar_math_delimiter())
+                    {
+                        add_curr_node_to_ast(&mut curr_nodes, &mut curr_lines, &mut curr_parse_state);
+                        curr_parse_state = ParseState::Math {
+                            end_index: index + 1 + lines[(index + 1)..].iter().position(|line| line.is_dollar_math_delimiter()).unwrap(),
+                            arguments: vec![
+                                vec![into_v32("math")],
+                                vec![into_v32("latex")]
+                            ]
+                        };
+                    }
+
                     else if lines[index].is_code_fence_begin() {
                         add_curr_node_to_ast(&mut curr_nodes, &mut curr_lines, &mut curr_parse_state);
-                        curr_parse_state = read_code_fence_info(&lines[index], fenced_code_count);
+                        curr_parse_state = read_code_fence_info(&lines[index], *fenced_code_count);
                     }
 
                     else if lines[index].is_header() {
@@ -128,6 +153,14 @@ impl AST {
                         curr_nodes.push(Node::ThematicBreak);
                     }
 
+                    // `Term\n\n: Definition`: the empty line doesn't end the terms, and the definitions are loose
+                    else if curr_parse_state == ParseState::Paragraph && lines[index].is_empty()
+                        && index + 1 < lines.len() && lines[index + 1].is_definition()
+                    {
+                        index += 1;
+                        continue;
+                    }
+
                     else if lines[index].is_empty() {
                         if !curr_lines.is_empty() {
                             add_curr_node_to_ast(&mut curr_nodes, &mut curr_lines, &mut curr_parse_state);
@@ -161,7 +194,7 @@ impl AST {
                             let header_lines = header_lines.into_iter().map(|line| (*line).clone()).collect::<Vec<Line>>();
                             let alignments = lines[header_end_index].clone();
 
-                            curr_parse_state = ParseState::Table { header_lines, alignments, index: table_count };
+                            curr_parse_state = ParseState::Table { header_lines, alignments, index: *table_count };
                             index = header_end_index;
                         }
 
@@ -172,6 +205,38 @@ impl AST {
                         }
                     }
 
+                    // `> [!NOTE]` is rewritten to `[[note]] ... [[/note]]`, so that its content is parsed like the other blocks
+                    else if let Some(admonition) = read_alert_marker(&lines[index]) {
+                        if !curr_lines.is_empty() {
+                            add_curr_node_to_ast(&mut curr_nodes, &mut curr_lines, &mut curr_parse_state);
+                        }
+
+                        let mut end_index = index + 1;
+
+                        while end_index < lines.len() && lines[end_index].is_blockquote() {
+                            end_index += 1;
+                        }
+
+                        let mut inner_lines = lines[(index + 1)..end_index].iter().map(remove_blockquote_marker).collect::<Vec<Line>>();
+
+                        // the empty line closes the last block of the content (lists, tables, blockquotes...)
+                        inner_lines.push(Line::new(vec![], 0));
+                        inner_lines.push(Line::new(admonition.get_closing_macro(), 0));
+                        lines.splice((index + 1)..end_index, inner_lines);
+
+                        // 2 lines are inserted
+                        macro_closing_indexes = macro_closing_indexes.into_iter().map(
+                            |closing_index| if closing_index > index { closing_index + 2 } else { closing_index }
+                        ).collect();
+                        macro_closing_indexes.insert(end_index + 1);
+
+                        curr_nodes.push(Node::MultiLineMacro(MultiLineMacro {
+                            macro_type: MultiLineMacroType::Admonition(admonition),
+                            is_closing: false,
+                        }));
+                        has_multiline_macro = true;
+                    }
+
                     else if lines[index].is_blockquote() {
                         if !curr_lines.is_empty() {
                             add_curr_node_to_ast(&mut curr_nodes, &mut curr_lines, &mut curr_parse_state);
@@ -202,6 +267,21 @@ impl AST {
                         curr_lines.push(lines[index].clone());
                     }
 
+                    // lines of the current paragraph are terms of the definition list
+                    else if curr_parse_state == ParseState::Paragraph && lines[index].is_definition() {
+                        let (definition_list, end_index) = DefinitionList::from_lines(
+                            &curr_lines, &lines, index,
+                            options, &mut doc_data,
+                            table_count, fenced_code_count
+                        );
+
+                        curr_nodes.push(Node::DefinitionList(definition_list));
+                        curr_lines = vec![];
+                        curr_parse_state = ParseState::None;
+                        index = end_index;
+                        continue;
+                    }
+
                     // it cannot interrupt a paragraph
                     else if curr_parse_state == ParseState::None && lines[index].is_link_or_footnote_reference_definition() {
                         let (link_label, link_destination) = read_link_reference(&lines[index].content);
@@ -209,6 +289,15 @@ impl AST {
                         if is_valid_footnote_label(&link_label) {
                             let footnote_label = normalize_link_label(&link_label);
 
+                            // `[^a]: ` -> 5 characters
+                            let first_line = drop_while(&lines[index].content[(link_label.len() + 3)..], ' ' as u32);
+                            let (footnote_lines, end_index) = read_footnote_lines(&lines, index, &first_line);
+                            let tight = !footnote_lines.iter().any(|line| line.is_empty());
+
+                            // the inner blocks are parsed like a separate document
+                            let footnote_ast = AST::from_nested_lines(footnote_lines, options, std::mem::take(&mut doc_data), table_count, fenced_code_count);
+                            doc_data = footnote_ast.doc_data;
+
                             let footnote_index = match doc_data.footnote_references.get(&footnote_label) {
                                 Some(f) => f.index,
                                 None => doc_data.footnote_references.len()
@@ -216,12 +305,11 @@ impl AST {
 
                             doc_data.footnote_references.insert(
                                 footnote_label,
-                                Footnote {
-                                    index: footnote_index,
-                                    inverse_index: vec![],
-                                    content: InlineNode::Raw(link_destination)
-                                }
+                                Footnote::new(footnote_index, footnote_ast.nodes, tight)
                             );
+
+                            index = end_index;
+                            continue;
                         }
 
                         else {
@@ -235,9 +323,9 @@ impl AST {
                         let macro_arguments = parse_arguments(&macro_content);
                         let macro_name = get_macro_name(&macro_arguments);
 
-                        match MACROS.get(&macro_name) {
+                        match get_macro(&macro_name, options) {
                             // if it has a closing, find its partner
-                            Some(macro_) if macro_.has_closing && macro_.is_valid(&macro_arguments) => {
+                            Some(macro_) if macro_.has_closing && macro_.is_valid(&macro_arguments, options) => {
                                 let mut macro_closing_index = index + 1;
 
                                 let mut inner_macro_stack = vec![macro_.clone()];
@@ -255,12 +343,12 @@ impl AST {
 
                                                 // into_v32("math") -> [109, 97, 116, 104]
                                                 if macro_name == &[109, 97, 116, 104] {
-                                                    curr_parse_state = ParseState::Math { end_index: macro_closing_index };
+                                                    curr_parse_state = ParseState::Math { end_index: macro_closing_index, arguments: macro_arguments };
                                                 }
 
                                                 else {
                                                     macro_closing_indexes.insert(macro_closing_index);
-                                                    curr_nodes.push(Node::new_macro(&lines[index], &mut doc_data));
+                                                    curr_nodes.push(Node::new_macro(&lines[index], &mut doc_data, options));
                                                     curr_parse_state = ParseState::Paragraph;
                                                 }
 
@@ -279,10 +367,10 @@ impl AST {
                                         let curr_macro_arguments = parse_arguments(&curr_macro);
                                         let curr_macro_name = get_macro_name(&curr_macro_arguments);
 
-                                        match MACROS.get(&curr_macro_name) {
+                                        match get_macro(&curr_macro_name, options) {
                                             Some(inner_macro)
                                                 if inner_macro.has_closing
-                                                    && inner_macro.is_valid(&curr_macro_arguments) =>
+                                                    && inner_macro.is_valid(&curr_macro_arguments, options) =>
                                             {
                                                 inner_macro_stack.push(inner_macro.clone());
                                                 curr_closing_macro = inner_macro.get_closing_macro();
@@ -292,8 +380,8 @@ impl AST {
                                                     let possibly_another_macro = curr_macro[1..].to_vec();
 
                                                     // it assumes that all the closing macros have the same form: `'/' + macro_name`
-                                                    match MACROS.get(&possibly_another_macro) {
-                                                        // it's always valid because I checked `MACROS.get(macro)`, not `MACROS.get(macro_name)`
+                                                    match get_macro(&possibly_another_macro, options) {
+                                                        // it's always valid because I checked `get_macro(macro)`, not `get_macro(macro_name)`
                                                         // if a valid macro is found here, that means the very first macro is not properly closed
                                                         Some(another_macro) if another_macro.has_closing => {
                                                             break;
@@ -323,7 +411,7 @@ impl AST {
                         curr_parse_state = ParseState::Paragraph;
                     }
                 },
-                ParseState::Math { end_index } => if index == *end_index {
+                ParseState::Math { end_index, .. } => if index == *end_index {
                     add_curr_node_to_ast(&mut curr_nodes, &mut curr_lines, &mut curr_parse_state);
                 }
 
@@ -337,7 +425,7 @@ impl AST {
 
                     else {
                         add_curr_node_to_ast(&mut curr_nodes, &mut curr_lines, &mut curr_parse_state);
-                        table_count += 1;
+                        *table_count += 1;
                         continue;
                     }
                 },
@@ -415,8 +503,8 @@ fn add_curr_node_to_ast(curr_nodes: &mut Vec<Node>, curr_lines: &mut Vec<Line>,
             *curr_lines = vec![];
             *curr_parse_state = ParseState::None;
         },
-        ParseState::CodeFence { language, line_num, highlights, copy_button, id, classes, index, .. } => {
-            curr_nodes.push(Node::new_code_fence(curr_lines, &language, &line_num, &highlights, *copy_button, &id, &classes, *index));
+        ParseState::CodeFence { language, line_num, highlights, copy_button, id, classes, caption, index, .. } => {
+            curr_nodes.push(Node::new_code_fence(curr_lines, &language, &line_num, &highlights, *copy_button, &id, &classes, &caption, *index));
             *curr_lines = vec![];
             *curr_parse_state = ParseState::None;
         },
@@ -446,7 +534,7 @@ fn add_curr_node_to_ast(curr_nodes: &mut Vec<Node>, curr_lines: &mut Vec<Line>,
             // empty code blocks are ignored
             if !curr_lines.is_empty() {
                 // a code fence without any decoration
-                curr_nodes.push(Node::new_code_fence(curr_lines, &vec![], &None, &vec![], false, &None, &vec![], usize::MAX));
+                curr_nodes.push(Node::new_code_fence(curr_lines, &vec![], &None, &vec![], false, &None, &vec![], &None, usize::MAX));
                 *curr_lines = vec![];
             }
 
@@ -455,16 +543,8 @@ fn add_curr_node_to_ast(curr_nodes: &mut Vec<Node>, curr_lines: &mut Vec<Line>,
         ParseState::None => if !curr_lines.is_empty() {
             panic!("What should I do?");
         },
-        ParseState::Math { .. } => {
-            curr_nodes.push(Node::new_math_ml(curr_lines));
-
-            // since the above line only generates an opening macro, it adds a closing one
-            curr_nodes.push(Node::MultiLineMacro(
-                MultiLineMacro {
-                    macro_type: MultiLineMacroType::Math(vec![91, 91, 47, 109, 97, 116, 104, 93, 93]),  // into_v32("[[/math]]")
-                    is_closing: true,
-                }
-            ));
+        ParseState::Math { arguments, .. } => {
+            curr_nodes.push(Node::new_math_block(curr_lines, arguments));
             *curr_lines = vec![];
             *curr_parse_state = ParseState::None;
         }
@@ -502,6 +582,16 @@ fn collect_nodes_for_multiline_macros(nodes: &mut Vec<Node>, sidebar: &mut Vec<N
                     else {
                         // `nodes[index - 1]` must be the opening multiline-macro 
                         nodes[index - 1].set_inner_nodes(stack_of_nodes.pop().unwrap());
+
+                        // if it's nested inside another macro, the opening one also belongs to the outer macro
+                        if !stack_of_nodes.is_empty() {
+                            let last_index = stack_of_nodes.len() - 1;
+                            let mut node = nodes.remove(index - 1);
+                            node.parse_inlines(render_option, doc_data);
+
+                            stack_of_nodes[last_index].push(node);
+                            index -= 1;
+                        }
                     }
                 }
 
@@ -26,6 +26,12 @@ impl Line {
         self.content.is_empty()
     }
 
+    // `$$`, with `RenderOption.dollar_math`
+    #[inline]
+    pub fn is_dollar_math_delimiter(&self) -> bool {
+        self.indent < 4 && *self.content == [36, 36]  // into_v32("$$")
+    }
+
     #[inline]
     pub fn is_code_fence_begin(&self) -> bool {
         self.is_code_fence() && is_valid_info_string(&drop_while(&self.content[3..], self.content[0]))
@@ -143,6 +149,14 @@ impl Line {
         }
     }
 
+    // `: definition`
+    #[inline]
+    pub fn is_definition(&self) -> bool {
+        self.indent < 4 && self.content.len() > 2
+        && self.content[0] == ':' as u32 && self.content[1] == ' ' as u32
+        && self.content[2..].iter().any(|c| *c != ' ' as u32)
+    }
+
     #[inline]
     pub fn is_link_or_footnote_reference_definition(&self) -> bool {
         self.indent < 4 && self.content.len() > 4
@@ -332,13 +332,13 @@ lines\r\n
 <h1 id=\"escape-rules\">Escape rules</h1>
 
 <p>
-    <math xmlns=\"http://www.w3.org/1998/Math/MathML\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"&gt;\">
         <mtext>&gt;</mtext>
     </math>
 </p>
 
 <p>
-    <math xmlns=\"http://www.w3.org/1998/Math/MathML\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"`&gt;`\">
         <mo>`</mo>
         <mtext>&gt;</mtext>
         <mo>`</mo>
@@ -350,13 +350,13 @@ lines\r\n
 </p>
 
 <p>
-    <math xmlns=\"http://www.w3.org/1998/Math/MathML\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"&gt;\">
         <mo>&gt;</mo>
     </math>
 </p>
 
 <p>
-    <math xmlns=\"http://www.w3.org/1998/Math/MathML\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"`&gt;`\">
         <mo>`</mo>
         <mo>&gt;</mo>
         <mo>`</mo>
@@ -368,13 +368,13 @@ lines\r\n
 </p>
 
 <p>
-    <math xmlns=\"http://www.w3.org/1998/Math/MathML\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"&amp;gt;\">
         <mtext>&amp;gt;</mtext>
     </math>
 </p>
 
 <p>
-    <math xmlns=\"http://www.w3.org/1998/Math/MathML\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"`&amp;gt;`\">
         <mo>`</mo>
         <mtext>&amp;gt;</mtext>
         <mo>`</mo>
@@ -386,7 +386,7 @@ lines\r\n
 </p>
 
 <p>
-    <math xmlns=\"http://www.w3.org/1998/Math/MathML\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"&amp;&gt;;\">
         <mo>&amp;</mo>
         <mo>&#62;</mo>
         <mo>;</mo>
@@ -394,7 +394,7 @@ lines\r\n
 </p>
 
 <p>
-    <math xmlns=\"http://www.w3.org/1998/Math/MathML\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"`&amp;&gt;;`\">
         <mo>`</mo>
         <mo>&amp;</mo>
         <mo>&#62;</mo>
@@ -408,13 +408,13 @@ lines\r\n
 </p>
 
 <p>
-    <math xmlns=\"http://www.w3.org/1998/Math/MathML\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"&amp;amp;gt;\">
         <mtext>&amp;amp;gt;</mtext>
     </math>
 </p>
 
 <p>
-    <math xmlns=\"http://www.w3.org/1998/Math/MathML\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"`&amp;amp;gt;`\">
         <mo>`</mo>
         <mtext>&amp;amp;gt;</mtext>
         <mo>`</mo>
@@ -426,7 +426,7 @@ lines\r\n
 </p>
 
 <p>
-    <math xmlns=\"http://www.w3.org/1998/Math/MathML\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"&amp;amp;&gt;;\">
         <mo>&amp;</mo>
         <mi>amp</mi>
         <mo>;</mo>
@@ -436,7 +436,7 @@ lines\r\n
 </p>
 
 <p>
-    <math xmlns=\"http://www.w3.org/1998/Math/MathML\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"`&amp;amp;&gt;;`\">
         <mo>`</mo>
         <mo>&amp;</mo>
         <mi>amp</mi>
@@ -452,7 +452,7 @@ lines\r\n
 </p>
 
 <p>
-    <math xmlns=\"http://www.w3.org/1998/Math/MathML\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"5 &gt; 4\">
         <mn>5</mn>
         <mo>&gt;</mo>
         <mn>4</mn>
@@ -460,7 +460,7 @@ lines\r\n
 </p>
 
 <p>
-    <math xmlns=\"http://www.w3.org/1998/Math/MathML\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"`5 \\&gt; 4`\">
         <mo>`</mo>
         <mn>5</mn>
         <mo>\\</mo>
@@ -475,7 +475,7 @@ lines\r\n
 </p>
 
 <p>
-    <math xmlns=\"http://www.w3.org/1998/Math/MathML\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"&lt;a &gt; a ?&lt;/ a &gt;\">
         <mo>&lt;</mo>
         <mi>a</mi>
         <mo>&gt;</mo>
@@ -489,7 +489,7 @@ lines\r\n
 </p>
 
 <p>
-    <math xmlns=\"http://www.w3.org/1998/Math/MathML\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"`&lt;a &gt; a ?&lt;/ a &gt;`\">
         <mo>`</mo>
         <mo>&lt;</mo>
         <mi>a</mi>
@@ -640,122 +640,63 @@ sqrt{3 + 3 + 3} = 3 br br
 ", "
 <h1 id=\"multiline-math-macro\">Multiline Math macro</h1>
 
-<math xmlns=\"http://www.w3.org/1998/Math/MathML\">
-    <msqrt>
-        <mn>2</mn>
-        <mo>+</mo>
-        <mn>2</mn>
-    </msqrt>
-    <mo>=</mo>
-    <mn>2</mn>
-    <mspace linebreak=\"newline\"/>
-    <mspace linebreak=\"newline\"/>
-    <msqrt>
-        <mn>3</mn>
-        <mo>+</mo>
-        <mn>3</mn>
-        <mo>+</mo>
-        <mn>3</mn>
-    </msqrt>
-    <mo>=</mo>
-    <mn>3</mn>
-    <mspace linebreak=\"newline\"/>
-    <mspace linebreak=\"newline\"/>
-</math>
+<div class=\"math-block\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"block\" alttext=\"sqrt(2 + 2) = 2; sqrt(3 + 3 + 3) = 3\">
+        <mtable displaystyle=\"true\" columnalign=\"right\">
+            <mtr>
+                <mtd><msqrt><mn>2</mn><mo>+</mo><mn>2</mn></msqrt><mo>=</mo><mn>2</mn></mtd>
+            </mtr>
+            <mtr>
+                <mtd><msqrt><mn>3</mn><mo>+</mo><mn>3</mn><mo>+</mo><mn>3</mn></msqrt><mo>=</mo><mn>3</mn></mtd>
+            </mtr>
+        </mtable>
+    </math>
+</div>
 
 <h2 id=\"nested-multiline-math-macros\">nested multiline math macros</h2>
 
-<math xmlns=\"http://www.w3.org/1998/Math/MathML\">
-    <msqrt>
-        <mn>2</mn>
-        <mo>+</mo>
-        <mn>2</mn>
-    </msqrt>
-    <mo>=</mo>
-    <mn>2</mn>
-    <mspace linebreak=\"newline\"/>
-    <mspace linebreak=\"newline\"/>
-    <msqrt>
-        <mn>3</mn>
-        <mo>+</mo>
-        <mn>3</mn>
-        <mo>+</mo>
-        <mn>3</mn>
-    </msqrt>
-    <mo>=</mo>
-    <mn>3</mn>
-    <mspace linebreak=\"newline\"/>
-    <mspace linebreak=\"newline\"/>
-    <mo>[</mo><mo>[</mo><mi>math</mi><mo>]</mo><mo>]</mo>
-    <msqrt>
-        <mn>2</mn>
-        <mo>+</mo>
-        <mn>2</mn>
-    </msqrt>
-    <mo>=</mo>
-    <mn>2</mn>
-    <mspace linebreak=\"newline\"/>
-    <mspace linebreak=\"newline\"/>
-    <msqrt>
-        <mn>3</mn>
-        <mo>+</mo>
-        <mn>3</mn>
-        <mo>+</mo>
-        <mn>3</mn>
-    </msqrt>
-    <mo>=</mo>
-    <mn>3</mn>
-    <mspace linebreak=\"newline\"/>
-    <mspace linebreak=\"newline\"/>
-    <mo>[</mo><mo>[</mo><mo>/</mo><mi>math</mi><mo>]</mo><mo>]</mo>
-    <msqrt>
-        <mn>2</mn>
-        <mo>+</mo>
-        <mn>2</mn>
-    </msqrt>
-    <mo>=</mo>
-    <mn>2</mn>
-    <mspace linebreak=\"newline\"/>
-    <mspace linebreak=\"newline\"/>
-    <msqrt>
-        <mn>3</mn>
-        <mo>+</mo>
-        <mn>3</mn>
-        <mo>+</mo>
-        <mn>3</mn>
-    </msqrt>
-    <mo>=</mo>
-    <mn>3</mn>
-    <mspace linebreak=\"newline\"/>
-    <mspace linebreak=\"newline\"/>
-</math>
+<div class=\"math-block\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"block\" alttext=\"sqrt(2 + 2) = 2; sqrt(3 + 3 + 3) = 3; [[math]]sqrt(2 + 2) = 2; sqrt(3 + 3 + 3) = 3; [[/math]]sqrt(2 + 2) = 2; sqrt(3 + 3 + 3) = 3\">
+        <mtable displaystyle=\"true\" columnalign=\"right\">
+            <mtr>
+                <mtd><msqrt><mn>2</mn><mo>+</mo><mn>2</mn></msqrt><mo>=</mo><mn>2</mn></mtd>
+            </mtr>
+            <mtr>
+                <mtd><msqrt><mn>3</mn><mo>+</mo><mn>3</mn><mo>+</mo><mn>3</mn></msqrt><mo>=</mo><mn>3</mn></mtd>
+            </mtr>
+            <mtr>
+                <mtd><mo>[</mo><mo>[</mo><mi>math</mi><mo>]</mo><mo>]</mo><msqrt><mn>2</mn><mo>+</mo><mn>2</mn></msqrt><mo>=</mo><mn>2</mn></mtd>
+            </mtr>
+            <mtr>
+                <mtd><msqrt><mn>3</mn><mo>+</mo><mn>3</mn><mo>+</mo><mn>3</mn></msqrt><mo>=</mo><mn>3</mn></mtd>
+            </mtr>
+            <mtr>
+                <mtd><mo>[</mo><mo>[</mo><mo>/</mo><mi>math</mi><mo>]</mo><mo>]</mo><msqrt><mn>2</mn><mo>+</mo><mn>2</mn></msqrt><mo>=</mo><mn>2</mn></mtd>
+            </mtr>
+            <mtr>
+                <mtd><msqrt><mn>3</mn><mo>+</mo><mn>3</mn><mo>+</mo><mn>3</mn></msqrt><mo>=</mo><mn>3</mn></mtd>
+            </mtr>
+        </mtable>
+    </math>
+</div>
 
 <h2 id=\"another-macro-inside-a-math-macro\">another macro inside a math macro</h2>
 
-<math xmlns=\"http://www.w3.org/1998/Math/MathML\">
-    <mo>[</mo><mo>[</mo><mi>center</mi><mo>]</mo><mo>]</mo>
-    <msqrt>
-        <mn>2</mn>
-        <mo>+</mo>
-        <mn>2</mn>
-    </msqrt>
-    <mo>=</mo>
-    <mn>2</mn>
-    <mspace linebreak=\"newline\"/>
-    <mspace linebreak=\"newline\"/>
-    <msqrt>
-        <mn>3</mn>
-        <mo>+</mo>
-        <mn>3</mn>
-        <mo>+</mo>
-        <mn>3</mn>
-    </msqrt>
-    <mo>=</mo>
-    <mn>3</mn>
-    <mspace linebreak=\"newline\"/>
-    <mspace linebreak=\"newline\"/>
-    <mo>[</mo><mo>[</mo><mo>/</mo><mi>center</mi><mo>]</mo><mo>]</mo>
-</math>
+<div class=\"math-block\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"block\" alttext=\"[[center]]sqrt(2 + 2) = 2; sqrt(3 + 3 + 3) = 3; [[/center]]\">
+        <mtable displaystyle=\"true\" columnalign=\"right\">
+            <mtr>
+                <mtd><mo>[</mo><mo>[</mo><mi>center</mi><mo>]</mo><mo>]</mo><msqrt><mn>2</mn><mo>+</mo><mn>2</mn></msqrt><mo>=</mo><mn>2</mn></mtd>
+            </mtr>
+            <mtr>
+                <mtd><msqrt><mn>3</mn><mo>+</mo><mn>3</mn><mo>+</mo><mn>3</mn></msqrt><mo>=</mo><mn>3</mn></mtd>
+            </mtr>
+            <mtr>
+                <mtd><mo>[</mo><mo>[</mo><mo>/</mo><mi>center</mi><mo>]</mo><mo>]</mo></mtd>
+            </mtr>
+        </mtable>
+    </math>
+</div>
 "), ("
 [[tooltip = foo]] nested tooltips? [[tooltip = bar]] nested tooltips...?? [[/tooltip]] [[/tooltip]]
 
@@ -1117,6 +1058,7 @@ const IS_UNORDERED_LIST: u32 = 256;
 const IS_ORDERED_LIST: u32 = 512;
 const IS_LINK_OR_FOOTNOTE_REFERENCE_DEFINITION: u32 = 1024;
 const IS_MULTILINE_MACRO: u32 = 2048;
+const IS_DEFINITION: u32 = 4096;
 
 fn line_samples() -> Vec<(Line, u32)> {
     vec![
@@ -1149,6 +1091,11 @@ fn line_samples() -> Vec<(Line, u32)> {
         (Line::from_raw_string("- "), IS_UNORDERED_LIST),
         (Line::from_raw_string("- abc"), IS_UNORDERED_LIST),
         (Line::from_raw_string("-abc"), IS_PARAGRAPH),
+        (Line::from_raw_string(": definition"), IS_DEFINITION),
+        (Line::from_raw_string("   :   definition"), IS_DEFINITION),
+        (Line::from_raw_string("    : definition"), IS_PARAGRAPH),
+        (Line::from_raw_string(":definition"), IS_PARAGRAPH),
+        (Line::from_raw_string(":   "), IS_PARAGRAPH),
     ]
 }
 
@@ -1170,7 +1117,8 @@ fn line_predicate_test() {
         | line.is_unordered_list() as u32 * IS_UNORDERED_LIST
         | line.is_ordered_list() as u32 * IS_ORDERED_LIST
         | line.is_link_or_footnote_reference_definition() as u32 * IS_LINK_OR_FOOTNOTE_REFERENCE_DEFINITION
-        | line.is_multiline_macro() as u32 * IS_MULTILINE_MACRO;
+        | line.is_multiline_macro() as u32 * IS_MULTILINE_MACRO
+        | line.is_definition() as u32 * IS_DEFINITION;
 
         if result != *predicates {
             failures.push(format!(
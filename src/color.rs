@@ -1,5 +1,6 @@
 // use this module to automate the creation of CSS files.
 
+use crate::inline::macros::normalize_macro;
 use crate::utils::{into_v32, from_v32};
 use lazy_static::lazy_static;
 
@@ -75,9 +76,93 @@ lazy_static! {
     ).collect();
 }
 
+// `red`: one of `COLORS`, or `palette`, which is `RenderOption.colors`
+// `#ff8800` or `#f80`: a hex code
+// `palette` comes first, so a user can redefine the built-in colors
+pub fn parse_color(color: &[u32], palette: &[Color]) -> Option<(u8, u8, u8)> {
+
+    if let Some(color) = palette.iter().chain(COLORS.iter()).find(|c| normalize_macro(&into_v32(&c.name)) == color) {
+        return Some((color.r, color.g, color.b));
+    }
+
+    let hex = match color.split_first() {
+        Some((sharp, hex)) if *sharp == '#' as u32 => hex.iter().map(
+            |c| char::from_u32(*c).and_then(|c| c.to_digit(16)).map(|n| n as u8)
+        ).collect::<Option<Vec<u8>>>()?,
+        _ => { return None; }
+    };
+
+    match hex[..] {
+        [r1, r2, g1, g2, b1, b2] => Some((r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2)),
+        [r, g, b] => Some((r * 17, g * 17, b * 17)),
+        _ => None
+    }
+
+}
+
+/// It generates the styles of `[[name]]` and `[[highlight = name]]` for `colors`, like the ones of the default colors in `markdown.css`.
+/// `class_prefix` has to be the same as `RenderOption.class_prefix`.
+/// The engine doesn't know the background of the page, so it's up to you which colors you give.
+/// If `dark_mode` is set, the colors are swapped with their complements when `prefers-color-scheme` is `dark`.
+pub fn colors_to_css(colors: &[Color], class_prefix: &str, dark_mode: bool) -> String {
+    let mut variables = vec![];
+    let mut dark_variables = vec![];
+    let mut rules = vec![];
+
+    for color in colors.iter() {
+        let name = from_v32(&normalize_macro(&into_v32(&color.name)));
+        let hex = color.to_hex();
+        let complement = color.complement().to_hex();
+
+        variables.push(color_variables(&name, &hex, &complement));
+        dark_variables.push(color_variables(&name, &complement, &hex));
+
+        rules.push(format!(
+".markdown .{class_prefix}color-{name} {{
+  color: var(--{name});
+}}
+.markdown .{class_prefix}color-{name}::selection, .markdown .{class_prefix}color-{name} ::selection {{
+  color: var(--{name}-compl);
+}}
+.markdown .{class_prefix}highlight-{name} {{
+  background-color: var(--{name});
+}}
+.markdown .{class_prefix}highlight-{name}::selection {{
+  background-color: var(--{name}-compl);
+}}
+.markdown .{class_prefix}color-{name} svg path, .markdown .{class_prefix}color-{name} svg rect, .markdown .{class_prefix}color-{name} svg circle, .markdown .{class_prefix}color-{name} svg use {{
+  fill: var(--{name});
+}}
+"
+        ));
+    }
+
+    let mut result = format!(":root {{\n{}}}\n{}", variables.concat(), rules.concat());
+
+    if dark_mode {
+        result = format!(
+            "{result}@media (prefers-color-scheme: dark) {{\n  :root {{\n{}  }}\n}}\n",
+            dark_variables.concat().lines().map(|line| format!("  {line}\n")).collect::<String>()
+        );
+    }
+
+    result
+}
+
+fn color_variables(name: &str, hex: &str, complement: &str) -> String {
+    format!(
+"  --{name}: {hex};
+  --{name}-compl: {complement};
+  --{name}-trans: {hex}60;
+  --{name}-compl-trans: {complement}60;
+"
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::color::Color;
+    use crate::color::{Color, colors, colors_to_css, parse_color};
+    use crate::utils::into_v32;
 
     #[test]
     fn hex_color_test() {
@@ -86,4 +171,57 @@ mod tests {
         assert_eq!(Color::new("", 64, 255, 64).to_hex(), String::from("#40ff40"));
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn parse_color_test() {
+        assert_eq!(parse_color(&into_v32("red"), &[]), Some((192, 32, 32)));
+        assert_eq!(parse_color(&into_v32("#ff8800"), &[]), Some((255, 136, 0)));
+        assert_eq!(parse_color(&into_v32("#f80"), &[]), Some((255, 136, 0)));
+        assert_eq!(parse_color(&into_v32("#ff880"), &[]), None);
+        assert_eq!(parse_color(&into_v32("#gg8800"), &[]), None);
+        assert_eq!(parse_color(&into_v32("crimson"), &[]), None);
+        assert_eq!(parse_color(&into_v32("brandorange"), &[Color::new("Brand Orange", 255, 136, 0)]), Some((255, 136, 0)));
+        assert_eq!(parse_color(&into_v32("red"), &[Color::new("red", 255, 0, 0)]), Some((255, 0, 0)));
+    }
+
+    #[test]
+    fn colors_to_css_test() {
+        let css = colors_to_css(&[Color::new("Brand", 255, 136, 0)], "mdxt-", true);
+
+        assert_eq!(css, String::from(
+":root {
+  --brand: #ff8800;
+  --brand-compl: #0077ff;
+  --brand-trans: #ff880060;
+  --brand-compl-trans: #0077ff60;
+}
+.markdown .mdxt-color-brand {
+  color: var(--brand);
+}
+.markdown .mdxt-color-brand::selection, .markdown .mdxt-color-brand ::selection {
+  color: var(--brand-compl);
+}
+.markdown .mdxt-highlight-brand {
+  background-color: var(--brand);
+}
+.markdown .mdxt-highlight-brand::selection {
+  background-color: var(--brand-compl);
+}
+.markdown .mdxt-color-brand svg path, .markdown .mdxt-color-brand svg rect, .markdown .mdxt-color-brand svg circle, .markdown .mdxt-color-brand svg use {
+  fill: var(--brand);
+}
+@media (prefers-color-scheme: dark) {
+  :root {
+    --brand: #0077ff;
+    --brand-compl: #ff8800;
+    --brand-trans: #0077ff60;
+    --brand-compl-trans: #ff880060;
+  }
+}
+"
+        ));
+
+        assert!(!colors_to_css(&colors(), "", false).contains("@media"));
+        assert!(colors_to_css(&colors(), "", false).contains("  --red: #c02020;\n  --red-compl: #3fdfdf;\n"));
+    }
+
+}
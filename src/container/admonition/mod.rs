@@ -0,0 +1,224 @@
+#[cfg(test)]
+mod testbench;
+
+use crate::ast::{doc_data::DocData, node::Node};
+use crate::container::icon::{Icon, render_icon};
+use crate::inline::InlineNode;
+use crate::render::render_option::RenderOption;
+use crate::utils::{from_v32, into_v32};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AdmonitionType {
+    Note, Tip, Warning, Danger
+}
+
+impl AdmonitionType {
+
+    // `[[note]]`, `> [!NOTE]`
+    // it expects a lowercased name
+    pub fn from_name(name: &[u32]) -> Option<Self> {
+
+        match name {
+            [110, 111, 116, 101] => Some(AdmonitionType::Note),  // into_v32("note")
+            [116, 105, 112] => Some(AdmonitionType::Tip),  // into_v32("tip")
+            [119, 97, 114, 110, 105, 110, 103] => Some(AdmonitionType::Warning),  // into_v32("warning")
+            [100, 97, 110, 103, 101, 114] => Some(AdmonitionType::Danger),  // into_v32("danger")
+
+            // GitHub's alerts
+            [105, 109, 112, 111, 114, 116, 97, 110, 116] => Some(AdmonitionType::Warning),  // into_v32("important")
+            [99, 97, 117, 116, 105, 111, 110] => Some(AdmonitionType::Danger),  // into_v32("caution")
+            _ => None
+        }
+
+    }
+
+    pub fn name(&self) -> &'static str {
+
+        match self {
+            AdmonitionType::Note => "note",
+            AdmonitionType::Tip => "tip",
+            AdmonitionType::Warning => "warning",
+            AdmonitionType::Danger => "danger",
+        }
+
+    }
+
+    pub fn default_title(&self) -> Vec<u32> {
+
+        match self {
+            AdmonitionType::Note => vec![78, 111, 116, 101],  // into_v32("Note")
+            AdmonitionType::Tip => vec![84, 105, 112],  // into_v32("Tip")
+            AdmonitionType::Warning => vec![87, 97, 114, 110, 105, 110, 103],  // into_v32("Warning")
+            AdmonitionType::Danger => vec![68, 97, 110, 103, 101, 114],  // into_v32("Danger")
+        }
+
+    }
+
+    // names of the icons in `container/icon`
+    pub fn default_icon(&self) -> Vec<u32> {
+
+        match self {
+            AdmonitionType::Note => vec![105, 110, 102, 111],  // into_v32("info")
+            AdmonitionType::Tip => vec![98, 117, 108, 98],  // into_v32("bulb")
+            AdmonitionType::Warning => vec![97, 108, 101, 114, 116],  // into_v32("alert")
+            AdmonitionType::Danger => vec![102, 105, 114, 101],  // into_v32("fire")
+        }
+
+    }
+
+}
+
+#[derive(Clone)]
+pub struct Admonition {
+    pub admonition_type: AdmonitionType,
+    title: InlineNode,
+    icon: Option<Vec<u32>>,
+    collapsible: bool,
+    default_hidden: bool,
+    content: Vec<Node>,
+}
+
+impl Admonition {
+
+    pub fn new(admonition_type: AdmonitionType) -> Self {
+        Admonition {
+            admonition_type,
+            title: InlineNode::Raw(admonition_type.default_title()),
+            icon: Some(admonition_type.default_icon()),
+            collapsible: false,
+            default_hidden: false,
+            content: vec![],
+        }
+    }
+
+    // `[[note, title = Read This First, icon = lock, collapsible, default = hidden]]`
+    // `arguments` are normalized, so the title is read from `raw_title`, which preserves cases and spaces
+    // all the validity checks are done before this function
+    pub fn from_macro_arguments(admonition_type: AdmonitionType, arguments: &Vec<Vec<Vec<u32>>>, raw_title: Option<Vec<u32>>) -> Self {
+        let mut result = Admonition::new(admonition_type);
+
+        if let Some(title) = raw_title {
+            result.set_title(title);
+        }
+
+        for argument in arguments[1..].iter() {
+
+            match argument[0].as_slice() {
+                [99, 111, 108, 108, 97, 112, 115, 105, 98, 108, 101] => {  // into_v32("collapsible")
+                    result.collapsible = true;
+                },
+                [110, 111, 105, 99, 111, 110] => {  // into_v32("noicon")
+                    result.icon = None;
+                },
+                [105, 99, 111, 110] => {  // into_v32("icon")
+                    result.icon = Some(argument[1].clone());
+                },
+                [100, 101, 102, 97, 117, 108, 116] => {  // into_v32("default")
+                    result.collapsible = true;
+                    result.default_hidden = argument[1] == [104, 105, 100, 100, 101, 110];  // into_v32("hidden")
+                },
+                _ => {}
+            }
+
+        }
+
+        result
+    }
+
+    pub fn set_title(&mut self, title: Vec<u32>) {
+        self.title = InlineNode::Raw(title);
+    }
+
+    pub fn set_collapsible(&mut self, collapsible: bool, default_hidden: bool) {
+        self.collapsible = collapsible;
+        self.default_hidden = default_hidden;
+    }
+
+    pub fn set_inner_nodes(&mut self, nodes: Vec<Node>) {
+        self.content = nodes;
+    }
+
+    // `[[/note]]`
+    pub fn get_closing_macro(&self) -> Vec<u32> {
+        into_v32(&format!("[[/{}]]", self.admonition_type.name()))
+    }
+
+    // inner nodes are parsed by `collect_nodes_for_multiline_macros`
+    pub fn parse_inlines(&mut self, doc_data: &mut DocData, render_option: &RenderOption) {
+        self.title.parse_raw(doc_data, render_option);
+    }
+
+    pub fn to_html(&self, toc_rendered: &Vec<u32>, render_option: &RenderOption, doc_data: &mut DocData) -> Vec<u32> {
+        let class_prefix = &render_option.class_prefix;
+        let name = self.admonition_type.name();
+        let mut inner_html_buffer = vec![];
+
+        for node in self.content.iter() {
+            node.to_html(toc_rendered, render_option, doc_data, &mut inner_html_buffer);
+        }
+
+        let icon = match &self.icon {
+            Some(icon) => render_icon(&Icon::new(icon.clone(), 20), render_option, doc_data).unwrap_or_default(),
+            None => vec![]
+        };
+
+        // collapsible admonitions don't need any javascript
+        let (outer_tag, title_tag, open) = if self.collapsible {
+            ("details", "summary", if self.default_hidden { "" } else { " open=\"open\"" })
+        } else {
+            ("div", "div", "")
+        };
+
+        vec![
+            into_v32(&format!(
+                "<{outer_tag} class=\"{class_prefix}admonition {class_prefix}admonition-{name}\"{open}><{title_tag} class=\"{class_prefix}admonition-title\">"
+            )),
+            icon,
+            vec![60, 115, 112, 97, 110, 62],  // into_v32("<span>")
+            self.title.to_html(toc_rendered, class_prefix),
+            into_v32(&format!("</span></{title_tag}><div class=\"{class_prefix}admonition-content\">")),
+            inner_html_buffer.concat(),
+            into_v32(&format!("</div></{outer_tag}>")),
+        ].concat()
+    }
+
+}
+
+// an admonition inside a paragraph doesn't have a title
+pub fn inline_admonition_to_html(admonition_type: &AdmonitionType, icon: &[u32], content: Vec<u32>, class_prefix: &str) -> Vec<u32> {
+    vec![
+        into_v32(&format!(
+            "<span class=\"{class_prefix}admonition {class_prefix}admonition-{}\">",
+            admonition_type.name()
+        )),
+        icon.to_vec(),
+        content,
+        vec![60, 47, 115, 112, 97, 110, 62],  // into_v32("</span>")
+    ].concat()
+}
+
+#[cfg(test)]
+pub fn admonition_to_mdxt(admonition_type: &AdmonitionType, content: Vec<u32>) -> Vec<u32> {
+    let name = into_v32(admonition_type.name());
+
+    vec![
+        vec![91, 91],  // into_v32("[[")
+        name.clone(),
+        vec![93, 93],  // into_v32("]]")
+        content,
+        vec![91, 91, 47],  // into_v32("[[/")
+        name,
+        vec![93, 93],  // into_v32("]]")
+    ].concat()
+}
+
+// `IMPORTANT` -> `Important`
+pub fn capitalize(name: &[u32]) -> Vec<u32> {
+    let name = from_v32(name).to_lowercase();
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(c) => into_v32(&format!("{}{}", c.to_uppercase(), chars.as_str())),
+        None => vec![]
+    }
+}
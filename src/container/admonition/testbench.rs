@@ -0,0 +1,158 @@
+use crate::container::icon::get_icon;
+use crate::utils::{from_v32, into_v32, remove_whitespaces};
+use crate::render_to_html_with_default_options;
+
+fn admonition_samples() -> Vec<(String, String)> {
+    let result = vec![
+        ("
+[[note, noicon]]
+
+This is a note.
+
+[[/note]]
+", "
+<div class=\"admonition admonition-note\">
+    <div class=\"admonition-title\"><span>Note</span></div>
+    <div class=\"admonition-content\"><p>This is a note.</p></div>
+</div>
+"), ("
+[[warning, title = Read This First, noicon]]
+
+- *one*
+- two
+
+[[/warning]]
+", "
+<div class=\"admonition admonition-warning\">
+    <div class=\"admonition-title\"><span>Read This First</span></div>
+    <div class=\"admonition-content\"><ul><li><em>one</em></li><li>two</li></ul></div>
+</div>
+"), ("
+[[tip, collapsible, noicon]]
+Shown by default
+[[/tip]]
+
+[[danger, default = hidden, noicon]]
+Hidden by default
+[[/danger]]
+", "
+<details class=\"admonition admonition-tip\" open=\"open\">
+    <summary class=\"admonition-title\"><span>Tip</span></summary>
+    <div class=\"admonition-content\"><p>Shown by default</p></div>
+</details>
+
+<details class=\"admonition admonition-danger\">
+    <summary class=\"admonition-title\"><span>Danger</span></summary>
+    <div class=\"admonition-content\"><p>Hidden by default</p></div>
+</details>
+"), ("
+[[note]]
+Default icon
+[[/note]]
+", "
+<div class=\"admonition admonition-note\">
+    <div class=\"admonition-title\">{info}<span>Note</span></div>
+    <div class=\"admonition-content\"><p>Default icon</p></div>
+</div>
+"), ("
+[[tip, icon = lock]]
+Custom icon
+[[/tip]]
+", "
+<div class=\"admonition admonition-tip\">
+    <div class=\"admonition-title\">{lock}<span>Tip</span></div>
+    <div class=\"admonition-content\"><p>Custom icon</p></div>
+</div>
+"), ("
+> [!NOTE]
+> GitHub style
+> *alert*
+", "
+<div class=\"admonition admonition-note\">
+    <div class=\"admonition-title\">{info}<span>Note</span></div>
+    <div class=\"admonition-content\"><p>GitHub style <em>alert</em></p></div>
+</div>
+"), ("
+paragraph
+> [!caution] Be Careful
+> - one
+> - two
+>
+> > quote
+
+paragraph
+", "
+<p>paragraph</p>
+<div class=\"admonition admonition-danger\">
+    <div class=\"admonition-title\">{fire}<span>Be Careful</span></div>
+    <div class=\"admonition-content\"><ul><li>one</li><li>two</li></ul><blockquote>quote </blockquote></div>
+</div>
+<p>paragraph</p>
+"), ("
+> [!IMPORTANT]-
+> Collapsed
+", "
+<details class=\"admonition admonition-warning\">
+    <summary class=\"admonition-title\">{alert}<span>Important</span></summary>
+    <div class=\"admonition-content\"><p>Collapsed</p></div>
+</details>
+"), ("
+[[note, noicon]]
+> [!TIP]
+> Nested
+[[/note]]
+", "
+<div class=\"admonition admonition-note\">
+    <div class=\"admonition-title\"><span>Note</span></div>
+    <div class=\"admonition-content\">
+        <div class=\"admonition admonition-tip\">
+            <div class=\"admonition-title\">{bulb}<span>Tip</span></div>
+            <div class=\"admonition-content\"><p>Nested</p></div>
+        </div>
+    </div>
+</div>
+"), ("
+> [!NOTICE]
+> not an alert
+", "
+<blockquote>[!NOTICE] not an alert </blockquote>
+"), ("
+[[note, title = Inline]] inline [[/note]]
+", "
+<p><span class=\"admonition admonition-note\">{info16} inline </span></p>
+"), ("
+[[note, color = red]]
+invalid argument
+[[/note]]
+", "
+<p>[[note, color = red]] invalid argument [[/note]]</p>
+")
+    ];
+
+    result.into_iter().map(
+        |(case, answer)| (case.to_string(), answer.to_string())
+    ).collect()
+}
+
+fn render_icon(name: &str, size: usize) -> String {
+    from_v32(&get_icon(&into_v32(name), size, None, false).unwrap())
+}
+
+#[test]
+fn admonition_test() {
+    for (md, html) in admonition_samples().iter() {
+        let rendered = render_to_html_with_default_options(md);
+        let html = html.replace("{info16}", &render_icon("info", 16))
+            .replace("{info}", &render_icon("info", 20))
+            .replace("{lock}", &render_icon("lock", 20))
+            .replace("{bulb}", &render_icon("bulb", 20))
+            .replace("{alert}", &render_icon("alert", 20))
+            .replace("{fire}", &render_icon("fire", 20));
+
+        if remove_whitespaces(&into_v32(&rendered)) != remove_whitespaces(&into_v32(&html)) {
+            panic!("{md} \n\n {rendered}");
+        }
+
+    }
+
+}
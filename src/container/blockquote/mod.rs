@@ -3,9 +3,11 @@ mod testbench;
 
 use crate::ast::doc_data::DocData;
 use crate::ast::line::{add_br_if_needed, Line};
+use crate::container::admonition::{Admonition, AdmonitionType, capitalize};
 use crate::escape::HTML_ESCAPE_OFFSET;
 use crate::inline::InlineNode;
 use crate::render::render_option::RenderOption;
+use crate::utils::{lowercase, strip_whitespaces};
 
 #[derive(Clone)]
 pub struct Blockquote {
@@ -81,6 +83,77 @@ impl Blockquote {
 
 }
 
+// `> [!NOTE]`, `> [!WARNING] Custom Title`
+// `> [!TIP]-` is collapsed by default, and `> [!TIP]+` is collapsible but expanded by default
+pub fn read_alert_marker(line: &Line) -> Option<Admonition> {
+
+    if !line.is_blockquote() {
+        return None;
+    }
+
+    let (level, mut index) = count_level_and_end_index(&line.content);
+
+    if level != 1 || line.content.get(index) != Some(&('[' as u32)) || line.content.get(index + 1) != Some(&('!' as u32)) {
+        return None;
+    }
+
+    index += 2;
+    let name_start_index = index;
+
+    while index < line.content.len() && line.content[index] != ']' as u32 {
+        index += 1;
+    }
+
+    if index == line.content.len() {
+        return None;
+    }
+
+    let name = &line.content[name_start_index..index];
+    let mut result = Admonition::new(AdmonitionType::from_name(&name.iter().map(lowercase).collect::<Vec<u32>>())?);
+    index += 1;
+
+    match line.content.get(index) {
+        Some(c) if *c == '-' as u32 => {
+            result.set_collapsible(true, true);
+            index += 1;
+        },
+        Some(c) if *c == '+' as u32 => {
+            result.set_collapsible(true, false);
+            index += 1;
+        },
+        _ => {}
+    }
+
+    let title = strip_whitespaces(&line.content[index..]);
+
+    if !title.is_empty() {
+        result.set_title(title);
+    }
+
+    // `> [!IMPORTANT]` is rendered to a warning, but its title is still `Important`
+    else {
+        result.set_title(capitalize(name));
+    }
+
+    Some(result)
+}
+
+// `> foo` -> `foo`, `>> foo` -> `> foo`
+pub fn remove_blockquote_marker(line: &Line) -> Line {
+
+    if !line.is_blockquote() {
+        return line.clone();
+    }
+
+    let mut index = 1;
+
+    if line.content.get(index) == Some(&(' ' as u32)) {
+        index += 1;
+    }
+
+    Line::from_raw(&line.content[index..])
+}
+
 fn count_level_and_end_index(content: &[u32]) -> (usize, usize) {  // (level, end_index)
     let mut level = 0;
     let mut conseq_space = 0;
@@ -1,5 +1,6 @@
 use super::FencedCode;
 use super::syntect::{highlight_syntax, is_syntax_available};
+use crate::RenderOption;
 use crate::escape::escape_htmls;
 use crate::utils::{from_v32, into_v32, log10};
 use std::collections::HashMap;
@@ -9,7 +10,8 @@ use std::collections::HashMap;
 
 impl FencedCode {
 
-    pub fn to_html(&self, class_prefix: &str) -> Vec<u32> {
+    pub fn to_html(&self, toc_rendered: &[u32], render_option: &RenderOption) -> Vec<u32> {
+        let class_prefix = &render_option.class_prefix;
 
         let rows = if is_syntax_available(&self.language) {
             let lines = highlight_syntax(&self.get_raw_content(), &self.language, class_prefix);
@@ -26,12 +28,14 @@ impl FencedCode {
         };
 
         let copy_button = if self.copy_button {
-            into_v32(
-                &format!(
-                    "<button class=\"{class_prefix}copy-fenced-code\" onclick=\"copy_code_to_clipboard({})\">Copy</button>",
-                    self.index
-                )
-            )
+            // `RenderOption.csp`: the handler is registered by `csp::copy_button_listener`
+            let handler = if render_option.csp {
+                format!(" data-mdxt-copy=\"{}\"", self.index)
+            } else {
+                format!(" onclick=\"copy_code_to_clipboard({})\"", self.index)
+            };
+
+            into_v32(&format!("<button class=\"{class_prefix}copy-fenced-code\"{handler}>Copy</button>"))
         } else {
             vec![]
         };
@@ -55,13 +59,23 @@ impl FencedCode {
             String::new()
         };
 
-        vec![
+        let code_block = vec![
             into_v32(&format!("<pre class=\"{}\"{id}><code>", classes.join(" "))),
             rows.concat(),
             vec![60, 47, 99, 111, 100, 101, 62],  // into_v32("</code>"),
             copy_button,
             vec![60, 47, 112, 114, 101, 62],  // into_v32("</pre>"),
-        ].concat()
+        ].concat();
+
+        match &self.caption {
+            Some(caption) => vec![
+                into_v32(&format!("<figure class=\"{class_prefix}code-listing\">")),
+                caption.to_html(toc_rendered, class_prefix),
+                code_block,
+                into_v32("</figure>")
+            ].concat(),
+            None => code_block
+        }
     }
 
 }
@@ -8,7 +8,13 @@ mod testbench;
 use crate::ast::line::Line;
 use crate::ast::parse::ParseState;
 use crate::escape::{undo_backslash_escapes, undo_html_escapes};
-use crate::utils::{lowercase, remove_whitespaces, take_and_drop_while, to_int};
+use crate::ast::doc_data::DocData;
+use crate::container::figure::{Caption, CaptionKind};
+use crate::render::render_option::RenderOption;
+use crate::utils::{
+    get_parenthesis_end_index, into_v32, lowercase,
+    remove_whitespaces, strip_whitespaces, take_and_drop_while, to_int
+};
 use predicate::{is_copy_button, is_highlight, is_html_attribute, is_line_num, parse_arguments};
 
 #[derive(Clone)]
@@ -23,6 +29,9 @@ pub struct FencedCode {
     id: Option<Vec<u32>>,
     classes: Vec<Vec<u32>>,
 
+    // a code block with a caption is a numbered code listing
+    caption: Option<Caption>,
+
     pub index: usize,
 }
 
@@ -36,6 +45,7 @@ impl FencedCode {
         copy_button: bool,
         id: Option<Vec<u32>>,
         classes: Vec<Vec<u32>>,
+        caption: Option<Vec<u32>>,
         index: usize
     ) -> Self {
         FencedCode {
@@ -46,10 +56,19 @@ impl FencedCode {
             highlights,
             id,
             classes,
+            caption: caption.map(|caption| Caption::new(CaptionKind::Listing, caption)),
             index
         }
     }
 
+    pub fn parse_inlines(&mut self, doc_data: &mut DocData, render_option: &RenderOption) {
+
+        if let Some(caption) = &mut self.caption {
+            caption.parse_inlines(&self.id, doc_data, render_option);
+        }
+
+    }
+
     pub fn get_raw_content(&self) -> Vec<u32> {
         undo_html_escapes(&self.content)
     }
@@ -59,6 +78,10 @@ impl FencedCode {
 // it assumes that the given line is a valid code fence
 pub fn read_code_fence_info(line: &Line, fenced_code_count: usize) -> ParseState {
     let (fence, mut info_string) = take_and_drop_while(&line.content, line.content[0]);
+
+    // unlike the other arguments, the caption preserves its cases and spaces
+    let caption = read_caption(&mut info_string);
+
     info_string = remove_whitespaces(&info_string).iter().map(lowercase).collect();
 
     let mut language = vec![];  // into_v32("")
@@ -135,6 +158,22 @@ pub fn read_code_fence_info(line: &Line, fenced_code_count: usize) -> ParseState
         is_tilde_fence: line.content[0] == '~' as u32,
         id,
         classes,
+        caption,
         index: fenced_code_count
     }
 }
+
+// `rust, caption(The main function)` -> `The main function`
+// the caption is removed from `info_string`
+fn read_caption(info_string: &mut Vec<u32>) -> Option<Vec<u32>> {
+    let lowercased = info_string.iter().map(lowercase).collect::<Vec<u32>>();
+    let caption_begin = into_v32("caption(");
+
+    let start_index = lowercased.windows(caption_begin.len()).position(|w| w == caption_begin)?;
+    let end_index = get_parenthesis_end_index(info_string, start_index + caption_begin.len() - 1)?;
+    let caption = strip_whitespaces(&info_string[(start_index + caption_begin.len())..end_index]);
+
+    info_string.drain(start_index..(end_index + 1));
+
+    Some(caption)
+}
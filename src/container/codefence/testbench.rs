@@ -142,7 +142,7 @@ is_code_fence_end: answer: {is_code_fence_end}, actual: {}",
             classes_actual,
             is_tilde_fence_actual
         ) = match read_code_fence_info(&line, 0) {
-            ParseState::CodeFence { language, line_num, highlights, code_fence_size, copy_button, is_tilde_fence, id, classes, index: _index } => (
+            ParseState::CodeFence { language, line_num, highlights, code_fence_size, copy_button, is_tilde_fence, id, classes, index: _index, .. } => (
                 from_v32(&language),
                 line_num,
                 highlights,
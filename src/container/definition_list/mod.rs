@@ -0,0 +1,257 @@
+#[cfg(test)]
+mod testbench;
+
+use crate::ast::{AST, doc_data::DocData, line::Line, node::Node};
+use crate::inline::InlineNode;
+use crate::render::render_option::RenderOption;
+
+/*
+```
+Term
+Another Term
+: Definition
+: Another Definition
+```
+
+Each line above `: ` is a term, and each `: ` begins a definition.
+An empty line between the terms and the definitions makes the definitions loose, like PHP Markdown Extra and Pandoc.
+*/
+#[derive(Clone)]
+pub struct DefinitionList {
+    items: Vec<DefinitionListItem>
+}
+
+#[derive(Clone)]
+struct DefinitionListItem {
+    terms: Vec<InlineNode>,
+    definitions: Vec<Definition>
+}
+
+#[derive(Clone)]
+struct Definition {
+    content: Vec<Node>,
+
+    // a definition without empty lines is rendered without `<p>` tags
+    tight: bool
+}
+
+impl DefinitionList {
+
+    // `terms` are the lines right above `lines[index]`, which is the first definition
+    // it returns the list and the index of the first line that doesn't belong to the list
+    pub fn from_lines(
+        terms: &[Line],
+        lines: &[Line],
+        mut index: usize,
+        options: &RenderOption,
+        doc_data: &mut DocData,
+        table_count: &mut usize,
+        fenced_code_count: &mut usize
+    ) -> (Self, usize) {
+        let mut items = vec![];
+        let mut curr_terms = terms.to_vec();
+
+        loop {
+            let mut definitions = vec![];
+            let loose = index > 0 && lines[index - 1].is_empty();
+
+            while index < lines.len() && lines[index].is_definition() {
+                let (definition_lines, end_index) = read_definition(lines, index);
+                let tight = !loose && !definition_lines.iter().any(|line| line.is_empty());
+
+                // the inner blocks are parsed like a separate document
+                let ast = AST::from_nested_lines(definition_lines, options, std::mem::take(doc_data), table_count, fenced_code_count);
+                *doc_data = ast.doc_data;
+
+                definitions.push(Definition { content: ast.nodes, tight });
+                index = end_index;
+            }
+
+            items.push(DefinitionListItem {
+                terms: curr_terms.iter().map(|term| InlineNode::Raw(term.content.to_vec())).collect(),
+                definitions
+            });
+
+            match read_next_terms(lines, index) {
+                Some((terms, definition_index)) => {
+                    curr_terms = terms;
+                    index = definition_index;
+                },
+                None => { break; }
+            }
+
+        }
+
+        (DefinitionList { items }, index)
+    }
+
+    pub fn parse_inlines(&mut self, doc_data: &mut DocData, render_option: &RenderOption) {
+
+        for item in self.items.iter_mut() {
+
+            for term in item.terms.iter_mut() {
+                term.parse_raw(doc_data, render_option);
+            }
+
+            for definition in item.definitions.iter_mut() {
+
+                for node in definition.content.iter_mut() {
+                    node.parse_inlines(render_option, doc_data);
+                }
+
+            }
+
+        }
+
+    }
+
+    pub fn to_html(&self, toc_rendered: &Vec<u32>, render_option: &RenderOption, doc_data: &mut DocData) -> Vec<u32> {
+        let class_prefix = &render_option.class_prefix;
+        let mut result = vec![];
+
+        result.push(vec![60, 100, 108, 62]);  // into_v32("<dl>")
+
+        for item in self.items.iter() {
+
+            for term in item.terms.iter() {
+                result.push(vec![60, 100, 116, 62]);  // into_v32("<dt>")
+                result.push(term.to_html(toc_rendered, class_prefix));
+                result.push(vec![60, 47, 100, 116, 62]);  // into_v32("</dt>")
+            }
+
+            for definition in item.definitions.iter() {
+                result.push(vec![60, 100, 100, 62]);  // into_v32("<dd>")
+
+                let nodes = definition.content.iter().filter(
+                    |node| !matches!(node, Node::Empty)
+                ).collect::<Vec<&Node>>();
+
+                match nodes[..] {
+                    [Node::Paragraph { content }] if definition.tight => {
+                        result.push(content.to_html(toc_rendered, class_prefix));
+                    },
+                    _ => {
+                        for node in nodes.iter() {
+                            node.to_html(toc_rendered, render_option, doc_data, &mut result);
+                        }
+                    }
+                }
+
+                result.push(vec![60, 47, 100, 100, 62]);  // into_v32("</dd>")
+            }
+
+        }
+
+        result.push(vec![60, 47, 100, 108, 62]);  // into_v32("</dl>")
+        result.concat()
+    }
+
+}
+
+/*
+```
+: The first line of the definition
+
+    A paragraph inside the definition
+
+    - a list
+      - a sublist
+a lazy line of the list
+```
+
+Lines indented as deep as the first line's text belong to the definition. The indentation is removed, so that
+the inner blocks (lists, code blocks...) follow their own indentation rules. A paragraph may have lazy continuation lines.
+*/
+fn read_definition(lines: &[Line], index: usize) -> (Vec<Line>, usize) {  // (lines, end_index)
+    let first_line = &lines[index];
+    let spaces = first_line.content[1..].iter().take_while(|c| **c == ' ' as u32).count();
+    let column = first_line.indent + 1 + spaces;
+
+    let mut result = vec![Line::new(first_line.content[(1 + spaces)..].to_vec(), 0)];
+    let mut curr_index = index + 1;
+    let mut end_index = index + 1;
+
+    while curr_index < lines.len() {
+
+        if lines[curr_index].is_empty() {
+            curr_index += 1;
+            continue;
+        }
+
+        else if lines[curr_index].indent >= column {
+
+            // empty lines between the blocks of the definition
+            for line in lines[end_index..curr_index].iter() {
+                result.push(line.try_sub_indent(column));
+            }
+
+            result.push(lines[curr_index].try_sub_indent(column));
+        }
+
+        else if curr_index == end_index && is_lazy_continuation(lines, curr_index) {
+            result.push(lines[curr_index].clone());
+        }
+
+        else {
+            break;
+        }
+
+        curr_index += 1;
+        end_index = curr_index;
+    }
+
+    (result, end_index)
+}
+
+// `Term\n: definition\n\nAnother Term\n: another definition`
+fn read_next_terms(lines: &[Line], index: usize) -> Option<(Vec<Line>, usize)> {  // (terms, index of the definition)
+    let mut curr_index = index;
+
+    while curr_index < lines.len() && lines[curr_index].is_empty() {
+        curr_index += 1;
+    }
+
+    let terms_start_index = curr_index;
+
+    while curr_index < lines.len() && !lines[curr_index].is_empty() && !lines[curr_index].is_definition() {
+
+        if lines[curr_index].indent >= 4 || is_block_beginning(&lines[curr_index]) {
+            return None;
+        }
+
+        curr_index += 1;
+    }
+
+    let terms_end_index = curr_index;
+
+    // an empty line may be between the terms and the definition
+    if curr_index + 1 < lines.len() && lines[curr_index].is_empty() {
+        curr_index += 1;
+    }
+
+    if terms_end_index > terms_start_index && curr_index < lines.len() && lines[curr_index].is_definition() {
+        Some((lines[terms_start_index..terms_end_index].to_vec(), curr_index))
+    }
+
+    else {
+        None
+    }
+
+}
+
+// a line that is not indented, but still belongs to the previous line
+fn is_lazy_continuation(lines: &[Line], index: usize) -> bool {
+    !(
+        lines[index].is_definition() || is_block_beginning(&lines[index])
+
+        // otherwise it's the next term
+        || index + 1 < lines.len() && lines[index + 1].is_definition()
+    )
+}
+
+fn is_block_beginning(line: &Line) -> bool {
+    line.is_header() || line.is_code_fence_begin()
+    || line.is_thematic_break() || line.is_table_row()
+    || line.is_blockquote() || line.is_unordered_list()
+    || line.is_ordered_list() || line.is_multiline_macro()
+}
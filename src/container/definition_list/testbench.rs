@@ -0,0 +1,136 @@
+use crate::utils::{into_v32, remove_whitespaces};
+use crate::render_to_html_with_default_options;
+
+fn definition_list_samples() -> Vec<(String, String)> {
+    let result = vec![
+        ("
+Apple
+: A *fruit*
+: A company
+", "
+<dl>
+    <dt>Apple</dt>
+    <dd>A <em>fruit</em></dd>
+    <dd>A company</dd>
+</dl>
+"), ("
+Term 1
+Term 2
+: Definition
+lazy line
+
+Term 3
+: Definition
+", "
+<dl>
+    <dt>Term 1</dt>
+    <dt>Term 2</dt>
+    <dd>Definition lazy line</dd>
+    <dt>Term 3</dt>
+    <dd>Definition</dd>
+</dl>
+"), ("
+Term
+:   First paragraph
+
+    Second paragraph
+
+    - list
+      - sublist
+    - list
+
+paragraph
+", "
+<dl>
+    <dt>Term</dt>
+    <dd>
+        <p>First paragraph</p>
+        <p>Second paragraph</p>
+        <ul><li>list<ul><li>sublist</li></ul></li><li>list</li></ul>
+    </dd>
+</dl>
+<p>paragraph</p>
+"), ("
+Term
+:   Definition
+    ```
+    code
+    ```
+", "
+<dl>
+    <dt>Term</dt>
+    <dd>
+        <p>Definition</p>
+        <pre class=\"fenced-code-block\"><code><span class=\"code-fence-row\"><span class=\"code-fence-code\">code</span></span></code></pre>
+    </dd>
+</dl>
+"), ("
+: not a definition
+
+- list
+: not a definition
+", "
+<p>: not a definition</p>
+<ul><li>list : not a definition</li></ul>
+"), ("
+Term
+: Definition
+# Header
+", "
+<dl><dt>Term</dt><dd>Definition</dd></dl>
+<h1 id=\"header\">Header</h1>
+"), ("
+Term
+: [link]
+
+[link]: https://example.com
+", "
+<dl><dt>Term</dt><dd><a href=\"https://example.com\">link</a></dd></dl>
+"), ("
+Term
+
+: Definition
+: Another Definition
+
+Another Term
+: Definition
+", "
+<dl>
+    <dt>Term</dt>
+    <dd><p>Definition</p></dd>
+    <dd><p>Another Definition</p></dd>
+    <dt>Another Term</dt>
+    <dd>Definition</dd>
+</dl>
+"), ("
+Term
+
+Another Term
+
+: Definition
+", "
+<p>Term</p>
+<dl>
+    <dt>Another Term</dt>
+    <dd><p>Definition</p></dd>
+</dl>
+")
+    ];
+
+    result.into_iter().map(
+        |(case, answer)| (case.to_string(), answer.to_string())
+    ).collect()
+}
+
+#[test]
+fn definition_list_test() {
+    for (md, html) in definition_list_samples().iter() {
+        let rendered = render_to_html_with_default_options(md);
+
+        if remove_whitespaces(&into_v32(&rendered)) != remove_whitespaces(&into_v32(html)) {
+            panic!("{md} \n\n {rendered}");
+        }
+
+    }
+
+}
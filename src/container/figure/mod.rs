@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod testbench;
+
+use crate::ast::doc_data::DocData;
+use crate::inline::{DecorationType, InlineMacro, InlineNode, MediaType};
+use crate::render::render_option::RenderOption;
+use crate::utils::{from_v32, into_v32};
+
+/*
+```
+![An image](image.png "The caption of the image")
+```
+
+An image alone in a paragraph with a title is rendered to a `<figure>`, and the title becomes its caption.
+An `[[id = ...]]` macro right after the image labels the figure, so that `[[ref = ...]]` can refer to it.
+*/
+#[derive(Clone)]
+pub struct Figure {
+    image: InlineNode,
+    caption: Caption,
+    id: Option<Vec<u32>>
+}
+
+// figures, tables and code listings with captions are numbered automatically
+#[derive(Clone, Copy, PartialEq)]
+pub enum CaptionKind {
+    Figure,
+    Table,
+    Listing
+}
+
+#[derive(Clone)]
+pub struct Caption {
+    kind: CaptionKind,
+    content: InlineNode,
+
+    // it's numbered when the inlines are parsed, so that the numbers follow the order of the document
+    index: usize
+}
+
+impl Figure {
+
+    // `content` is a parsed paragraph
+    pub fn from_paragraph(content: &InlineNode) -> Option<Self> {
+        let nodes = flatten_inline_nodes(content).into_iter().filter(
+            |node| match node {
+                InlineNode::Raw(content) => content.iter().any(|c| *c != ' ' as u32),
+                _ => true
+            }
+        ).collect::<Vec<&InlineNode>>();
+
+        let (nodes, id) = match nodes[..] {
+            [
+                ref init @ ..,
+                InlineNode::Decoration { deco_type: DecorationType::Macro(InlineMacro::Id(id)), .. }
+            ] => (init, Some(id.clone())),
+            _ => (&nodes[..], None)
+        };
+
+        match nodes {
+            [InlineNode::Image { media_type: MediaType::Image, title: Some(title), description, address }] => Some(Figure {
+
+                // the title is rendered as a caption, not as an attribute
+                image: InlineNode::Image {
+                    media_type: MediaType::Image,
+                    description: description.clone(),
+                    address: address.clone(),
+                    title: None
+                },
+                caption: Caption::new(CaptionKind::Figure, title.clone()),
+                id
+            }),
+            _ => None
+        }
+
+    }
+
+    pub fn parse_inlines(&mut self, doc_data: &mut DocData, render_option: &RenderOption) {
+        self.caption.parse_inlines(&self.id, doc_data, render_option);
+    }
+
+    pub fn to_html(&self, toc_rendered: &[u32], class_prefix: &str) -> Vec<u32> {
+        let id = match &self.id {
+            Some(id) => format!(" id=\"{}\"", from_v32(id)),
+            None => String::new()
+        };
+
+        vec![
+            into_v32(&format!("<figure class=\"{class_prefix}figure\"{id}>")),
+            self.image.to_html(toc_rendered, class_prefix),
+            self.caption.to_html(toc_rendered, class_prefix),
+            into_v32("</figure>")
+        ].concat()
+    }
+
+}
+
+// `Complex` nodes may be nested
+fn flatten_inline_nodes(content: &InlineNode) -> Vec<&InlineNode> {
+
+    match content {
+        InlineNode::Complex(nodes) => nodes.iter().map(flatten_inline_nodes).collect::<Vec<Vec<&InlineNode>>>().concat(),
+        node => vec![node]
+    }
+
+}
+
+impl CaptionKind {
+
+    fn label(&self) -> &str {
+        match self {
+            CaptionKind::Figure => "Figure",
+            CaptionKind::Table => "Table",
+            CaptionKind::Listing => "Listing"
+        }
+    }
+
+}
+
+impl Caption {
+
+    pub fn new(kind: CaptionKind, content: Vec<u32>) -> Self {
+        Caption {
+            kind,
+            content: InlineNode::Raw(content),
+            index: 0
+        }
+    }
+
+    // `id` is the id of the element that has this caption
+    // if it exists, `[[ref = id]]` is rendered to `Figure 3`
+    pub fn parse_inlines(&mut self, id: &Option<Vec<u32>>, doc_data: &mut DocData, render_option: &RenderOption) {
+        self.index = doc_data.add_caption(self.kind);
+        self.content.parse_raw(doc_data, render_option);
+
+        if let Some(id) = id {
+            doc_data.add_label(id, &format!("{} {}", self.kind.label(), self.index));
+        }
+
+    }
+
+    // `<figcaption>` for figures and code listings, `<caption>` for tables
+    pub fn to_html(&self, toc_rendered: &[u32], class_prefix: &str) -> Vec<u32> {
+        let tag = match self.kind {
+            CaptionKind::Table => "caption",
+            _ => "figcaption"
+        };
+
+        vec![
+            into_v32(&format!(
+                "<{tag} class=\"{class_prefix}caption\"><span class=\"{class_prefix}caption-label\">{} {}.</span> ",
+                self.kind.label(),
+                self.index
+            )),
+            self.content.to_html(toc_rendered, class_prefix),
+            into_v32(&format!("</{tag}>"))
+        ].concat()
+    }
+
+}
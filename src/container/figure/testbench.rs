@@ -0,0 +1,177 @@
+use crate::utils::{into_v32, remove_whitespaces};
+use crate::{render_to_html, render_to_html_with_default_options, Diagnostic, RenderOption};
+
+fn figure_samples() -> Vec<(String, String)> {
+    let result = vec![
+        ("
+![A cat](cat.png \"A *cute* cat\")
+
+![A dog](dog.png)
+
+An inline ![image](a.png 'title').
+
+![Another](b.png 'Another figure')
+", "
+<figure class=\"figure\">
+    <img src=\"cat.png\" alt=\"A cat\"/>
+    <figcaption class=\"caption\"><span class=\"caption-label\">Figure 1.</span> A <em>cute</em> cat</figcaption>
+</figure>
+<p><img src=\"dog.png\" alt=\"A dog\"/></p>
+<p>An inline <img src=\"a.png\" alt=\"image\" title=\"title\"/>.</p>
+<figure class=\"figure\">
+    <img src=\"b.png\" alt=\"Another\"/>
+    <figcaption class=\"caption\"><span class=\"caption-label\">Figure 2.</span> Another figure</figcaption>
+</figure>
+"), ("
+| a | b |
+|---|---|
+|!![[caption = Results of Run 2, class = result]]|
+| 1 | 2 |
+
+| c |
+|---|
+| 3 |
+", "
+<table class=\"result\">
+    <caption class=\"caption\"><span class=\"caption-label\">Table 1.</span> Results of Run 2</caption>
+    <thead><tr><th>a</th><th>b</th></tr></thead>
+    <tbody><tr><td>1</td><td>2</td></tr></tbody>
+</table>
+<table>
+    <thead><tr><th>c</th></tr></thead>
+    <tbody><tr><td>3</td></tr></tbody>
+</table>
+"), ("
+```caption(The Main Function), line_num, copy_button(false)
+fn main() {}
+```
+", "
+<figure class=\"code-listing\">
+    <figcaption class=\"caption\"><span class=\"caption-label\">Listing 1.</span> The Main Function</figcaption>
+    <pre class=\"fenced-code-block line-num-width-0\"><code><span class=\"code-fence-row\"><span class=\"code-fence-index\">1</span><span class=\"code-fence-code\">fn main() {}</span></span></code></pre>
+</figure>
+")
+    ];
+
+    result.into_iter().map(
+        |(case, answer)| (case.to_string(), answer.to_string())
+    ).collect()
+}
+
+#[test]
+fn figure_test() {
+    for (md, html) in figure_samples().iter() {
+        let rendered = render_to_html_with_default_options(md);
+
+        if remove_whitespaces(&into_v32(&rendered)) != remove_whitespaces(&into_v32(html)) {
+            panic!("{md} \n\n {rendered}");
+        }
+
+    }
+
+}
+
+#[test]
+fn caption_count_test() {
+    let result = render_to_html("
+![A](a.png \"A\")
+
+[^a]
+
+[^a]: ![B](b.png \"B\")
+
+| a |
+|---|
+|!![[caption = A Table]]|
+
+```
+no caption
+```
+
+```caption(A Listing)
+code
+```
+", RenderOption::default());
+
+    // figures inside footnotes are also numbered
+    assert_eq!(result.figure_count, 2);
+    assert_eq!(result.table_count, 1);
+    assert_eq!(result.listing_count, 1);
+}
+
+#[test]
+fn cross_reference_test() {
+    let result = render_to_html("
+# Intro
+
+See [[ref = fig-arch]], [[ref = tbl-data]], [[ref = lst-main]] and [[ref = Usage]].
+[[ref = nothing]] is not defined.
+
+## Usage
+
+![Arch](arch.png \"The architecture\") [[id = fig-arch]]
+
+| a |
+|---|
+|!![[id = tbl-data, caption = Data]]|
+| 1 |
+
+```id(lst-main), caption(Main), copy_button(false)
+fn main() {}
+```
+", RenderOption::default());
+
+    let answer = "
+<h1 id=\"intro\">Intro</h1>
+<p>
+    See <a class=\"cross-reference\" href=\"#fig-arch\">Figure 1</a>,
+    <a class=\"cross-reference\" href=\"#tbl-data\">Table 1</a>,
+    <a class=\"cross-reference\" href=\"#lst-main\">Listing 1</a>
+    and <a class=\"cross-reference\" href=\"#usage\">Section 1.1</a>.
+    <span class=\"cross-reference dangling-reference\">nothing</span> is not defined.
+</p>
+<h2 id=\"usage\">Usage</h2>
+<figure class=\"figure\" id=\"fig-arch\">
+    <img src=\"arch.png\" alt=\"Arch\"/>
+    <figcaption class=\"caption\"><span class=\"caption-label\">Figure 1.</span> The architecture</figcaption>
+</figure>
+<table id=\"tbl-data\">
+    <caption class=\"caption\"><span class=\"caption-label\">Table 1.</span> Data</caption>
+    <thead><tr><th>a</th></tr></thead>
+    <tbody><tr><td>1</td></tr></tbody>
+</table>
+<figure class=\"code-listing\">
+    <figcaption class=\"caption\"><span class=\"caption-label\">Listing 1.</span> Main</figcaption>
+    <pre class=\"fenced-code-block\" id=\"lst-main\"><code><span class=\"code-fence-row\"><span class=\"code-fence-code\">fn main() {}</span></span></code></pre>
+</figure>
+";
+
+    if remove_whitespaces(&into_v32(&result.content)) != remove_whitespaces(&into_v32(answer)) {
+        panic!("{}", result.content);
+    }
+
+    assert_eq!(result.diagnostics, vec![Diagnostic::DanglingReference { label: String::from("nothing") }]);
+
+    let result = render_to_html("
+![A](a.png \"A\") [[id = dup]]
+
+![B](b.png \"B\") [[id = dup]]
+
+[[ref = dup]]
+", RenderOption::default());
+
+    assert!(result.content.contains("<a class=\"cross-reference\" href=\"#dup\">Figure 1</a>"));
+    assert_eq!(result.diagnostics, vec![Diagnostic::DuplicateLabel { label: String::from("dup") }]);
+
+    // an id in a paragraph, and a header in both the content and the toc
+    let result = render_to_html("
+[[toc]]
+
+# See [[ref = nope]]
+
+Some text [[id = here]] and [[ref = here]].
+", RenderOption::default());
+
+    assert!(result.content.contains("<span id=\"here\"></span> and <a class=\"cross-reference\" href=\"#here\">here</a>"));
+    assert_eq!(result.diagnostics, vec![Diagnostic::DanglingReference { label: String::from("nope") }]);
+}
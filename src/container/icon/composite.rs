@@ -0,0 +1,59 @@
+use super::render::{SIZE, XMLNS};
+use crate::utils::into_v32;
+
+/*
+`[[icon = file, badge = plus]]`, `[[icon = folder, overlay = lock]]`
+
+Each icon is nested in a 24 x 24 `<svg>`. Nested `<svg>`s scale their contents to their own `width` and `height` using their `viewBox`,
+so icons with different `viewBox`es can be combined.
+
+- A badge is at the top-right corner, and an overlay is at the bottom-right corner. Both are half the size of the whole icon.
+- The base icon shrinks to 3/4, and moves away from them.
+*/
+
+// templates of `render::format`: `<svg{XMLNS}{SIZE} viewBox="...">...</svg>`
+pub fn compose(base: &[u32], badge: Option<&Vec<u32>>, overlay: Option<&Vec<u32>>) -> Vec<u32> {
+
+    if badge.is_none() && overlay.is_none() {
+        return base.to_vec();
+    }
+
+    let base_y = match (badge, overlay) {
+        (Some(_), None) => 6,
+        (Some(_), Some(_)) => 3,
+        _ => 0
+    };
+
+    vec![
+        vec![60, 115, 118, 103, XMLNS, SIZE],  // into_v32("<svg")
+        into_v32(" viewBox=\"0 0 24 24\">"),
+        nest(base, &position(0, base_y, 18)),
+        badge.map(|badge| nest(badge, &position(12, 0, 12))).unwrap_or_default(),
+        overlay.map(|overlay| nest(overlay, &position(12, 12, 12))).unwrap_or_default(),
+        vec![60, 47, 115, 118, 103, 62],  // into_v32("</svg>")
+    ].concat()
+}
+
+// only the outermost `<svg>` has the size and the `xmlns`
+// `attributes` replace the size of the inner `<svg>`
+pub fn nest(icon: &[u32], attributes: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(icon.len() + attributes.len());
+
+    for c in icon.iter() {
+
+        if *c == SIZE {
+            result.extend_from_slice(attributes);
+        }
+
+        else if *c != XMLNS {
+            result.push(*c);
+        }
+
+    }
+
+    result
+}
+
+fn position(x: usize, y: usize, size: usize) -> Vec<u32> {
+    into_v32(&format!(" x=\"{x}\" y=\"{y}\" width=\"{size}\" height=\"{size}\""))
+}
@@ -12,7 +12,7 @@ def main():
         rust_file = f.read()
 
     ind = rust_file.index('^')
-    rust_file = rust_file[:ind] + '\n'.join(result) + rust_file[ind + 1:] + f'\nconst COLOR: u32 = {hex(color_token)};\nconst SIZE: u32 = {hex(size_token)};\nconst XMLNS: u32 = {hex(xmlns_token)};\n#[cfg(test)] pub const EVA_ICON: usize = {hex(EVA)};\n#[cfg(test)] pub const MATERIAL_ICON: usize = {hex(MATERIAL)};\n#[cfg(test)] pub const DEV_ICON: usize = {hex(DEV)};\n#[cfg(test)] pub const ION_ICON: usize = {hex(ION)};\n#[cfg(test)] pub const BOOTSTRAP_ICON: usize = {hex(BOOTSTRAP)};\n#[cfg(test)] pub const SVG_SILH: usize = {hex(SVG_SILH)};\n' + licenses
+    rust_file = rust_file[:ind] + '\n'.join(result) + rust_file[ind + 1:] + f'\npub const COLOR: u32 = {hex(color_token)};\npub const SIZE: u32 = {hex(size_token)};\npub const XMLNS: u32 = {hex(xmlns_token)};\n#[cfg(test)] pub const EVA_ICON: usize = {hex(EVA)};\n#[cfg(test)] pub const MATERIAL_ICON: usize = {hex(MATERIAL)};\n#[cfg(test)] pub const DEV_ICON: usize = {hex(DEV)};\n#[cfg(test)] pub const ION_ICON: usize = {hex(ION)};\n#[cfg(test)] pub const BOOTSTRAP_ICON: usize = {hex(BOOTSTRAP)};\n#[cfg(test)] pub const SVG_SILH: usize = {hex(SVG_SILH)};\n' + licenses
 
     with open('./render.rs', 'w') as f:
         f.write(rust_file)
@@ -113,6 +113,7 @@ def get_icons(size, color):
         'Phone': f"<svg{xmlns} viewBox=\"0 0 24 24\"{size}><path d=\"M17.4 22A15.42 15.42 0 0 1 2 6.6 4.6 4.6 0 0 1 6.6 2a3.94 3.94 0 0 1 .77.07 3.79 3.79 0 0 1 .72.18 1 1 0 0 1 .65.75l1.37 6a1 1 0 0 1-.26.92c-.13.14-.14.15-1.37.79a9.91 9.91 0 0 0 4.87 4.89c.65-1.24.66-1.25.8-1.38a1 1 0 0 1 .92-.26l6 1.37a1 1 0 0 1 .72.65 4.34 4.34 0 0 1 .19.73 4.77 4.77 0 0 1 .06.76A4.6 4.6 0 0 1 17.4 22z\"{color}/></svg>",
         'Pin': f"<svg{xmlns} viewBox=\"0 0 24 24\"{size}><circle cx=\"12\" cy=\"9.5\" r=\"1.5\"{color}/><path d=\"M12 2a8 8 0 0 0-8 7.92c0 5.48 7.05 11.58 7.35 11.84a1 1 0 0 0 1.3 0C13 21.5 20 15.4 20 9.92A8 8 0 0 0 12 2zm0 11a3.5 3.5 0 1 1 3.5-3.5A3.5 3.5 0 0 1 12 13z\"{color}/></svg>",
         'Pizza': f"<svg{xmlns} viewBox=\"0 0 512 512\"{size}><path{color} d=\"M441.82 67.83C383.44 44.73 317.3 32 255.56 32 192 32 125.76 44.53 69 67.26 48.7 75.49 45.21 90 48.71 100.82L52.78 111a16 16 0 0021.31 8.69c10.8-4.76 23.93-10.54 27-11.78C145.1 89.64 198.71 80 256 80c57.47 0 108.09 9.24 154.76 28.25 4.42 1.8 14.88 6.42 26.17 11.46a16 16 0 0021.35-8.59L462 102l.34-.9c3.45-10.21.14-25.05-20.52-33.27z\"/><path{color} d=\"M409.18 140.86C363.67 122.53 307.68 112 255.56 112a425 425 0 00-153.74 28.89c-.53.21-2.06.88-4.29 1.88a16 16 0 00-8 21.27c4 8.71 9.42 20.58 15.5 33.89C137.94 270 199.21 404 227.26 462A31.74 31.74 0 00256 480a31.73 31.73 0 0028.76-18.06l.06-.13 137.3-297.57a15.94 15.94 0 00-8.31-21.45c-2.26-.95-3.85-1.61-4.5-1.87zm-215.1 83.07a32 32 0 1129.85-29.85 32 32 0 01-29.85 29.85zm64 128a32 32 0 1129.85-29.85 32 32 0 01-29.85 29.85zm64-112a32 32 0 1129.85-29.85 32 32 0 01-29.85 29.85z\"/></svg>",
+        'Plus': f"<svg{xmlns} viewBox=\"0 0 24 24\"{size}><path d=\"M19 11h-6V5a1 1 0 0 0-2 0v6H5a1 1 0 0 0 0 2h6v6a1 1 0 0 0 2 0v-6h6a1 1 0 0 0 0-2z\"{color}/></svg>",
         'Power': f"<svg{xmlns} viewBox=\"0 0 24 24\"{size}><path d=\"M12 13a1 1 0 0 0 1-1V2a1 1 0 0 0-2 0v10a1 1 0 0 0 1 1z\"{color}/><path d=\"M16.59 3.11a1 1 0 0 0-.92 1.78 8 8 0 1 1-7.34 0 1 1 0 1 0-.92-1.78 10 10 0 1 0 9.18 0z\"{color}/></svg>",
         'Python': f"<svg{xmlns} viewBox=\"0 0 128 128\"{size}><path{color} d=\"M49.33 62h29.159C86.606 62 93 55.132 93 46.981V19.183c0-7.912-6.632-13.856-14.555-15.176-5.014-.835-10.195-1.215-15.187-1.191-4.99.023-9.612.448-13.805 1.191C37.098 6.188 35 10.758 35 19.183V30h29v4H23.776c-8.484 0-15.914 5.108-18.237 14.811-2.681 11.12-2.8 17.919 0 29.53C7.614 86.983 12.569 93 21.054 93H31V79.952C31 70.315 39.428 62 49.33 62zm-1.838-39.11c-3.026 0-5.478-2.479-5.478-5.545 0-3.079 2.451-5.581 5.478-5.581 3.015 0 5.479 2.502 5.479 5.581-.001 3.066-2.465 5.545-5.479 5.545zm74.789 25.921C120.183 40.363 116.178 34 107.682 34H97v12.981C97 57.031 88.206 65 78.489 65H49.33C41.342 65 35 72.326 35 80.326v27.8c0 7.91 6.745 12.564 14.462 14.834 9.242 2.717 17.994 3.208 29.051 0C85.862 120.831 93 116.549 93 108.126V97H64v-4h43.682c8.484 0 11.647-5.776 14.599-14.66 3.047-9.145 2.916-17.799 0-29.529zm-41.955 55.606c3.027 0 5.479 2.479 5.479 5.547 0 3.076-2.451 5.579-5.479 5.579-3.015 0-5.478-2.502-5.478-5.579 0-3.068 2.463-5.547 5.478-5.547z\"/></svg>",
         'Question': f"<svg{xmlns}{size} viewBox=\"0 0 512 512\"><path{color} d=\"M256,64C150,64,64,150,64,256s86,192,192,192,192-86,192-192S362,64,256,64Zm-6,304a20,20,0,1,1,20-20A20,20,0,0,1,250,368Zm33.44-102C267.23,276.88,265,286.85,265,296a14,14,0,0,1-28,0c0-21.91,10.08-39.33,30.82-53.26C287.1,229.8,298,221.6,298,203.57c0-12.26-7-21.57-21.49-28.46-3.41-1.62-11-3.2-20.34-3.09-11.72.15-20.82,2.95-27.83,8.59C215.12,191.25,214,202.83,214,203a14,14,0,1,1-28-1.35c.11-2.43,1.8-24.32,24.77-42.8,11.91-9.58,27.06-14.56,45-14.78,12.7-.15,24.63,2,32.72,5.82C312.7,161.34,326,180.43,326,203.57,326,237.4,303.39,252.59,283.44,266Z\"/></svg>",
@@ -0,0 +1,225 @@
+use super::render::{COLOR, SIZE, XMLNS};
+use super::composite::compose;
+use super::ICONS;
+use crate::inline::macros::normalize_macro;
+use crate::utils::{from_v32, into_v32};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Icons registered by the user. `[[icon = name]]` looks them up before the built-in icons,
+/// so an icon here replaces a built-in one with the same name.
+#[derive(Clone, Default)]
+pub struct IconSet {
+    icons: HashMap<Vec<u32>, Vec<u32>>  // HashMap<IconName, SVG>
+}
+
+impl IconSet {
+
+    /// `svg` is an `<svg>` element. `width`, `height` and `xmlns` of the root element are replaced by the engine,
+    /// like the built-in icons: `size` of the macro sets the size, and the color of the text fills the icon.
+    /// Names are case-insensitive, and spaces and underscores are ignored: `Our_Logo` is `[[icon = ourlogo]]`.
+    pub fn add(&mut self, name: &str, svg: &str) -> Result<&mut Self, String> {
+        let name = normalize_macro(&into_v32(name));
+
+        if name.is_empty() {
+            return Err(String::from("an icon without a name"));
+        }
+
+        let svg = normalize_svg(&into_v32(svg)).ok_or_else(|| format!("`{}` is not an svg", from_v32(&name)))?;
+        self.icons.insert(name, svg);
+
+        Ok(self)
+    }
+
+    /// All the `.svg` files in `path`, named after their file names: `logo.svg` is `[[icon = logo]]`.
+    /// The other files and the subdirectories are ignored.
+    pub fn from_dir<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let mut result = IconSet::default();
+        let entries = std::fs::read_dir(path.as_ref()).map_err(|e| format!("{}: {e}", path.as_ref().display()))?;
+
+        for entry in entries {
+            let path = entry.map_err(|e| format!("{e}"))?.path();
+
+            let is_svg = path.is_file() && match path.extension().and_then(|ext| ext.to_str()) {
+                Some(ext) => ext.eq_ignore_ascii_case("svg"),
+                None => false
+            };
+
+            if !is_svg {
+                continue;
+            }
+
+            if let Some(name) = path.file_stem().and_then(|name| name.to_str()) {
+                let svg = std::fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+                result.add(name, &svg)?;
+            }
+
+        }
+
+        Ok(result)
+    }
+
+    /// The icons of `other` overwrite the ones with the same name.
+    pub fn merge(&mut self, other: IconSet) -> &mut Self {
+        self.icons.extend(other.icons);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.icons.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.icons.is_empty()
+    }
+
+    // `name` is normalized by `read_macro`
+    pub(crate) fn contains(&self, name: &[u32]) -> bool {
+        self.icons.contains_key(name) || ICONS.contains_key(name)
+    }
+
+    // the template of `render::format`
+    // the user's icons first, then the built-in ones
+    pub(crate) fn get_composite_template(&self, name: &Vec<u32>, badge: Option<&Vec<u32>>, overlay: Option<&Vec<u32>>) -> Option<Vec<u32>> {
+        let base = self.get_template(name)?;
+
+        let badge = match badge {
+            Some(badge) => Some(self.get_template(badge)?),
+            None => None
+        };
+
+        let overlay = match overlay {
+            Some(overlay) => Some(self.get_template(overlay)?),
+            None => None
+        };
+
+        Some(compose(base, badge, overlay))
+    }
+
+    fn get_template(&self, name: &Vec<u32>) -> Option<&Vec<u32>> {
+
+        match self.icons.get(name) {
+            Some(svg) => Some(svg),
+            None => ICONS.get(name).map(|(svg, _)| svg)
+        }
+
+    }
+
+}
+
+// `<?xml ...?><svg xmlns="..." width="24" height="24" viewBox="0 0 24 24"><path d="..."/></svg>`
+// -> `<svg{XMLNS}{SIZE} viewBox="0 0 24 24"><path{COLOR} d="..."/></svg>`
+// the tokens are rendered by `format`, as the ones of the built-in icons
+// like the stylesheet, the color goes to `<path>`, `<rect>` and `<circle>`
+fn normalize_svg(svg: &[u32]) -> Option<Vec<u32>> {
+    let start_index = svg.windows(4).position(|w| w == [60, 115, 118, 103])?;  // into_v32("<svg")
+    let mut index = start_index + 4;
+    let mut attributes = vec![];
+
+    loop {
+
+        while index < svg.len() && is_whitespace(svg[index]) {
+            index += 1;
+        }
+
+        match svg.get(index) {
+            None => { return None; },
+            Some(c) if *c == '>' as u32 || *c == '/' as u32 => { break; },
+            _ => {}
+        }
+
+        let attribute_start_index = index;
+
+        while index < svg.len() && !is_attribute_end(svg[index]) {
+            index += 1;
+        }
+
+        let attribute_name = svg[attribute_start_index..index].to_vec();
+
+        if svg.get(index) == Some(&('=' as u32)) {
+            index += 1;
+            let quote = *svg.get(index)?;
+
+            if quote == '"' as u32 || quote == '\'' as u32 {
+                index += svg[(index + 1)..].iter().position(|c| *c == quote)? + 2;
+            }
+
+            else {
+
+                while index < svg.len() && !is_attribute_end(svg[index]) {
+                    index += 1;
+                }
+
+            }
+
+        }
+
+        // a character that cannot start an attribute
+        if index == attribute_start_index {
+            return None;
+        }
+
+        if ![
+            vec![119, 105, 100, 116, 104],        // into_v32("width")
+            vec![104, 101, 105, 103, 104, 116],   // into_v32("height")
+            vec![120, 109, 108, 110, 115],        // into_v32("xmlns")
+        ].contains(&attribute_name) {
+            attributes.push(vec![32]);  // ' '
+            attributes.push(svg[attribute_start_index..index].to_vec());
+        }
+
+    }
+
+    // `</svg>` and anything after it are kept as they are, except the trailing whitespaces
+    let mut end_index = svg.len();
+
+    while end_index > index && is_whitespace(svg[end_index - 1]) {
+        end_index -= 1;
+    }
+
+    Some(vec![
+        vec![60, 115, 118, 103, XMLNS, SIZE],  // into_v32("<svg")
+        attributes.concat(),
+        add_color_tokens(&svg[index..end_index]),
+    ].concat())
+}
+
+fn add_color_tokens(svg: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(svg.len() + 16);
+    let mut index = 0;
+
+    while index < svg.len() {
+        result.push(svg[index]);
+
+        if svg[index] == '<' as u32 {
+
+            for tag in [
+                &[112, 97, 116, 104][..],     // into_v32("path")
+                &[114, 101, 99, 116][..],     // into_v32("rect")
+                &[99, 105, 114, 99, 108, 101][..],  // into_v32("circle")
+            ] {
+
+                if svg[(index + 1)..].starts_with(tag) && svg.get(index + tag.len() + 1).map(|c| is_attribute_end(*c)).unwrap_or(false) {
+                    result.extend_from_slice(tag);
+                    result.push(COLOR);
+                    index += tag.len();
+                    break;
+                }
+
+            }
+
+        }
+
+        index += 1;
+    }
+
+    result
+}
+
+fn is_attribute_end(c: u32) -> bool {
+    c == '=' as u32 || c == '>' as u32 || c == '/' as u32 || is_whitespace(c)
+}
+
+fn is_whitespace(c: u32) -> bool {
+    c == ' ' as u32 || c == '\n' as u32 || c == '\r' as u32 || c == '\t' as u32
+}
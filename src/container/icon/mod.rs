@@ -1,15 +1,25 @@
+mod composite;
+mod icon_set;
 mod render;
 
 #[cfg(test)]
 mod testbench;
 
+use crate::ast::doc_data::DocData;
+use crate::color::parse_color;
+use crate::render::render_option::{IconMode, RenderOption};
+use crate::utils::{from_v32, into_v32, to_int};
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 
+pub use icon_set::IconSet;
+
 lazy_static! {
     pub static ref ICONS: HashMap<Vec<u32>, (Vec<u32>, usize)> = self::render::data();
 }
 
+// the engine uses `render_icon`, which also looks up `RenderOption.icons`
+#[cfg(test)]
 pub fn get_icon(name: &Vec<u32>, size: usize, color: Option<(u8, u8, u8)>, standalone: bool) -> Option<Vec<u32>> {
 
     match ICONS.get(name) {
@@ -17,4 +27,218 @@ pub fn get_icon(name: &Vec<u32>, size: usize, color: Option<(u8, u8, u8)>, stand
         _ => None
     }
 
-}
\ No newline at end of file
+}
+
+// `[[icon = github, size = 24, color = red, title = GitHub]]`
+#[derive(Clone)]
+pub struct Icon {
+    pub name: Vec<u32>,
+    pub size: usize,
+    pub badge: Option<Vec<u32>>,
+    pub overlay: Option<Vec<u32>>,
+    pub color: Option<Vec<u32>>,  // `red` or `#ff8800`
+    pub title: Option<Vec<u32>>,  // it keeps the cases and the spaces
+    pub decorative: bool,
+    pub rotate: u32,  // degrees
+    pub flip: Option<Vec<u32>>  // `horizontal`, `vertical` or `both`
+}
+
+impl Icon {
+
+    pub fn new(name: Vec<u32>, size: usize) -> Self {
+        Icon {
+            name,
+            size,
+            badge: None,
+            overlay: None,
+            color: None,
+            title: None,
+            decorative: false,
+            rotate: 0,
+            flip: None
+        }
+    }
+
+    // all the validity checks are done by `is_valid`
+    // `raw_title` is read by `read_raw_macro_argument`, because `arguments` are lowercased and don't have spaces
+    pub fn from_macro_arguments(arguments: &[Vec<Vec<u32>>], raw_title: Option<Vec<u32>>) -> Self {
+        let mut result = Icon::new(arguments[0][1].clone(), 32);
+
+        for argument in arguments[1..].iter() {
+
+            match argument[0].as_slice() {
+                [115, 105, 122, 101] => { result.size = to_int(&argument[1]).unwrap() as usize; },  // into_v32("size")
+                [98, 97, 100, 103, 101] => { result.badge = Some(argument[1].clone()); },  // into_v32("badge")
+                [111, 118, 101, 114, 108, 97, 121] => { result.overlay = Some(argument[1].clone()); },  // into_v32("overlay")
+                [99, 111, 108, 111, 114] => { result.color = Some(argument[1].clone()); },  // into_v32("color")
+                [116, 105, 116, 108, 101] => { result.title = raw_title.clone(); },  // into_v32("title")
+                [100, 101, 99, 111, 114, 97, 116, 105, 118, 101] => { result.decorative = true; },  // into_v32("decorative")
+                [114, 111, 116, 97, 116, 101] => { result.rotate = to_int(&argument[1]).unwrap() % 360; },  // into_v32("rotate")
+                [102, 108, 105, 112] => { result.flip = Some(argument[1].clone()); },  // into_v32("flip")
+                _ => unreachable!()
+            }
+
+        }
+
+        result
+    }
+
+    // ` role="img" aria-label="GitHub" transform="rotate(90) scale(-1, 1)"`
+    fn attributes(&self) -> Vec<u32> {
+        let mut result = vec![];
+
+        if let Some(title) = &self.title {
+            result.push(into_v32(&format!(" role=\"img\" aria-label=\"{}\"", from_v32(title))));
+        }
+
+        if self.decorative {
+            result.push(into_v32(" aria-hidden=\"true\""));
+        }
+
+        let mut transforms = vec![];
+
+        if self.rotate != 0 {
+            transforms.push(format!("rotate({})", self.rotate));
+        }
+
+        match self.flip.as_deref() {
+            Some([104, 111, 114, 105, 122, 111, 110, 116, 97, 108]) => { transforms.push(String::from("scale(-1, 1)")); },  // into_v32("horizontal")
+            Some([118, 101, 114, 116, 105, 99, 97, 108]) => { transforms.push(String::from("scale(1, -1)")); },  // into_v32("vertical")
+            Some(_) => { transforms.push(String::from("scale(-1, -1)")); },  // `both`
+            None => {}
+        }
+
+        if !transforms.is_empty() {
+            result.push(into_v32(&format!(" transform=\"{}\"", transforms.join(" "))));
+        }
+
+        result.concat()
+    }
+
+    // `<title>GitHub</title>`
+    fn title_element(&self) -> Vec<u32> {
+
+        match &self.title {
+            Some(title) => vec![
+                into_v32("<title>"),
+                title.clone(),
+                into_v32("</title>"),
+            ].concat(),
+            None => vec![]
+        }
+
+    }
+
+    #[cfg(test)]  // it's used for roundtrip tests
+    pub fn to_mdxt(&self) -> Vec<u32> {
+        let mut result = vec![
+            into_v32("[[icon="),
+            self.name.clone(),
+            into_v32(&format!(",size={}", self.size)),
+        ];
+
+        for (key, value) in [
+            ("badge", &self.badge),
+            ("overlay", &self.overlay),
+            ("color", &self.color),
+            ("title", &self.title),
+            ("flip", &self.flip),
+        ] {
+
+            if let Some(value) = value {
+                result.push(into_v32(&format!(",{key}={}", from_v32(value))));
+            }
+
+        }
+
+        if self.rotate != 0 {
+            result.push(into_v32(&format!(",rotate={}", self.rotate)));
+        }
+
+        if self.decorative {
+            result.push(into_v32(",decorative"));
+        }
+
+        result.push(into_v32("]]"));
+        result.concat()
+    }
+
+}
+
+// `IconMode::Inline`: the whole `<svg>`
+// `IconMode::Sprite`: `<svg width="32" height="32"><use href="#mdxt-icon-github"/></svg>`, and the `<symbol>` is added to `doc_data`
+pub fn render_icon(icon: &Icon, render_option: &RenderOption, doc_data: &mut DocData) -> Option<Vec<u32>> {
+    let template = render_option.icons.get_composite_template(&icon.name, icon.badge.as_ref(), icon.overlay.as_ref())?;
+    let color = icon.color.as_ref().and_then(|color| parse_color(color, &render_option.colors));
+
+    let sprite_path = match &render_option.icon_mode {
+        IconMode::Inline => {
+            let svg = self::render::format(&template, icon.size, color, false);
+
+            // the attributes go right after `<svg`, and the title is the first child
+            let root_end_index = svg.iter().position(|c| *c == '>' as u32).unwrap_or(svg.len() - 1);
+
+            return Some(vec![
+                svg[..4].to_vec(),
+                icon.attributes(),
+                svg[4..(root_end_index + 1)].to_vec(),
+                icon.title_element(),
+                svg[(root_end_index + 1)..].to_vec(),
+            ].concat());
+        },
+        IconMode::Sprite => "",
+        IconMode::ExternalSprite(path) => path.as_str()
+    };
+
+    // `document-badge-plus-overlay-lock`
+    let id = vec![
+        into_v32("mdxt-icon-"),
+        icon.name.clone(),
+        icon.badge.as_ref().map(|badge| vec![into_v32("-badge-"), badge.clone()].concat()).unwrap_or_default(),
+        icon.overlay.as_ref().map(|overlay| vec![into_v32("-overlay-"), overlay.clone()].concat()).unwrap_or_default(),
+    ].concat();
+
+    // a `<symbol>` without a `viewBox` fills the `<use>` with the inner `<svg>`, which scales the icon with its own `viewBox`
+    doc_data.icon_symbols.entry(id.clone()).or_insert_with(|| vec![
+        into_v32(&format!("<symbol id=\"{}\">", from_v32(&id))),
+        self::render::format(&composite::nest(&template, &into_v32(" width=\"100%\" height=\"100%\"")), 0, None, false),
+        into_v32("</symbol>"),
+    ].concat());
+
+    // the shapes in the `<symbol>` inherit `fill` from `<use>`
+    let fill = match color {
+        Some((r, g, b)) => format!(" fill=\"rgb({r}, {g}, {b})\""),
+        None => String::new()
+    };
+
+    Some(vec![
+        into_v32(&format!("<svg width=\"{}\" height=\"{}\"", icon.size, icon.size)),
+        icon.attributes(),
+        vec![62],  // into_v32(">")
+        icon.title_element(),
+        into_v32(&format!("<use href=\"{sprite_path}#{}\"{fill}/></svg>", from_v32(&id))),
+    ].concat())
+}
+
+// `<svg>` with all the `<symbol>`s that the document uses, sorted by their ids
+// a sprite sheet in the same page is hidden
+pub fn render_sprite(symbols: &HashMap<Vec<u32>, Vec<u32>>, icon_mode: &IconMode) -> Vec<u32> {
+
+    if symbols.is_empty() {
+        return vec![];
+    }
+
+    let mut symbols = symbols.iter().collect::<Vec<(&Vec<u32>, &Vec<u32>)>>();
+    symbols.sort_unstable_by_key(|(id, _)| *id);
+
+    vec![
+        into_v32("<svg xmlns=\"http://www.w3.org/2000/svg\""),
+        if *icon_mode == IconMode::Sprite {
+            into_v32(" display=\"none\">")
+        } else {
+            vec![62]  // into_v32(">")
+        },
+        symbols.into_iter().map(|(_, symbol)| symbol.clone()).collect::<Vec<Vec<u32>>>().concat(),
+        into_v32("</svg>"),
+    ].concat()
+}
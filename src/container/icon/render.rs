@@ -90,6 +90,7 @@ result.insert(vec![112, 101, 114, 115, 111, 110], (vec![60, 115, 118, 103, 62996
 result.insert(vec![112, 104, 111, 110, 101], (vec![60, 115, 118, 103, 6299652, 32, 118, 105, 101, 119, 66, 111, 120, 61, 34, 48, 32, 48, 32, 50, 52, 32, 50, 52, 34, 6299651, 62, 60, 112, 97, 116, 104, 32, 100, 61, 34, 77, 49, 55, 46, 52, 32, 50, 50, 65, 49, 53, 46, 52, 50, 32, 49, 53, 46, 52, 50, 32, 48, 32, 48, 32, 49, 32, 50, 32, 54, 46, 54, 32, 52, 46, 54, 32, 52, 46, 54, 32, 48, 32, 48, 32, 49, 32, 54, 46, 54, 32, 50, 97, 51, 46, 57, 52, 32, 51, 46, 57, 52, 32, 48, 32, 48, 32, 49, 32, 46, 55, 55, 46, 48, 55, 32, 51, 46, 55, 57, 32, 51, 46, 55, 57, 32, 48, 32, 48, 32, 49, 32, 46, 55, 50, 46, 49, 56, 32, 49, 32, 49, 32, 48, 32, 48, 32, 49, 32, 46, 54, 53, 46, 55, 53, 108, 49, 46, 51, 55, 32, 54, 97, 49, 32, 49, 32, 48, 32, 48, 32, 49, 45, 46, 50, 54, 46, 57, 50, 99, 45, 46, 49, 51, 46, 49, 52, 45, 46, 49, 52, 46, 49, 53, 45, 49, 46, 51, 55, 46, 55, 57, 97, 57, 46, 57, 49, 32, 57, 46, 57, 49, 32, 48, 32, 48, 32, 48, 32, 52, 46, 56, 55, 32, 52, 46, 56, 57, 99, 46, 54, 53, 45, 49, 46, 50, 52, 46, 54, 54, 45, 49, 46, 50, 53, 46, 56, 45, 49, 46, 51, 56, 97, 49, 32, 49, 32, 48, 32, 48, 32, 49, 32, 46, 57, 50, 45, 46, 50, 54, 108, 54, 32, 49, 46, 51, 55, 97, 49, 32, 49, 32, 48, 32, 48, 32, 49, 32, 46, 55, 50, 46, 54, 53, 32, 52, 46, 51, 52, 32, 52, 46, 51, 52, 32, 48, 32, 48, 32, 49, 32, 46, 49, 57, 46, 55, 51, 32, 52, 46, 55, 55, 32, 52, 46, 55, 55, 32, 48, 32, 48, 32, 49, 32, 46, 48, 54, 46, 55, 54, 65, 52, 46, 54, 32, 52, 46, 54, 32, 48, 32, 48, 32, 49, 32, 49, 55, 46, 52, 32, 50, 50, 122, 34, 6299650, 47, 62, 60, 47, 115, 118, 103, 62], 4096));
 result.insert(vec![112, 105, 110], (vec![60, 115, 118, 103, 6299652, 32, 118, 105, 101, 119, 66, 111, 120, 61, 34, 48, 32, 48, 32, 50, 52, 32, 50, 52, 34, 6299651, 62, 60, 99, 105, 114, 99, 108, 101, 32, 99, 120, 61, 34, 49, 50, 34, 32, 99, 121, 61, 34, 57, 46, 53, 34, 32, 114, 61, 34, 49, 46, 53, 34, 6299650, 47, 62, 60, 112, 97, 116, 104, 32, 100, 61, 34, 77, 49, 50, 32, 50, 97, 56, 32, 56, 32, 48, 32, 48, 32, 48, 45, 56, 32, 55, 46, 57, 50, 99, 48, 32, 53, 46, 52, 56, 32, 55, 46, 48, 53, 32, 49, 49, 46, 53, 56, 32, 55, 46, 51, 53, 32, 49, 49, 46, 56, 52, 97, 49, 32, 49, 32, 48, 32, 48, 32, 48, 32, 49, 46, 51, 32, 48, 67, 49, 51, 32, 50, 49, 46, 53, 32, 50, 48, 32, 49, 53, 46, 52, 32, 50, 48, 32, 57, 46, 57, 50, 65, 56, 32, 56, 32, 48, 32, 48, 32, 48, 32, 49, 50, 32, 50, 122, 109, 48, 32, 49, 49, 97, 51, 46, 53, 32, 51, 46, 53, 32, 48, 32, 49, 32, 49, 32, 51, 46, 53, 45, 51, 46, 53, 65, 51, 46, 53, 32, 51, 46, 53, 32, 48, 32, 48, 32, 49, 32, 49, 50, 32, 49, 51, 122, 34, 6299650, 47, 62, 60, 47, 115, 118, 103, 62], 4096));
 result.insert(vec![112, 105, 122, 122, 97], (vec![60, 115, 118, 103, 6299652, 32, 118, 105, 101, 119, 66, 111, 120, 61, 34, 48, 32, 48, 32, 53, 49, 50, 32, 53, 49, 50, 34, 6299651, 62, 60, 112, 97, 116, 104, 6299650, 32, 100, 61, 34, 77, 52, 52, 49, 46, 56, 50, 32, 54, 55, 46, 56, 51, 67, 51, 56, 51, 46, 52, 52, 32, 52, 52, 46, 55, 51, 32, 51, 49, 55, 46, 51, 32, 51, 50, 32, 50, 53, 53, 46, 53, 54, 32, 51, 50, 32, 49, 57, 50, 32, 51, 50, 32, 49, 50, 53, 46, 55, 54, 32, 52, 52, 46, 53, 51, 32, 54, 57, 32, 54, 55, 46, 50, 54, 32, 52, 56, 46, 55, 32, 55, 53, 46, 52, 57, 32, 52, 53, 46, 50, 49, 32, 57, 48, 32, 52, 56, 46, 55, 49, 32, 49, 48, 48, 46, 56, 50, 76, 53, 50, 46, 55, 56, 32, 49, 49, 49, 97, 49, 54, 32, 49, 54, 32, 48, 32, 48, 48, 50, 49, 46, 51, 49, 32, 56, 46, 54, 57, 99, 49, 48, 46, 56, 45, 52, 46, 55, 54, 32, 50, 51, 46, 57, 51, 45, 49, 48, 46, 53, 52, 32, 50, 55, 45, 49, 49, 46, 55, 56, 67, 49, 52, 53, 46, 49, 32, 56, 57, 46, 54, 52, 32, 49, 57, 56, 46, 55, 49, 32, 56, 48, 32, 50, 53, 54, 32, 56, 48, 99, 53, 55, 46, 52, 55, 32, 48, 32, 49, 48, 56, 46, 48, 57, 32, 57, 46, 50, 52, 32, 49, 53, 52, 46, 55, 54, 32, 50, 56, 46, 50, 53, 32, 52, 46, 52, 50, 32, 49, 46, 56, 32, 49, 52, 46, 56, 56, 32, 54, 46, 52, 50, 32, 50, 54, 46, 49, 55, 32, 49, 49, 46, 52, 54, 97, 49, 54, 32, 49, 54, 32, 48, 32, 48, 48, 50, 49, 46, 51, 53, 45, 56, 46, 53, 57, 76, 52, 54, 50, 32, 49, 48, 50, 108, 46, 51, 52, 45, 46, 57, 99, 51, 46, 52, 53, 45, 49, 48, 46, 50, 49, 46, 49, 52, 45, 50, 53, 46, 48, 53, 45, 50, 48, 46, 53, 50, 45, 51, 51, 46, 50, 55, 122, 34, 47, 62, 60, 112, 97, 116, 104, 6299650, 32, 100, 61, 34, 77, 52, 48, 57, 46, 49, 56, 32, 49, 52, 48, 46, 56, 54, 67, 51, 54, 51, 46, 54, 55, 32, 49, 50, 50, 46, 53, 51, 32, 51, 48, 55, 46, 54, 56, 32, 49, 49, 50, 32, 50, 53, 53, 46, 53, 54, 32, 49, 49, 50, 97, 52, 50, 53, 32, 52, 50, 53, 32, 48, 32, 48, 48, 45, 49, 53, 51, 46, 55, 52, 32, 50, 56, 46, 56, 57, 99, 45, 46, 53, 51, 46, 50, 49, 45, 50, 46, 48, 54, 46, 56, 56, 45, 52, 46, 50, 57, 32, 49, 46, 56, 56, 97, 49, 54, 32, 49, 54, 32, 48, 32, 48, 48, 45, 56, 32, 50, 49, 46, 50, 55, 99, 52, 32, 56, 46, 55, 49, 32, 57, 46, 52, 50, 32, 50, 48, 46, 53, 56, 32, 49, 53, 46, 53, 32, 51, 51, 46, 56, 57, 67, 49, 51, 55, 46, 57, 52, 32, 50, 55, 48, 32, 49, 57, 57, 46, 50, 49, 32, 52, 48, 52, 32, 50, 50, 55, 46, 50, 54, 32, 52, 54, 50, 65, 51, 49, 46, 55, 52, 32, 51, 49, 46, 55, 52, 32, 48, 32, 48, 48, 50, 53, 54, 32, 52, 56, 48, 97, 51, 49, 46, 55, 51, 32, 51, 49, 46, 55, 51, 32, 48, 32, 48, 48, 50, 56, 46, 55, 54, 45, 49, 56, 46, 48, 54, 108, 46, 48, 54, 45, 46, 49, 51, 32, 49, 51, 55, 46, 51, 45, 50, 57, 55, 46, 53, 55, 97, 49, 53, 46, 57, 52, 32, 49, 53, 46, 57, 52, 32, 48, 32, 48, 48, 45, 56, 46, 51, 49, 45, 50, 49, 46, 52, 53, 99, 45, 50, 46, 50, 54, 45, 46, 57, 53, 45, 51, 46, 56, 53, 45, 49, 46, 54, 49, 45, 52, 46, 53, 45, 49, 46, 56, 55, 122, 109, 45, 50, 49, 53, 46, 49, 32, 56, 51, 46, 48, 55, 97, 51, 50, 32, 51, 50, 32, 48, 32, 49, 49, 50, 57, 46, 56, 53, 45, 50, 57, 46, 56, 53, 32, 51, 50, 32, 51, 50, 32, 48, 32, 48, 49, 45, 50, 57, 46, 56, 53, 32, 50, 57, 46, 56, 53, 122, 109, 54, 52, 32, 49, 50, 56, 97, 51, 50, 32, 51, 50, 32, 48, 32, 49, 49, 50, 57, 46, 56, 53, 45, 50, 57, 46, 56, 53, 32, 51, 50, 32, 51, 50, 32, 48, 32, 48, 49, 45, 50, 57, 46, 56, 53, 32, 50, 57, 46, 56, 53, 122, 109, 54, 52, 45, 49, 49, 50, 97, 51, 50, 32, 51, 50, 32, 48, 32, 49, 49, 50, 57, 46, 56, 53, 45, 50, 57, 46, 56, 53, 32, 51, 50, 32, 51, 50, 32, 48, 32, 48, 49, 45, 50, 57, 46, 56, 53, 32, 50, 57, 46, 56, 53, 122, 34, 47, 62, 60, 47, 115, 118, 103, 62], 4099));
+result.insert(vec![112, 108, 117, 115], (vec![60, 115, 118, 103, 6299652, 32, 118, 105, 101, 119, 66, 111, 120, 61, 34, 48, 32, 48, 32, 50, 52, 32, 50, 52, 34, 6299651, 62, 60, 112, 97, 116, 104, 32, 100, 61, 34, 77, 49, 57, 32, 49, 49, 104, 45, 54, 86, 53, 97, 49, 32, 49, 32, 48, 32, 48, 32, 48, 45, 50, 32, 48, 118, 54, 72, 53, 97, 49, 32, 49, 32, 48, 32, 48, 32, 48, 32, 48, 32, 50, 104, 54, 118, 54, 97, 49, 32, 49, 32, 48, 32, 48, 32, 48, 32, 50, 32, 48, 118, 45, 54, 104, 54, 97, 49, 32, 49, 32, 48, 32, 48, 32, 48, 32, 48, 45, 50, 122, 34, 6299650, 47, 62, 60, 47, 115, 118, 103, 62], 4096));
 result.insert(vec![112, 111, 119, 101, 114], (vec![60, 115, 118, 103, 6299652, 32, 118, 105, 101, 119, 66, 111, 120, 61, 34, 48, 32, 48, 32, 50, 52, 32, 50, 52, 34, 6299651, 62, 60, 112, 97, 116, 104, 32, 100, 61, 34, 77, 49, 50, 32, 49, 51, 97, 49, 32, 49, 32, 48, 32, 48, 32, 48, 32, 49, 45, 49, 86, 50, 97, 49, 32, 49, 32, 48, 32, 48, 32, 48, 45, 50, 32, 48, 118, 49, 48, 97, 49, 32, 49, 32, 48, 32, 48, 32, 48, 32, 49, 32, 49, 122, 34, 6299650, 47, 62, 60, 112, 97, 116, 104, 32, 100, 61, 34, 77, 49, 54, 46, 53, 57, 32, 51, 46, 49, 49, 97, 49, 32, 49, 32, 48, 32, 48, 32, 48, 45, 46, 57, 50, 32, 49, 46, 55, 56, 32, 56, 32, 56, 32, 48, 32, 49, 32, 49, 45, 55, 46, 51, 52, 32, 48, 32, 49, 32, 49, 32, 48, 32, 49, 32, 48, 45, 46, 57, 50, 45, 49, 46, 55, 56, 32, 49, 48, 32, 49, 48, 32, 48, 32, 49, 32, 48, 32, 57, 46, 49, 56, 32, 48, 122, 34, 6299650, 47, 62, 60, 47, 115, 118, 103, 62], 4096));
 result.insert(vec![112, 121, 116, 104, 111, 110], (vec![60, 115, 118, 103, 6299652, 32, 118, 105, 101, 119, 66, 111, 120, 61, 34, 48, 32, 48, 32, 49, 50, 56, 32, 49, 50, 56, 34, 6299651, 62, 60, 112, 97, 116, 104, 6299650, 32, 100, 61, 34, 77, 52, 57, 46, 51, 51, 32, 54, 50, 104, 50, 57, 46, 49, 53, 57, 67, 56, 54, 46, 54, 48, 54, 32, 54, 50, 32, 57, 51, 32, 53, 53, 46, 49, 51, 50, 32, 57, 51, 32, 52, 54, 46, 57, 56, 49, 86, 49, 57, 46, 49, 56, 51, 99, 48, 45, 55, 46, 57, 49, 50, 45, 54, 46, 54, 51, 50, 45, 49, 51, 46, 56, 53, 54, 45, 49, 52, 46, 53, 53, 53, 45, 49, 53, 46, 49, 55, 54, 45, 53, 46, 48, 49, 52, 45, 46, 56, 51, 53, 45, 49, 48, 46, 49, 57, 53, 45, 49, 46, 50, 49, 53, 45, 49, 53, 46, 49, 56, 55, 45, 49, 46, 49, 57, 49, 45, 52, 46, 57, 57, 46, 48, 50, 51, 45, 57, 46, 54, 49, 50, 46, 52, 52, 56, 45, 49, 51, 46, 56, 48, 53, 32, 49, 46, 49, 57, 49, 67, 51, 55, 46, 48, 57, 56, 32, 54, 46, 49, 56, 56, 32, 51, 53, 32, 49, 48, 46, 55, 53, 56, 32, 51, 53, 32, 49, 57, 46, 49, 56, 51, 86, 51, 48, 104, 50, 57, 118, 52, 72, 50, 51, 46, 55, 55, 54, 99, 45, 56, 46, 52, 56, 52, 32, 48, 45, 49, 53, 46, 57, 49, 52, 32, 53, 46, 49, 48, 56, 45, 49, 56, 46, 50, 51, 55, 32, 49, 52, 46, 56, 49, 49, 45, 50, 46, 54, 56, 49, 32, 49, 49, 46, 49, 50, 45, 50, 46, 56, 32, 49, 55, 46, 57, 49, 57, 32, 48, 32, 50, 57, 46, 53, 51, 67, 55, 46, 54, 49, 52, 32, 56, 54, 46, 57, 56, 51, 32, 49, 50, 46, 53, 54, 57, 32, 57, 51, 32, 50, 49, 46, 48, 53, 52, 32, 57, 51, 72, 51, 49, 86, 55, 57, 46, 57, 53, 50, 67, 51, 49, 32, 55, 48, 46, 51, 49, 53, 32, 51, 57, 46, 52, 50, 56, 32, 54, 50, 32, 52, 57, 46, 51, 51, 32, 54, 50, 122, 109, 45, 49, 46, 56, 51, 56, 45, 51, 57, 46, 49, 49, 99, 45, 51, 46, 48, 50, 54, 32, 48, 45, 53, 46, 52, 55, 56, 45, 50, 46, 52, 55, 57, 45, 53, 46, 52, 55, 56, 45, 53, 46, 53, 52, 53, 32, 48, 45, 51, 46, 48, 55, 57, 32, 50, 46, 52, 53, 49, 45, 53, 46, 53, 56, 49, 32, 53, 46, 52, 55, 56, 45, 53, 46, 53, 56, 49, 32, 51, 46, 48, 49, 53, 32, 48, 32, 53, 46, 52, 55, 57, 32, 50, 46, 53, 48, 50, 32, 53, 46, 52, 55, 57, 32, 53, 46, 53, 56, 49, 45, 46, 48, 48, 49, 32, 51, 46, 48, 54, 54, 45, 50, 46, 52, 54, 53, 32, 53, 46, 53, 52, 53, 45, 53, 46, 52, 55, 57, 32, 53, 46, 53, 52, 53, 122, 109, 55, 52, 46, 55, 56, 57, 32, 50, 53, 46, 57, 50, 49, 67, 49, 50, 48, 46, 49, 56, 51, 32, 52, 48, 46, 51, 54, 51, 32, 49, 49, 54, 46, 49, 55, 56, 32, 51, 52, 32, 49, 48, 55, 46, 54, 56, 50, 32, 51, 52, 72, 57, 55, 118, 49, 50, 46, 57, 56, 49, 67, 57, 55, 32, 53, 55, 46, 48, 51, 49, 32, 56, 56, 46, 50, 48, 54, 32, 54, 53, 32, 55, 56, 46, 52, 56, 57, 32, 54, 53, 72, 52, 57, 46, 51, 51, 67, 52, 49, 46, 51, 52, 50, 32, 54, 53, 32, 51, 53, 32, 55, 50, 46, 51, 50, 54, 32, 51, 53, 32, 56, 48, 46, 51, 50, 54, 118, 50, 55, 46, 56, 99, 48, 32, 55, 46, 57, 49, 32, 54, 46, 55, 52, 53, 32, 49, 50, 46, 53, 54, 52, 32, 49, 52, 46, 52, 54, 50, 32, 49, 52, 46, 56, 51, 52, 32, 57, 46, 50, 52, 50, 32, 50, 46, 55, 49, 55, 32, 49, 55, 46, 57, 57, 52, 32, 51, 46, 50, 48, 56, 32, 50, 57, 46, 48, 53, 49, 32, 48, 67, 56, 53, 46, 56, 54, 50, 32, 49, 50, 48, 46, 56, 51, 49, 32, 57, 51, 32, 49, 49, 54, 46, 53, 52, 57, 32, 57, 51, 32, 49, 48, 56, 46, 49, 50, 54, 86, 57, 55, 72, 54, 52, 118, 45, 52, 104, 52, 51, 46, 54, 56, 50, 99, 56, 46, 52, 56, 52, 32, 48, 32, 49, 49, 46, 54, 52, 55, 45, 53, 46, 55, 55, 54, 32, 49, 52, 46, 53, 57, 57, 45, 49, 52, 46, 54, 54, 32, 51, 46, 48, 52, 55, 45, 57, 46, 49, 52, 53, 32, 50, 46, 57, 49, 54, 45, 49, 55, 46, 55, 57, 57, 32, 48, 45, 50, 57, 46, 53, 50, 57, 122, 109, 45, 52, 49, 46, 57, 53, 53, 32, 53, 53, 46, 54, 48, 54, 99, 51, 46, 48, 50, 55, 32, 48, 32, 53, 46, 52, 55, 57, 32, 50, 46, 52, 55, 57, 32, 53, 46, 52, 55, 57, 32, 53, 46, 53, 52, 55, 32, 48, 32, 51, 46, 48, 55, 54, 45, 50, 46, 52, 53, 49, 32, 53, 46, 53, 55, 57, 45, 53, 46, 52, 55, 57, 32, 53, 46, 53, 55, 57, 45, 51, 46, 48, 49, 53, 32, 48, 45, 53, 46, 52, 55, 56, 45, 50, 46, 53, 48, 50, 45, 53, 46, 52, 55, 56, 45, 53, 46, 53, 55, 57, 32, 48, 45, 51, 46, 48, 54, 56, 32, 50, 46, 52, 54, 51, 45, 53, 46, 53, 52, 55, 32, 53, 46, 52, 55, 56, 45, 53, 46, 53, 52, 55, 122, 34, 47, 62, 60, 47, 115, 118, 103, 62], 4098));
 result.insert(vec![113, 117, 101, 115, 116, 105, 111, 110], (vec![60, 115, 118, 103, 6299652, 6299651, 32, 118, 105, 101, 119, 66, 111, 120, 61, 34, 48, 32, 48, 32, 53, 49, 50, 32, 53, 49, 50, 34, 62, 60, 112, 97, 116, 104, 6299650, 32, 100, 61, 34, 77, 50, 53, 54, 44, 54, 52, 67, 49, 53, 48, 44, 54, 52, 44, 54, 52, 44, 49, 53, 48, 44, 54, 52, 44, 50, 53, 54, 115, 56, 54, 44, 49, 57, 50, 44, 49, 57, 50, 44, 49, 57, 50, 44, 49, 57, 50, 45, 56, 54, 44, 49, 57, 50, 45, 49, 57, 50, 83, 51, 54, 50, 44, 54, 52, 44, 50, 53, 54, 44, 54, 52, 90, 109, 45, 54, 44, 51, 48, 52, 97, 50, 48, 44, 50, 48, 44, 48, 44, 49, 44, 49, 44, 50, 48, 45, 50, 48, 65, 50, 48, 44, 50, 48, 44, 48, 44, 48, 44, 49, 44, 50, 53, 48, 44, 51, 54, 56, 90, 109, 51, 51, 46, 52, 52, 45, 49, 48, 50, 67, 50, 54, 55, 46, 50, 51, 44, 50, 55, 54, 46, 56, 56, 44, 50, 54, 53, 44, 50, 56, 54, 46, 56, 53, 44, 50, 54, 53, 44, 50, 57, 54, 97, 49, 52, 44, 49, 52, 44, 48, 44, 48, 44, 49, 45, 50, 56, 44, 48, 99, 48, 45, 50, 49, 46, 57, 49, 44, 49, 48, 46, 48, 56, 45, 51, 57, 46, 51, 51, 44, 51, 48, 46, 56, 50, 45, 53, 51, 46, 50, 54, 67, 50, 56, 55, 46, 49, 44, 50, 50, 57, 46, 56, 44, 50, 57, 56, 44, 50, 50, 49, 46, 54, 44, 50, 57, 56, 44, 50, 48, 51, 46, 53, 55, 99, 48, 45, 49, 50, 46, 50, 54, 45, 55, 45, 50, 49, 46, 53, 55, 45, 50, 49, 46, 52, 57, 45, 50, 56, 46, 52, 54, 45, 51, 46, 52, 49, 45, 49, 46, 54, 50, 45, 49, 49, 45, 51, 46, 50, 45, 50, 48, 46, 51, 52, 45, 51, 46, 48, 57, 45, 49, 49, 46, 55, 50, 46, 49, 53, 45, 50, 48, 46, 56, 50, 44, 50, 46, 57, 53, 45, 50, 55, 46, 56, 51, 44, 56, 46, 53, 57, 67, 50, 49, 53, 46, 49, 50, 44, 49, 57, 49, 46, 50, 53, 44, 50, 49, 52, 44, 50, 48, 50, 46, 56, 51, 44, 50, 49, 52, 44, 50, 48, 51, 97, 49, 52, 44, 49, 52, 44, 48, 44, 49, 44, 49, 45, 50, 56, 45, 49, 46, 51, 53, 99, 46, 49, 49, 45, 50, 46, 52, 51, 44, 49, 46, 56, 45, 50, 52, 46, 51, 50, 44, 50, 52, 46, 55, 55, 45, 52, 50, 46, 56, 44, 49, 49, 46, 57, 49, 45, 57, 46, 53, 56, 44, 50, 55, 46, 48, 54, 45, 49, 52, 46, 53, 54, 44, 52, 53, 45, 49, 52, 46, 55, 56, 44, 49, 50, 46, 55, 45, 46, 49, 53, 44, 50, 52, 46, 54, 51, 44, 50, 44, 51, 50, 46, 55, 50, 44, 53, 46, 56, 50, 67, 51, 49, 50, 46, 55, 44, 49, 54, 49, 46, 51, 52, 44, 51, 50, 54, 44, 49, 56, 48, 46, 52, 51, 44, 51, 50, 54, 44, 50, 48, 51, 46, 53, 55, 44, 51, 50, 54, 44, 50, 51, 55, 46, 52, 44, 51, 48, 51, 46, 51, 57, 44, 50, 53, 50, 46, 53, 57, 44, 50, 56, 51, 46, 52, 52, 44, 50, 54, 54, 90, 34, 47, 62, 60, 47, 115, 118, 103, 62], 4099));
@@ -200,9 +201,9 @@ pub fn format(icon: &Vec<u32>, size: usize, color: Option<(u8, u8, u8)>, standal
     result
 }
 
-const COLOR: u32 = 0x602002;
-const SIZE: u32 = 0x602003;
-const XMLNS: u32 = 0x602004;
+pub const COLOR: u32 = 0x602002;
+pub const SIZE: u32 = 0x602003;
+pub const XMLNS: u32 = 0x602004;
 #[cfg(test)] pub const EVA_ICON: usize = 0x1000;
 #[cfg(test)] pub const MATERIAL_ICON: usize = 0x1001;
 #[cfg(test)] pub const DEV_ICON: usize = 0x1002;
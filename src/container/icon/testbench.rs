@@ -27,6 +27,15 @@ fn render_icon_test() {
         String::from("`[[turquoise]]  [[icon=github]]  [[/turquoise]]`: [[turquoise]][[icon=github]][[/turquoise]]\n\n"),
         String::from("`[[seagreen]]   [[icon=github]]   [[/seagreen]]`: [[seagreen]][[icon=github]][[/seagreen]]\n\n"),
         String::from("`[[gold]]       [[icon=github]]       [[/gold]]`: [[gold]][[icon=github]][[/gold]]\n\n"),
+        String::from("`[[icon=github, color = red]]`: [[icon=github, color = red]]\n\n"),
+        String::from("`[[icon=github, color = #ff8800]]`: [[icon=github, color = #ff8800]]\n\n"),
+        String::from("## Transforms\n\n"),
+        String::from("`[[icon=arrowforward, rotate = 90]]`: [[icon=arrowforward, rotate = 90]]\n\n"),
+        String::from("`[[icon=arrowforward, flip = horizontal]]`: [[icon=arrowforward, flip = horizontal]]\n\n"),
+        String::from("## Composition\n\n"),
+        String::from("`[[icon=document, badge = plus]]`: [[icon=document, badge = plus]]\n\n"),
+        String::from("`[[icon=folder, overlay = lock]]`: [[icon=folder, overlay = lock]]\n\n"),
+        String::from("`[[icon=person, badge = star, overlay = shield]]`: [[icon=person, badge = star, overlay = shield]]\n\n"),
         String::from("## Alignments\n\n"),
         String::from("```\n"),
         String::from("[[center]]\n\n"),
@@ -109,4 +118,174 @@ fn render_icon_test() {
 
     let mut f = File::create("icon_test.html").unwrap();
     f.write_all(html.as_bytes()).unwrap();
-}
\ No newline at end of file
+}
+#[test]
+fn user_icon_test() {
+    use crate::{render_to_html, Diagnostic, RenderOption};
+    use crate::utils::{into_v32, remove_whitespaces};
+    use super::IconSet;
+
+    let mut icons = IconSet::default();
+    icons.add(
+        "Our_Logo",
+        "<?xml version=\"1.0\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" height='100' viewBox=\"0 0 10 10\"><circle r=\"4\"/></svg>\n"
+    ).unwrap();
+
+    assert!(icons.add("empty", "<div></div>").is_err());
+    assert!(icons.add("unclosed", "<svg viewBox=\"0 0 10 10\"").is_err());
+
+    let mut render_option = RenderOption::default();
+    render_option.set_icons(icons);
+
+    let result = render_to_html(
+        "[[icon = ourlogo, size = 24]] [[icon = nope]]\n\n[[note, icon = ourlogo]]\n\nHi\n\n[[/note]]",
+        render_option
+    );
+
+    assert_eq!(
+        remove_whitespaces(&into_v32(&result.content)),
+        remove_whitespaces(&into_v32(
+            "<p><svg width=\"24\" height=\"24\" viewBox=\"0 0 10 10\"><circle r=\"4\"/></svg> [[icon = nope]]</p>
+            <div class=\"admonition admonition-note\"><div class=\"admonition-title\"><svg width=\"20\" height=\"20\" viewBox=\"0 0 10 10\"><circle r=\"4\"/></svg><span>Note</span></div>
+            <div class=\"admonition-content\"><p>Hi</p></div></div>"
+        ))
+    );
+    assert_eq!(result.diagnostics, vec![Diagnostic::UnknownIcon { name: String::from("nope") }]);
+
+    // without the icon set, `ourlogo` is unknown
+    let result = render_to_html("[[icon = ourlogo]]", RenderOption::default());
+    assert_eq!(result.diagnostics, vec![Diagnostic::UnknownIcon { name: String::from("ourlogo") }]);
+}
+
+#[test]
+fn composite_icon_test() {
+    use crate::{render_to_html, Diagnostic, RenderOption};
+    use crate::utils::{into_v32, remove_whitespaces};
+    use super::IconSet;
+
+    let mut icons = IconSet::default();
+    icons.add("square", "<svg viewBox=\"0 0 10 10\"><rect width=\"10\" height=\"10\"/></svg>").unwrap();
+    icons.add("dot", "<svg viewBox=\"0 0 2 2\"><circle r=\"1\"/></svg>").unwrap();
+
+    let mut render_option = RenderOption::default();
+    render_option.set_icons(icons);
+
+    let result = render_to_html(
+        "[[icon = square, badge = dot, size = 24]] [[icon = square, overlay = dot]] [[icon = square, badge = dot, overlay = dot]] [[icon = square, badge = nope]] [[icon = square, size = 8, size = 9]]",
+        render_option
+    );
+
+    assert_eq!(
+        remove_whitespaces(&into_v32(&result.content)),
+        remove_whitespaces(&into_v32(
+            "<p><svg width=\"24\" height=\"24\" viewBox=\"0 0 24 24\">
+                <svg x=\"0\" y=\"6\" width=\"18\" height=\"18\" viewBox=\"0 0 10 10\"><rect width=\"10\" height=\"10\"/></svg>
+                <svg x=\"12\" y=\"0\" width=\"12\" height=\"12\" viewBox=\"0 0 2 2\"><circle r=\"1\"/></svg>
+            </svg>
+            <svg width=\"32\" height=\"32\" viewBox=\"0 0 24 24\">
+                <svg x=\"0\" y=\"0\" width=\"18\" height=\"18\" viewBox=\"0 0 10 10\"><rect width=\"10\" height=\"10\"/></svg>
+                <svg x=\"12\" y=\"12\" width=\"12\" height=\"12\" viewBox=\"0 0 2 2\"><circle r=\"1\"/></svg>
+            </svg>
+            <svg width=\"32\" height=\"32\" viewBox=\"0 0 24 24\">
+                <svg x=\"0\" y=\"3\" width=\"18\" height=\"18\" viewBox=\"0 0 10 10\"><rect width=\"10\" height=\"10\"/></svg>
+                <svg x=\"12\" y=\"0\" width=\"12\" height=\"12\" viewBox=\"0 0 2 2\"><circle r=\"1\"/></svg>
+                <svg x=\"12\" y=\"12\" width=\"12\" height=\"12\" viewBox=\"0 0 2 2\"><circle r=\"1\"/></svg>
+            </svg>
+            [[icon = square, badge = nope]] [[icon = square, size = 8, size = 9]]</p>"
+        ))
+    );
+    assert_eq!(result.diagnostics, vec![Diagnostic::UnknownIcon { name: String::from("nope") }]);
+}
+
+#[test]
+fn icon_sprite_test() {
+    use crate::{render_to_html, IconMode, RenderOption};
+    use crate::utils::{into_v32, remove_whitespaces};
+    use super::IconSet;
+
+    let mut icons = IconSet::default();
+    icons.add("square", "<svg viewBox=\"0 0 10 10\"><rect width=\"10\" height=\"10\"/></svg>").unwrap();
+    icons.add("dot", "<svg viewBox=\"0 0 2 2\"><circle r=\"1\"/></svg>").unwrap();
+
+    let mut render_option = RenderOption::default();
+    render_option.set_icons(icons).set_icon_mode(IconMode::Sprite);
+
+    let result = render_to_html("[[icon = square]] [[icon = square, size = 16]] [[icon = square, badge = dot]]", render_option.clone());
+
+    assert_eq!(
+        remove_whitespaces(&into_v32(&result.content)),
+        remove_whitespaces(&into_v32(
+            "<p><svg width=\"32\" height=\"32\"><use href=\"#mdxt-icon-square\"/></svg>
+            <svg width=\"16\" height=\"16\"><use href=\"#mdxt-icon-square\"/></svg>
+            <svg width=\"32\" height=\"32\"><use href=\"#mdxt-icon-square-badge-dot\"/></svg></p>"
+        ))
+    );
+
+    // each icon is in the sprite sheet only once
+    assert_eq!(
+        remove_whitespaces(&into_v32(&result.icon_sprite)),
+        remove_whitespaces(&into_v32(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" display=\"none\">
+                <symbol id=\"mdxt-icon-square\"><svg width=\"100%\" height=\"100%\" viewBox=\"0 0 10 10\"><rect width=\"10\" height=\"10\"/></svg></symbol>
+                <symbol id=\"mdxt-icon-square-badge-dot\"><svg width=\"100%\" height=\"100%\" viewBox=\"0 0 24 24\">
+                    <svg x=\"0\" y=\"6\" width=\"18\" height=\"18\" viewBox=\"0 0 10 10\"><rect width=\"10\" height=\"10\"/></svg>
+                    <svg x=\"12\" y=\"0\" width=\"12\" height=\"12\" viewBox=\"0 0 2 2\"><circle r=\"1\"/></svg>
+                </svg></symbol>
+            </svg>"
+        ))
+    );
+
+    render_option.set_icon_mode(IconMode::ExternalSprite(String::from("icons.svg")));
+    let result = render_to_html("[[icon = dot]]", render_option.clone());
+
+    assert_eq!(
+        remove_whitespaces(&into_v32(&result.content)),
+        remove_whitespaces(&into_v32("<p><svg width=\"32\" height=\"32\"><use href=\"icons.svg#mdxt-icon-dot\"/></svg></p>"))
+    );
+    assert_eq!(
+        remove_whitespaces(&into_v32(&result.icon_sprite)),
+        remove_whitespaces(&into_v32(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\"><symbol id=\"mdxt-icon-dot\"><svg width=\"100%\" height=\"100%\" viewBox=\"0 0 2 2\"><circle r=\"1\"/></svg></symbol></svg>"
+        ))
+    );
+
+    render_option.set_icon_mode(IconMode::Inline);
+    assert!(render_to_html("[[icon = dot]]", render_option).icon_sprite.is_empty());
+}
+
+#[test]
+fn icon_attribute_test() {
+    use crate::{render_to_html, IconMode, RenderOption};
+    use crate::utils::{into_v32, remove_whitespaces};
+    use super::IconSet;
+
+    let mut icons = IconSet::default();
+    icons.add("dot", "<svg viewBox=\"0 0 2 2\"><circle r=\"1\"/></svg>").unwrap();
+
+    let mut render_option = RenderOption::default();
+    render_option.set_icons(icons);
+
+    let content = "[[icon = dot, color = red]] [[icon = dot, color = #f80, size = 16]] [[icon = dot, title = Red Dot]] [[icon = dot, rotate = 90, flip = horizontal, decorative]]
+
+[[icon = dot, color = crimson]] [[icon = dot, decorative, title = Dot]] [[icon = dot, flip = diagonal]]";
+
+    assert_eq!(
+        remove_whitespaces(&into_v32(&render_to_html(content, render_option.clone()).content)),
+        remove_whitespaces(&into_v32(
+            "<p><svg width=\"32\" height=\"32\" viewBox=\"0 0 2 2\"><circle fill=\"rgb(192, 32, 32)\" r=\"1\"/></svg>
+            <svg width=\"16\" height=\"16\" viewBox=\"0 0 2 2\"><circle fill=\"rgb(255, 136, 0)\" r=\"1\"/></svg>
+            <svg role=\"img\" aria-label=\"Red Dot\" width=\"32\" height=\"32\" viewBox=\"0 0 2 2\"><title>Red Dot</title><circle r=\"1\"/></svg>
+            <svg aria-hidden=\"true\" transform=\"rotate(90) scale(-1, 1)\" width=\"32\" height=\"32\" viewBox=\"0 0 2 2\"><circle r=\"1\"/></svg></p>
+            <p>[[icon = dot, color = crimson]] [[icon = dot, decorative, title = Dot]] [[icon = dot, flip = diagonal]]</p>"
+        ))
+    );
+
+    render_option.set_icon_mode(IconMode::Sprite);
+
+    assert_eq!(
+        remove_whitespaces(&into_v32(&render_to_html("[[icon = dot, color = red, title = Red Dot]]", render_option).content)),
+        remove_whitespaces(&into_v32(
+            "<p><svg width=\"32\" height=\"32\" role=\"img\" aria-label=\"Red Dot\"><title>Red Dot</title><use href=\"#mdxt-icon-dot\" fill=\"rgb(192, 32, 32)\"/></svg></p>"
+        ))
+    );
+}
@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod testbench;
+
+use crate::ast::doc_data::DocData;
+use crate::escape::undo_html_escapes;
+use crate::inline::math::Math;
+use crate::render::render_option::RenderOption;
+use crate::utils::{from_v32, into_v32};
+
+/*
+```
+[[math, id = eq-euler]]
+e sup{i pi} + 1 = 0
+[[/math]]
+```
+
+A multiline `[[math]]` macro is rendered to a block math, `<math display="block">`.
+Each `br` starts a new row, and `&` aligns the rows, like `align` of LaTeX.
+
+- `number`: it's numbered.
+- `nonumber`: it's not numbered, even though `RenderOption.equation_numbers` is on.
+- `id = ...`: it's numbered, and `[[ref = ...]]` can refer to it.
+- `latex`: the content is LaTeX, not MDxt math. `$$` lines are the same as `[[math, latex]]` when `RenderOption.dollar_math` is on.
+*/
+#[derive(Clone)]
+pub struct MathBlock {
+    content: Vec<u32>,
+    id: Option<Vec<u32>>,
+    numbered: Option<bool>,  // None if it follows the render option
+    latex: bool,
+    speech: bool,  // `RenderOption.math_speech`
+    error_span: bool,  // `RenderOption.math_error_span`
+
+    // it's numbered when the inlines are parsed, so that the numbers follow the order of the document
+    index: Option<usize>
+}
+
+impl MathBlock {
+
+    // it assumes that the arguments are valid
+    pub fn new(content: Vec<u32>, arguments: &[Vec<Vec<u32>>]) -> Self {
+        let mut id = None;
+        let mut numbered = None;
+        let mut latex = false;
+
+        for argument in arguments[1..].iter() {
+
+            if argument[0] == into_v32("id") {
+                id = Some(argument[1].clone());
+            }
+
+            else if argument[0] == into_v32("number") {
+                numbered = Some(true);
+            }
+
+            else if argument[0] == into_v32("nonumber") {
+                numbered = Some(false);
+            }
+
+            else if argument[0] == into_v32("latex") {
+                latex = true;
+            }
+
+        }
+
+        MathBlock { content, id, numbered, latex, speech: false, error_span: false, index: None }
+    }
+
+    pub fn parse_inlines(&mut self, doc_data: &mut DocData, render_option: &RenderOption) {
+        let numbered = self.numbered.unwrap_or(render_option.equation_numbers || self.id.is_some());
+        self.speech = render_option.math_speech;
+        self.error_span = render_option.math_error_span;
+        doc_data.add_math_errors(&self.math());
+
+        if numbered {
+            let index = doc_data.add_equation();
+            self.index = Some(index);
+
+            if let Some(id) = &self.id {
+                doc_data.add_label(id, &format!("Equation {index}"));
+            }
+
+        }
+
+    }
+
+    fn math(&self) -> Math {
+
+        if self.latex {
+            Math::from_latex(&self.content)
+        }
+
+        else {
+            Math::from_mdxt(&undo_html_escapes(&self.content))
+        }
+
+    }
+
+    // the anchor doesn't change unless the order of the numbered equations changes
+    fn anchor(&self) -> Option<String> {
+
+        match (&self.id, self.index) {
+            (Some(id), _) => Some(from_v32(id)),
+            (None, Some(index)) => Some(format!("equation-{index}")),
+            (None, None) => None
+        }
+
+    }
+
+    pub fn to_html(&self, class_prefix: &str) -> Vec<u32> {
+        let id = match self.anchor() {
+            Some(anchor) => format!(" id=\"{anchor}\""),
+            None => String::new()
+        };
+
+        let number = match self.index {
+            Some(index) => into_v32(&format!("<span class=\"{class_prefix}equation-number\">({index})</span>")),
+            None => vec![]
+        };
+
+        let math = self.math();
+
+        let math_ml = if self.speech {
+            math.with_speech(math.to_block_math_ml(), class_prefix)
+        } else {
+            math.to_block_math_ml()
+        };
+
+        let math_ml = if self.error_span {
+            math.with_error_span(math_ml, class_prefix)
+        } else {
+            math_ml
+        };
+
+        vec![
+            into_v32(&format!("<div class=\"{class_prefix}math-block\"{id}>")),
+            math_ml,
+            number,
+            vec![60, 47, 100, 105, 118, 62]  // into_v32("</div>")
+        ].concat()
+    }
+
+}
@@ -0,0 +1,95 @@
+use crate::utils::{into_v32, remove_whitespaces};
+use crate::{render_to_html, render_to_html_with_default_options, RenderOption};
+
+fn math_block_samples() -> Vec<(String, String)> {
+    let result = vec![
+        ("
+[[math]]
+sqrt{2}
+[[/math]]
+", "
+<div class=\"math-block\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"block\" alttext=\"sqrt(2)\"><msqrt><mn>2</mn></msqrt></math>
+</div>
+"), ("
+[[math]]
+a &= b + c br
+  &= d br br
+[[/math]]
+", "
+<div class=\"math-block\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"block\" alttext=\"a = b + c; = d\">
+        <mtable displaystyle=\"true\" columnalign=\"right left\" columnspacing=\"0em\">
+            <mtr><mtd><mi>a</mi></mtd><mtd><mo>=</mo><mi>b</mi><mo>+</mo><mi>c</mi></mtd></mtr>
+            <mtr><mtd></mtd><mtd><mo>=</mo><mi>d</mi></mtd></mtr>
+        </mtable>
+    </math>
+</div>
+"), ("
+See [[ref = eq-pythagoras]].
+
+[[math, number]]
+a < b
+[[/math]]
+
+[[math, id = eq-pythagoras]]
+sup{a}{2} + sup{b}{2} = sup{c}{2}
+[[/math]]
+", "
+<p>See <a class=\"cross-reference\" href=\"#eq-pythagoras\">Equation 2</a>.</p>
+<div class=\"math-block\" id=\"equation-1\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"block\" alttext=\"a &lt; b\"><mi>a</mi><mo>&lt;</mo><mi>b</mi></math>
+    <span class=\"equation-number\">(1)</span>
+</div>
+<div class=\"math-block\" id=\"eq-pythagoras\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"block\" alttext=\"a^2 + b^2 = c^2\"><msup><mi>a</mi><mn>2</mn></msup><mo>+</mo><msup><mi>b</mi><mn>2</mn></msup><mo>=</mo><msup><mi>c</mi><mn>2</mn></msup></math>
+    <span class=\"equation-number\">(2)</span>
+</div>
+")
+    ];
+
+    result.into_iter().map(
+        |(case, answer)| (case.to_string(), answer.to_string())
+    ).collect()
+}
+
+#[test]
+fn math_block_test() {
+    for (md, html) in math_block_samples().iter() {
+        let rendered = render_to_html_with_default_options(md);
+
+        if remove_whitespaces(&into_v32(&rendered)) != remove_whitespaces(&into_v32(html)) {
+            panic!("{md} \n\n {rendered}");
+        }
+
+    }
+
+}
+
+#[test]
+fn equation_number_test() {
+    let md = "
+[[math]]
+x
+[[/math]]
+
+[[math, nonumber]]
+y
+[[/math]]
+
+[[math]]
+z
+[[/math]]
+";
+    let mut render_option = RenderOption::default();
+    render_option.set_equation_numbers(true);
+
+    let result = render_to_html(md, render_option);
+
+    assert_eq!(result.equation_count, 2);
+    assert!(result.content.contains("<div class=\"math-block\" id=\"equation-2\">"));
+    assert_eq!(result.content.matches("equation-number").count(), 2);
+
+    let result = render_to_html(md, RenderOption::default());
+    assert_eq!(result.equation_count, 0);
+}
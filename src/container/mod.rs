@@ -1,8 +1,12 @@
+pub mod admonition;
 pub mod blockquote;
 pub mod codefence;
+pub mod definition_list;
+pub mod figure;
 pub mod header;
 pub mod icon;
 pub mod list;
+pub mod math_block;
 pub mod metadata;
 pub mod sidebar;
 pub mod table;
\ No newline at end of file
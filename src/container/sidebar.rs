@@ -6,8 +6,15 @@ use crate::ast::{
 use crate::utils::into_v32;
 
 pub fn sidebar_to_html(nodes: &Vec<Node>, toc_rendered: &Vec<u32>, render_option: &RenderOption, doc_data: &mut DocData) -> Vec<u32> {
+    // `RenderOption.csp`: the handlers are registered by `csp::sidebar_listener`
+    let (toggle_handler, close_handler) = if render_option.csp {
+        (" data-mdxt-sidebar=\"toggle\"", " data-mdxt-sidebar=\"close\"")
+    } else {
+        (" onclick=\"mdxt_toggle_sidebar()\"", " onclick=\"mdxt_close_sidebar()\"")
+    };
+
     let mut result = vec![
-        into_v32("<div id=\"mdxt-sidebar-toggle\" onclick=\"mdxt_toggle_sidebar()\"><span id=\"mdxt-sidebar-button-content\">≫</span></div><div id=\"mdxt-sidebar\"><div id=\"mdxt-sidebar-close-button\" onclick=\"mdxt_close_sidebar()\">&times;</div><div id=\"mdxt-sidebar-content\">")
+        into_v32(&format!("<div id=\"mdxt-sidebar-toggle\"{toggle_handler}><span id=\"mdxt-sidebar-button-content\">≫</span></div><div id=\"mdxt-sidebar\"><div id=\"mdxt-sidebar-close-button\"{close_handler}>&times;</div><div id=\"mdxt-sidebar-content\">"))
     ];
 
     for node in nodes.iter() {
@@ -14,7 +14,7 @@ Table-wide macros
 
 use crate::inline::macros::{
     parse_arguments,
-    predicate::read_macro,
+    predicate::{read_macro, read_raw_macro_argument},
 };
 use crate::utils::{get_bracket_end_index, into_v32, remove_whitespaces};
 
@@ -25,6 +25,7 @@ pub struct TableMacros {
     pub id: Option<Vec<u32>>,
     pub classes: Vec<Vec<u32>>,
     pub sort: bool,
+    pub caption: Option<Vec<u32>>,
 }
 
 pub fn try_parse_macro(content: &[u32]) -> TableMacros {
@@ -83,12 +84,20 @@ pub fn try_parse_macro(content: &[u32]) -> TableMacros {
         index = get_bracket_end_index(&macros, index).unwrap() + 1;
     }
 
+    // `[[caption = Some Caption]]`
+    // unlike the other arguments, it preserves the cases and the spaces
+    let caption = (0..content.len()).filter(
+        |i| content[*i] == '[' as u32 && content.get(i + 1) == Some(&('[' as u32))
+    ).find_map(
+        |i| read_raw_macro_argument(content, i, &into_v32("caption"))
+    );
+
     // if both `collapsible` and `headless` are set, `headless` is ignored
     if collapsible && headless {
         headless = false;
     }
 
-    TableMacros { collapsible, default_hidden, headless, id, classes, sort }
+    TableMacros { collapsible, default_hidden, headless, id, classes, sort, caption }
 }
 
 /// You can also write your own.
@@ -103,11 +112,16 @@ pub fn try_parse_macro(content: &[u32]) -> TableMacros {
 /// }
 /// ```
 pub fn collapsible_table_javascript() -> String {
-"function collapse_table(n) {
+    prefixed_collapsible_table_javascript("")
+}
+
+// `RenderOption.class_prefix` goes to the class names
+pub(crate) fn prefixed_collapsible_table_javascript(class_prefix: &str) -> String {
+format!("function collapse_table(n) {{
     var head = document.getElementById(\"table-collapse-toggle-\" + n);
-    head.classList.toggle(\"collapsed\");
+    head.classList.toggle(\"{class_prefix}collapsed\");
 
     var content = document.getElementById(\"collapsible-table-\" + n);
-    content.classList.toggle(\"invisible\");
-}".to_string()
+    content.classList.toggle(\"{class_prefix}invisible\");
+}}")
 }
@@ -10,6 +10,7 @@ use alignment::parse_alignments;
 use cell::{Cell, get_colspan, row_to_cells};
 use macros::{TableMacros, try_parse_macro};
 use crate::ast::{doc_data::DocData, line::Line};
+use crate::container::figure::{Caption, CaptionKind};
 use crate::escape::BACKSLASH_ESCAPE_OFFSET;
 use crate::inline::parse::{escape_code_spans, is_code_span_marker_begin, is_code_span_marker_end};
 use crate::inline::macros::predicate::is_special_macro;
@@ -33,6 +34,7 @@ pub struct Table {
     classes: Vec<Vec<u32>>,
 
     sort: bool,
+    caption: Option<Caption>,
     index: usize,
 }
 
@@ -87,7 +89,8 @@ impl Table {
             mut id,
             mut classes,
             mut sort,
-        ) = (false, false, false, None, vec![], false);
+            mut caption,
+        ) = (false, false, false, None, vec![], false, None);
 
         // if rows[0] is `|!![[whatever macro ...]] [[another macro...]]|`
         if !rows.is_empty()
@@ -101,6 +104,7 @@ impl Table {
                 id: id_,
                 classes: classes_,
                 sort: sort_,
+                caption: caption_,
             } = try_parse_macro(&rows[0].content);
             collapsible = collapsible_;
             default_hidden = default_hidden_;
@@ -108,6 +112,7 @@ impl Table {
             id = id_;
             classes = classes_;
             sort = sort_;
+            caption = caption_.map(|caption| Caption::new(CaptionKind::Table, caption));
 
             rows = &rows[1..];
         }
@@ -120,7 +125,7 @@ impl Table {
             header, cells, cols,
             collapsible, default_hidden, headless,
             id, classes, sort,
-            index,
+            caption, index,
         }
     }
 
@@ -147,13 +152,18 @@ impl Table {
             }
         );
 
+        if let Some(caption) = &mut self.caption {
+            caption.parse_inlines(&self.id, doc_data, render_option);
+        }
+
         if self.sort {
             let sort_data = self.get_sort_data();
             todo!();
         }
     }
 
-    pub fn to_html(&self, toc_rendered: &[u32], class_prefix: &str) -> Vec<u32> {
+    pub fn to_html(&self, toc_rendered: &[u32], render_option: &RenderOption) -> Vec<u32> {
+        let class_prefix = &render_option.class_prefix;
         let mut result = Vec::with_capacity(6 + self.header.len() + 3 * self.cells.len());
         let mut classes = vec![];
 
@@ -185,14 +195,25 @@ impl Table {
             }
         )));
 
+        if let Some(caption) = &self.caption {
+            result.push(caption.to_html(toc_rendered, class_prefix));
+        }
+
         let collapsible_head = if self.collapsible {
             let default_value = if self.default_hidden {
-                " collapsed"
+                format!(" {class_prefix}collapsed")
+            } else {
+                String::new()
+            };
+
+            // `RenderOption.csp`: the handler is registered by `csp::collapsible_table_listener`
+            let handler = if render_option.csp {
+                format!(" data-mdxt-collapse=\"{}\"", self.index)
             } else {
-                ""
+                format!(" onclick =\"collapse_table('{}')\"", self.index)
             };
 
-            format!(" id=\"table-collapse-toggle-{}\" class=\"{class_prefix}collapsible{default_value}\" onclick =\"collapse_table('{}')\"", self.index, self.index)
+            format!(" id=\"table-collapse-toggle-{}\" class=\"{class_prefix}collapsible{default_value}\"{handler}", self.index)
         } else {
             String::new()
         };
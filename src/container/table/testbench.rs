@@ -40,7 +40,7 @@ fn table_samples() -> Vec<(String, String)> {
     <tbody>
         <tr>
             <td> math inside a table </td>
-            <td> <math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mo>|</mo><mi>a</mi><mo>|</mo></math> </td>
+            <td> <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"|a|\"><mo>|</mo><mi>a</mi><mo>|</mo></math> </td>
             <td> the pipe shouldn&apos;t break a cell </td>
         </tr>
         <tr>
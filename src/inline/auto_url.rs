@@ -1,112 +1,237 @@
-use crate::escape::HTML_ESCAPE_OFFSET;
+use crate::escape::{BACKSLASH_ESCAPE_OFFSET, HTML_ESCAPE_OFFSET};
 use crate::inline::{INLINE_CODE_SPAN_MARKER1, INLINE_CODE_SPAN_MARKER4};
-use crate::utils::is_alpha_numeric;
+use crate::obfuscate::{is_email_local_char, is_valid_email};
+use crate::utils::{into_v32, is_alpha_numeric, lowercase};
 
-#[derive(Debug)]
-enum ParseState {
-    ReadParagraph,
-    ReadURL,
-    SkipUntil(u32),
-}
+/*
+Autolinks, like GFM's autolink extension
+
+An autolink starts at the beginning of a line, or after a whitespace, `(`, `*`, `_` or `~`. It ends before a whitespace or a `<`.
+
+- `https://github.com`: a scheme in `RenderOption.autolink_schemes`. With `//`, a valid domain has to follow.
+- `mailto:user@example.com`: `mailto` is also a scheme, but the address is rendered by `obfuscate`.
+- `www.github.com`: `http://` is added to the destination.
+- `user@example.com`: rendered by `obfuscate`.
+- `baehyunsol.github.io`: a valid domain that `contains_url_substr`. It's not in GFM. `http://` is added to the destination.
+
+A valid domain is segments of alphanumeric characters, `_` and `-`, separated by `.`. It has at least one `.`, and its last two segments don't have `_`.
+The trailing punctuations are not a part of an autolink. See `has_to_pop_last_char`.
+*/
 
 pub enum UrlOrNot {
     NoUrl(Vec<u32>),
 
     // (prefix, url, suffix)
     HasUrl(Vec<u32>, Vec<u32>, Vec<u32>),
+
+    // (prefix, email, suffix)
+    // `email` may start with `mailto:`
+    HasEmail(Vec<u32>, Vec<u32>, Vec<u32>),
 }
 
-pub fn render_auto_urls(content: &[u32]) -> UrlOrNot {
-    let mut last_char = '\n' as u32;
-    let mut curr_state = ParseState::ReadParagraph;
-    let mut url_buffer = vec![];
-    let mut para_buffer = vec![];
-
-    // it adds a dummy character in order to empty `url_buffer` when the iteration finishes
-    for (ind, c) in content.iter().chain([32].iter()).enumerate() {
-        match &curr_state {
-            ParseState::ReadParagraph => {
-                if is_alpha_numeric(c) && (
-                    last_char == '\n' as u32
-                    || last_char == ' ' as u32
-                    || last_char == '(' as u32
-                ) {
-                    curr_state = ParseState::ReadURL;
-                    url_buffer.push(*c);
-                } else if *c == INLINE_CODE_SPAN_MARKER1 {
-                    curr_state = ParseState::SkipUntil(INLINE_CODE_SPAN_MARKER4);
-                    para_buffer.push(*c);
-                } else {
-                    last_char = *c;
-                    para_buffer.push(*c);
-                }
-            },
-            ParseState::ReadURL => {
-                if is_allowed_char(c) {
-                    url_buffer.push(*c);
-                } else if *c == ' ' as u32 || *c == '<' as u32 + HTML_ESCAPE_OFFSET
-                || (44032 <= *c && *c < 55024) {
-                    if contains_url_substr(&url_buffer) {  // is a valid url
-                        if has_to_pop_last_char(&url_buffer, last_char == '(' as u32) {
-                            return UrlOrNot::HasUrl(
-                                para_buffer,
-                                url_buffer[..(url_buffer.len() - 1)].to_vec(),
-                                if ind - 1 < content.len() {
-                                    content[(ind - 1)..].to_vec()
-                                } else {
-                                    vec![]
-                                },
-                            );
-                        } else {
-                            return UrlOrNot::HasUrl(
-                                para_buffer,
-                                url_buffer,
-                                if ind < content.len() {
-                                    content[ind..].to_vec()
-                                } else {
-                                    vec![]
-                                },
-                            );
-                        }
-                    } else {
-                        para_buffer = vec![
-                            para_buffer,
-                            url_buffer,
-                        ].concat();
-
-                        url_buffer = vec![];
-                        para_buffer.push(*c);
-
-                        curr_state = ParseState::ReadParagraph;
-                    }
-                } else {
-                    para_buffer = vec![
-                        para_buffer,
-                        url_buffer,
-                    ].concat();
+pub fn render_auto_urls(content: &[u32], schemes: &[String]) -> UrlOrNot {
+    let mut index = 0;
 
-                    url_buffer = vec![];
-                    para_buffer.push(*c);
+    while index < content.len() {
 
-                    curr_state = ParseState::ReadParagraph;
-                }
-            },
-            ParseState::SkipUntil(s) => {
-                para_buffer.push(*c);
+        // urls inside code spans are not rendered
+        if content[index] == INLINE_CODE_SPAN_MARKER1 {
+            index += content[index..].iter().position(|c| *c == INLINE_CODE_SPAN_MARKER4).unwrap_or(content.len() - index);
+            continue;
+        }
 
-                if c == s {
-                    curr_state = ParseState::ReadParagraph;
-                }
-            },
+        if is_alpha_numeric(&content[index]) && (index == 0 || is_autolink_boundary(content[index - 1])) {
+            let mut end_index = index;
+
+            while end_index < content.len() && !is_autolink_end(content[end_index]) {
+                end_index += 1;
+            }
+
+            if let Some(result) = read_autolink(content, index, end_index, schemes) {
+                return result;
+            }
+
+            index = end_index;
+            continue;
         }
+
+        index += 1;
+    }
+
+    UrlOrNot::NoUrl(content.to_vec())
+}
+
+// `content[start_index..end_index]` doesn't have whitespaces
+fn read_autolink(content: &[u32], start_index: usize, end_index: usize, schemes: &[String]) -> Option<UrlOrNot> {
+    let candidate = &content[start_index..end_index];
+    let split = |url_end: usize| (content[..start_index].to_vec(), content[start_index..(start_index + url_end)].to_vec(), content[(start_index + url_end)..].to_vec());
+
+    if let Some(scheme) = get_scheme(candidate, schemes) {
+        let rest = &candidate[(scheme.len() + 1)..];
+
+        if scheme == [109, 97, 105, 108, 116, 111] {  // into_v32("mailto")
+            let email_length = read_email(rest)?;
+            let (prefix, email, suffix) = split(scheme.len() + 1 + email_length);
+
+            return Some(UrlOrNot::HasEmail(prefix, email, suffix));
+        }
+
+        if rest.starts_with(&[47, 47]) {  // into_v32("//")
+
+            if !is_valid_domain(&rest[2..]) {
+                return None;
+            }
+
+        }
+
+        else if rest.is_empty() {
+            return None;
+        }
+
+        let url_length = trim_trailing_punctuations(candidate);
+
+        if url_length <= scheme.len() + 1 {
+            return None;
+        }
+
+        let (prefix, url, suffix) = split(url_length);
+
+        return Some(UrlOrNot::HasUrl(prefix, url, suffix));
+    }
+
+    if let Some(email_length) = read_email(candidate) {
+        let (prefix, email, suffix) = split(email_length);
+
+        return Some(UrlOrNot::HasEmail(prefix, email, suffix));
+    }
+
+    if !is_valid_domain(candidate) {
+        return None;
+    }
+
+    let url_length = trim_trailing_punctuations(candidate);
+
+    if candidate.starts_with(&[119, 119, 119, 46]) || contains_url_substr(&candidate[..url_length]) {  // into_v32("www.")
+        let (prefix, url, suffix) = split(url_length);
+
+        Some(UrlOrNot::HasUrl(prefix, url, suffix))
+    }
+
+    else {
+        None
+    }
+
+}
+
+// `https://github.com` -> `https://github.com`
+// `www.github.com` -> `http://www.github.com`
+pub fn get_url_destination(url: &[u32], schemes: &[String]) -> Vec<u32> {
+
+    if get_scheme(url, schemes).is_some() {
+        url.to_vec()
+    }
+
+    else {
+        [
+            &[104, 116, 116, 112, 58, 47, 47],  // into_v32("http://")
+            url,
+        ].concat()
     }
 
-    // we added a dummy character for simpler code
-    para_buffer.pop().unwrap();
+}
+
+// lowercase scheme, without `:`
+fn get_scheme(url: &[u32], schemes: &[String]) -> Option<Vec<u32>> {
+    let colon_index = url.iter().position(|c| *c == ':' as u32)?;
+    let scheme = url[..colon_index].iter().map(lowercase).collect::<Vec<u32>>();
+
+    if schemes.iter().any(|s| into_v32(&s.to_lowercase()) == scheme) {
+        Some(scheme)
+    }
+
+    else {
+        None
+    }
+
+}
+
+// the length of the email at the start of `content`
+// `user@example.com.` -> `user@example.com`
+fn read_email(content: &[u32]) -> Option<usize> {
+    let at_index = content.iter().position(|c| *c == '@' as u32)?;
+
+    if !content[..at_index].iter().all(is_email_local_char) {
+        return None;
+    }
+
+    let mut email_length = at_index + 1;
+
+    while email_length < content.len() && (is_alpha_numeric(&content[email_length]) || content[email_length] == '-' as u32 || content[email_length] == '.' as u32) {
+        email_length += 1;
+    }
+
+    while content[email_length - 1] == '.' as u32 {
+        email_length -= 1;
+    }
+
+    if is_valid_email(&content[..email_length]) {
+        Some(email_length)
+    }
+
+    else {
+        None
+    }
 
-    UrlOrNot::NoUrl(para_buffer)
 }
 
+// it only reads the domain at the start of `content`: `www.github.com/baehyunsol` -> `www.github.com`
+// the trailing `.`s are punctuations, not a part of the domain
+fn is_valid_domain(content: &[u32]) -> bool {
+    let mut domain_length = content.iter().position(
+        |c| !is_alpha_numeric(c) && *c != '-' as u32 && *c != '_' as u32 && *c != '.' as u32
+    ).unwrap_or(content.len());
+
+    while domain_length > 0 && content[domain_length - 1] == '.' as u32 {
+        domain_length -= 1;
+    }
+
+    let segments = content[..domain_length].split(|c| *c == '.' as u32).collect::<Vec<&[u32]>>();
+
+    segments.len() > 1
+    && segments.iter().all(|segment| !segment.is_empty())
+    && segments[(segments.len() - 2)..].iter().all(|segment| !segment.contains(&('_' as u32)))
+}
+
+// the length of `url` without the trailing punctuations
+fn trim_trailing_punctuations(url: &[u32]) -> usize {
+    let mut url_length = url.len();
+
+    while url_length > 0 {
+
+        if has_to_pop_last_char(&url[..url_length]) {
+            url_length -= 1;
+        }
+
+        // `www.github.com&amp;` -> `www.github.com`
+        else if url[url_length - 1] == ';' as u32 {
+
+            match url[..(url_length - 1)].iter().rposition(|c| !is_alpha_numeric(c)) {
+                Some(ampersand_index) if url[ampersand_index] == '&' as u32 + HTML_ESCAPE_OFFSET && ampersand_index + 2 < url_length => {
+                    url_length = ampersand_index;
+                },
+                _ => { break; }
+            }
+
+        }
+
+        else {
+            break;
+        }
+
+    }
+
+    url_length
+}
 enum UrlSubStrState {
     Init,
     ReadW(usize),
@@ -193,24 +318,33 @@ fn contains_url_substr(url: &[u32]) -> bool {
     false
 }
 
-fn is_allowed_char(c: &u32) -> bool {
-
-    is_alpha_numeric(c)
+// `?`, `!`, `.`, `,`, `:`, `*`, `_`, `~`, `'` and `"`
+// `)` when the url has more `)`s than `(`s: `(see www.github.com/a_(b))` -> `www.github.com/a_(b)`
+fn has_to_pop_last_char(url: &[u32]) -> bool {
+    let last_char = url.last().unwrap();
 
-    // -/#+&=()?!_.,;:*~
-    || [45, 47, 35, 43, 38, 61, 40, 41, 63, 33, 95, 46, 44, 59, 58, 42, 126].contains(c)
+    [63, 33, 95, 46, 44, 58, 42, 126].contains(last_char)
+    || *last_char == '\'' as u32 + HTML_ESCAPE_OFFSET || *last_char == '"' as u32 + HTML_ESCAPE_OFFSET
+    || *last_char == ')' as u32 && url.iter().filter(|c| **c == ')' as u32).count() > url.iter().filter(|c| **c == '(' as u32).count()
 }
 
-fn has_to_pop_last_char(url: &[u32], inside_paren: bool) -> bool {
-    let last_char = url.last().unwrap();
+fn is_autolink_boundary(c: u32) -> bool {
+    c == ' ' as u32 || c == '\n' as u32 || c == '\t' as u32
+    || c == '(' as u32 || c == '*' as u32 || c == '_' as u32 || c == '~' as u32
+}
 
-    [63, 33, 95, 46, 44, 58, 42, 126].contains(last_char)
-    || *last_char == ')' as u32 && inside_paren
+// backslash escapes and meta characters end autolinks
+fn is_autolink_end(c: u32) -> bool {
+    c == ' ' as u32 || c == '\n' as u32 || c == '\t' as u32
+    || c == '<' as u32 + HTML_ESCAPE_OFFSET
+    || (44032 <= c && c < 55204)  // korean
+    || c >= BACKSLASH_ESCAPE_OFFSET
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::render::render_option::RenderOption;
     use crate::utils::{into_v32, from_v32};
 
     #[test]
@@ -230,11 +364,16 @@ mod tests {
 
     #[test]
     fn auto_url_test() {
+        let schemes = RenderOption::default().autolink_schemes;
         let samples = vec![
             ("a ", "www.github.com", " b"),
             ("(", "www.github.com", ")"),
             ("(", "www.github.com/(abc)/def", ")"),
-            ("", "https://github.com)", "  !"),
+            ("", "https://github.com", ")  !"),
+            ("(", "https://en.wikipedia.org/wiki/Rust_(programming_language)", ")"),
+            ("", "www.github.com/a?b=c", "?!"),
+            ("*", "ftp://example.com/file", "*"),
+            ("Call ", "tel:+82-10-1234-5678", ""),
             ("The url is ", "www.github.com", "."),
             ("The url is ", "baehyunsol.github.io", ", try it!"),
             ("[ ", "https://github.com", " ]"),
@@ -245,7 +384,7 @@ mod tests {
         ).collect();
 
         for (prefix, url, suffix) in samples.into_iter() {
-            match render_auto_urls(&vec![prefix.clone(), url.clone(), suffix.clone()].concat()) {
+            match render_auto_urls(&[prefix.clone(), url.clone(), suffix.clone()].concat(), &schemes) {
                 UrlOrNot::HasUrl(p, u, s) if p != prefix || u != url || s != suffix => panic!(
                     "expected: ({:?}, {:?}, {:?})\ngot: ({:?}, {:?}, {:?})",
                     from_v32(&prefix),
@@ -266,10 +405,31 @@ mod tests {
             }
         }
 
+        let emails = vec![
+            ("Mail ", "user@example.com", "."),
+            ("(", "first.last+tag@mail.example.co.kr", ")"),
+            ("", "a-b_c@x-y.io", ""),
+            ("", "mailto:user@example.com", ""),
+        ];
+
+        for (prefix, email, suffix) in emails.into_iter() {
+            match render_auto_urls(&into_v32(&format!("{prefix}{email}{suffix}")), &schemes) {
+                UrlOrNot::HasEmail(p, e, s) if p == into_v32(prefix) && e == into_v32(email) && s == into_v32(suffix) => { /* Ok */ },
+                _ => panic!("expected: ({prefix:?}, {email:?}, {suffix:?})"),
+            }
+        }
+
         let no_urls = vec![
+            "a@b",
+            "@example.com",
+            "a.@example.com",
+            "a@-example.com",
             "a.b.c",
             "baehyunsol.",
             "[https://github.com]",
+            "http://localhost",
+            "www.a_b.com",
+            "gopher://example.com",
         ];
 
         let no_urls: Vec<Vec<u32>> = no_urls.iter().map(
@@ -277,14 +437,14 @@ mod tests {
         ).collect();
 
         for content in no_urls.into_iter() {
-            match render_auto_urls(&content) {
+            match render_auto_urls(&content, &schemes) {
                 UrlOrNot::NoUrl(c) if c == content => { /* Ok */ },
                 UrlOrNot::NoUrl(c) => panic!(
                     "original: {:?}\nprocessed: {:?}",
                     from_v32(&content),
                     from_v32(&c),
                 ),
-                UrlOrNot::HasUrl(p, u, s) => panic!(
+                UrlOrNot::HasUrl(p, u, s) | UrlOrNot::HasEmail(p, u, s) => panic!(
                     "original: {:?}\n({:?}, {:?}, {:?})",
                     from_v32(&content),
                     from_v32(&p),
@@ -294,4 +454,20 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn url_destination_test() {
+        let schemes = RenderOption::default().autolink_schemes;
+        let samples = vec![
+            ("www.github.com", "http://www.github.com"),
+            ("https://github.com", "https://github.com"),
+            ("FTP://example.com", "FTP://example.com"),
+            ("tel:+82-10-1234-5678", "tel:+82-10-1234-5678"),
+        ];
+
+        for (url, destination) in samples.into_iter() {
+            assert_eq!(from_v32(&get_url_destination(&into_v32(url), &schemes)), destination);
+        }
+    }
+
 }
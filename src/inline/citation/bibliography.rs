@@ -0,0 +1,466 @@
+use std::collections::HashMap;
+use yaml_rust::{Yaml, YamlLoader};
+
+/// A set of references that `[@key]` citations refer to.
+/// It's read from BibTeX or CSL-JSON.
+#[derive(Clone, Default)]
+pub struct Bibliography {
+    entries: HashMap<String, BibEntry>
+}
+
+#[derive(Clone, Default)]
+pub struct BibEntry {
+    pub key: String,
+    pub authors: Vec<Author>,
+    pub title: String,
+    pub year: String,
+
+    // journal, booktitle, or `container-title` of CSL
+    pub container: String,
+    pub volume: String,
+    pub pages: String,
+    pub publisher: String,
+    pub url: String,
+    pub doi: String
+}
+
+#[derive(Clone, Default)]
+pub struct Author {
+    pub family: String,
+    pub given: String
+}
+
+impl Bibliography {
+
+    /// `@article{key, author = {...}, title = {...}, ...}`
+    /// `@string`, `@preamble` and `@comment` are ignored, and so are the texts outside the entries, like `% contact me@example.com`.
+    pub fn from_bibtex(bibtex: &str) -> Result<Self, String> {
+        let chars = bibtex.chars().collect::<Vec<char>>();
+        let mut entries = HashMap::new();
+        let mut index = 0;
+
+        while index < chars.len() {
+
+            if chars[index] != '@' {
+                index += 1;
+                continue;
+            }
+
+            index += 1;
+            let entry_type = read_while(&chars, &mut index, |c| c.is_alphanumeric()).to_lowercase();
+            skip_whitespaces(&chars, &mut index);
+
+            let closing = match chars.get(index) {
+                Some('{') => '}',
+                Some('(') => ')',
+
+                // it's a comment
+                _ => { continue; }
+            };
+
+            if entry_type == "comment" || entry_type == "string" || entry_type == "preamble" {
+                index = match get_bibtex_block_end(&chars, index) {
+                    Some(end_index) => end_index + 1,
+                    None => { return Err(format!("`@{entry_type}` is not closed")); }
+                };
+
+                continue;
+            }
+
+            index += 1;
+            skip_whitespaces(&chars, &mut index);
+            let key = read_while(&chars, &mut index, |c| c != ',' && c != closing && !c.is_whitespace());
+            let mut fields = HashMap::new();
+
+            loop {
+                skip_whitespaces(&chars, &mut index);
+
+                match chars.get(index) {
+                    None => { return Err(format!("`@{entry_type}{{{key}` is not closed")); },
+                    Some(c) if *c == closing => {
+                        index += 1;
+                        break;
+                    },
+                    Some(',') => {
+                        index += 1;
+                        continue;
+                    },
+                    _ => {}
+                }
+
+                let field_name = read_while(&chars, &mut index, |c| c != '=' && c != ',' && c != closing).trim().to_lowercase();
+
+                if chars.get(index) != Some(&'=') {
+                    continue;
+                }
+
+                index += 1;
+                let value = read_bibtex_value(&chars, &mut index, closing)?;
+                fields.insert(field_name, value);
+            }
+
+            if !key.is_empty() {
+                entries.insert(key.clone(), BibEntry::from_bibtex_fields(key, &fields));
+            }
+
+        }
+
+        Ok(Bibliography { entries })
+    }
+
+    /// An array of CSL items, like `[{"id": "key", "title": "...", "author": [{"family": "...", "given": "..."}]}]`
+    pub fn from_csl_json(json: &str) -> Result<Self, String> {
+
+        // json is a subset of yaml
+        match YamlLoader::load_from_str(json) {
+            Ok(data) if !data.is_empty() => Bibliography::from_csl_yaml(&data[0]),
+            Ok(_) => Ok(Bibliography::default()),
+            Err(e) => Err(format!("{e}"))
+        }
+
+    }
+
+    // `references` field of the metadata has the same structure as CSL-JSON
+    pub fn from_csl_yaml(data: &Yaml) -> Result<Self, String> {
+
+        match data {
+            Yaml::Array(items) => {
+                let mut entries = HashMap::new();
+
+                for item in items.iter() {
+
+                    match BibEntry::from_csl_item(item) {
+                        Some(entry) => { entries.insert(entry.key.clone(), entry); },
+                        None => { return Err(String::from("a CSL item without an `id`")); }
+                    }
+
+                }
+
+                Ok(Bibliography { entries })
+            },
+            _ => Err(String::from("CSL-JSON must be an array of items"))
+        }
+
+    }
+
+    /// The entries of `other` overwrite the ones with the same key.
+    pub fn merge(&mut self, other: Bibliography) -> &mut Self {
+        self.entries.extend(other.entries);
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&BibEntry> {
+        self.entries.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+}
+
+impl BibEntry {
+
+    fn from_bibtex_fields(key: String, fields: &HashMap<String, String>) -> Self {
+        let get = |name: &str| match fields.get(name) {
+            Some(value) => clean_bibtex_value(value),
+            None => String::new()
+        };
+
+        let authors = match fields.get("author").or_else(|| fields.get("editor")) {
+            Some(authors) => split_bibtex_authors(authors).iter().map(
+                |author| Author::from_bibtex_name(author)
+            ).collect(),
+            None => vec![]
+        };
+
+        let mut year = get("year");
+
+        // biblatex uses `date = {2020-01-31}`
+        if year.is_empty() {
+            year = get("date").chars().take(4).collect();
+        }
+
+        let mut container = get("journal");
+
+        for name in ["journaltitle", "booktitle", "series"] {
+
+            if container.is_empty() {
+                container = get(name);
+            }
+
+        }
+
+        let mut publisher = get("publisher");
+
+        for name in ["school", "institution", "organization"] {
+
+            if publisher.is_empty() {
+                publisher = get(name);
+            }
+
+        }
+
+        BibEntry {
+            key,
+            authors,
+            title: get("title"),
+            year,
+            container,
+            volume: get("volume"),
+            pages: get("pages").replace("--", "–"),
+            publisher,
+            url: get("url"),
+            doi: get("doi")
+        }
+    }
+
+    fn from_csl_item(item: &Yaml) -> Option<Self> {
+        let get = |name: &str| yaml_to_string(&item[name]);
+
+        let key = get("id");
+
+        if key.is_empty() {
+            return None;
+        }
+
+        let authors = match (&item["author"], &item["editor"]) {
+            (Yaml::Array(authors), _) | (_, Yaml::Array(authors)) => authors.iter().map(
+                |author| match yaml_to_string(&author["literal"]) {
+                    literal if !literal.is_empty() => Author { family: literal, given: String::new() },
+                    _ => Author {
+                        family: yaml_to_string(&author["family"]),
+                        given: yaml_to_string(&author["given"])
+                    }
+                }
+            ).collect(),
+            _ => vec![]
+        };
+
+        let issued = &item["issued"];
+        let mut year = yaml_to_string(&issued["date-parts"][0][0]);
+
+        for name in ["literal", "raw"] {
+
+            if year.is_empty() {
+                year = yaml_to_string(&issued[name]).chars().take(4).collect();
+            }
+
+        }
+
+        Some(BibEntry {
+            key,
+            authors,
+            title: get("title"),
+            year,
+            container: get("container-title"),
+            volume: get("volume"),
+            pages: get("page").replace('-', "–"),
+            publisher: get("publisher"),
+            url: get("URL"),
+            doi: get("DOI")
+        })
+    }
+
+}
+
+impl Author {
+
+    // `Knuth, Donald E.` or `Donald E. Knuth`
+    fn from_bibtex_name(name: &str) -> Self {
+
+        match name.split_once(',') {
+            Some((family, given)) => Author {
+                family: clean_bibtex_value(family),
+                given: clean_bibtex_value(given)
+            },
+            None => {
+                let name = name.trim();
+
+                // `{World Health Organization}`
+                if name.starts_with('{') && name.ends_with('}') {
+                    return Author { family: clean_bibtex_value(name), given: String::new() };
+                }
+
+                match name.rsplit_once(' ') {
+                    Some((given, family)) => Author {
+                        family: clean_bibtex_value(family),
+                        given: clean_bibtex_value(given)
+                    },
+                    None => Author {
+                        family: clean_bibtex_value(name),
+                        given: String::new()
+                    }
+                }
+
+            }
+        }
+
+    }
+
+}
+
+fn yaml_to_string(yaml: &Yaml) -> String {
+
+    match yaml {
+        Yaml::String(s) | Yaml::Real(s) => s.clone(),
+        Yaml::Integer(n) => n.to_string(),
+        _ => String::new()
+    }
+
+}
+
+fn read_while<F: Fn(char) -> bool>(chars: &[char], index: &mut usize, f: F) -> String {
+    let mut result = String::new();
+
+    while *index < chars.len() && f(chars[*index]) {
+        result.push(chars[*index]);
+        *index += 1;
+    }
+
+    result
+}
+
+fn skip_whitespaces(chars: &[char], index: &mut usize) {
+
+    while *index < chars.len() && chars[*index].is_whitespace() {
+        *index += 1;
+    }
+
+}
+
+// `chars[index]` is `{` or `(`
+fn get_bibtex_block_end(chars: &[char], index: usize) -> Option<usize> {
+    let (opening, closing) = if chars[index] == '{' { ('{', '}') } else { ('(', ')') };
+    let mut depth = 0;
+
+    for (i, c) in chars.iter().enumerate().skip(index) {
+
+        if *c == opening {
+            depth += 1;
+        }
+
+        else if *c == closing {
+            depth -= 1;
+
+            if depth == 0 {
+                return Some(i);
+            }
+
+        }
+
+    }
+
+    None
+}
+
+// `{...}`, `"..."` or a bare word, which may be concatenated with `#`
+// the braces inside the value are kept, so that the author names can be split properly
+fn read_bibtex_value(chars: &[char], index: &mut usize, closing: char) -> Result<String, String> {
+    let mut result = String::new();
+
+    loop {
+        skip_whitespaces(chars, index);
+
+        match chars.get(*index) {
+            Some('{') => match get_bibtex_block_end(chars, *index) {
+                Some(end_index) => {
+                    result.push_str(&chars[(*index + 1)..end_index].iter().collect::<String>());
+                    *index = end_index + 1;
+                },
+                None => { return Err(String::from("unclosed `{` in a field")); }
+            },
+            Some('"') => {
+                let mut depth = 0;
+                *index += 1;
+
+                while *index < chars.len() && (chars[*index] != '"' || depth > 0) {
+
+                    if chars[*index] == '{' {
+                        depth += 1;
+                    }
+
+                    else if chars[*index] == '}' {
+                        depth -= 1;
+                    }
+
+                    result.push(chars[*index]);
+                    *index += 1;
+                }
+
+                if *index == chars.len() {
+                    return Err(String::from("unclosed `\"` in a field"));
+                }
+
+                *index += 1;
+            },
+            Some(_) => {
+                result.push_str(read_while(chars, index, |c| c != ',' && c != '#' && c != closing).trim());
+            },
+            None => { return Err(String::from("a field is not closed")); }
+        }
+
+        skip_whitespaces(chars, index);
+
+        if chars.get(*index) == Some(&'#') {
+            *index += 1;
+        }
+
+        else {
+            break;
+        }
+
+    }
+
+    Ok(result)
+}
+
+// `A and B and {C and D}` -> [`A`, `B`, `{C and D}`]
+fn split_bibtex_authors(authors: &str) -> Vec<String> {
+    let chars = authors.chars().collect::<Vec<char>>();
+    let mut result = vec![];
+    let mut curr = String::new();
+    let mut depth = 0;
+    let mut index = 0;
+
+    while index < chars.len() {
+
+        if chars[index] == '{' {
+            depth += 1;
+        }
+
+        else if chars[index] == '}' {
+            depth -= 1;
+        }
+
+        else if depth == 0 && chars[index..].iter().take(5).collect::<String>() == " and " {
+            result.push(curr.trim().to_string());
+            curr = String::new();
+            index += 5;
+            continue;
+        }
+
+        curr.push(chars[index]);
+        index += 1;
+    }
+
+    result.push(curr.trim().to_string());
+
+    result.into_iter().filter(|author| !author.is_empty()).collect()
+}
+
+// removes braces, simple escapes and redundant whitespaces
+fn clean_bibtex_value(value: &str) -> String {
+    value.replace(['{', '}'], "")
+        .replace("\\&", "&")
+        .replace("\\%", "%")
+        .replace("\\_", "_")
+        .replace('~', " ")
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
@@ -0,0 +1,295 @@
+pub mod bibliography;
+pub mod predicate;
+
+#[cfg(test)]
+mod testbench;
+
+use bibliography::{Bibliography, BibEntry};
+use crate::ast::doc_data::DocData;
+use crate::escape::{escape_htmls, render_backslash_escapes};
+use crate::render::render_option::{CitationStyle, RenderOption};
+use crate::utils::into_v32;
+use yaml_rust::Yaml;
+
+// an item of `[@key1, p. 12; @key2]`
+#[derive(Clone)]
+pub struct CitationItem {
+    #[cfg(test)]  // it's used for roundtrip tests
+    pub key: String,
+    pub index: usize,  // index of the reference
+
+    // `1` or `Knuth 1984`, depending on the style
+    pub label: Vec<u32>,
+    pub locator: Vec<u32>
+}
+
+// a reference that's cited at least once
+#[derive(Clone)]
+pub struct CitedReference {
+    pub index: usize,
+    pub inverse_index: Vec<usize>
+}
+
+impl CitationItem {
+
+    pub fn new(key: String, locator: Vec<u32>, doc_data: &DocData, render_option: &RenderOption) -> Self {
+        let index = doc_data.citations[&key].index;
+        let label = citation_label(render_option.bibliography.get(&key).unwrap(), index, render_option.citation_style);
+
+        CitationItem {
+            #[cfg(test)] key,
+            index,
+            label,
+            locator
+        }
+    }
+
+}
+
+pub fn citation_to_html(inverse_index: usize, items: &[CitationItem], style: CitationStyle, class_prefix: &str) -> Vec<u32> {
+    let (opening, closing) = match style {
+        CitationStyle::Numeric => ("[", "]"),
+        CitationStyle::AuthorYear => ("(", ")")
+    };
+
+    let items = items.iter().map(
+        |CitationItem { index, label, locator, .. }| {
+            let mut result = vec![
+                into_v32(&format!("<a href=\"#reference-{index}\">")),
+                label.clone(),
+                vec![60, 47, 97, 62]  // into_v32("</a>")
+            ];
+
+            if !locator.is_empty() {
+                result.push(vec![44, 32]);  // into_v32(", ")
+                result.push(render_backslash_escapes(locator));
+            }
+
+            result.concat()
+        }
+    ).collect::<Vec<Vec<u32>>>();
+
+    vec![
+        into_v32(&format!("<span class=\"{class_prefix}citation\" id=\"citation-ref-{inverse_index}\">{opening}")),
+        items.join(&[59, 32][..]),  // into_v32("; ")
+        into_v32(&format!("{closing}</span>"))
+    ].concat()
+}
+
+#[cfg(test)]
+pub fn citation_to_mdxt(items: &[CitationItem]) -> Vec<u32> {
+    let items = items.iter().map(
+        |CitationItem { key, locator, .. }| if locator.is_empty() {
+            into_v32(&format!("@{key}"))
+        } else {
+            vec![into_v32(&format!("@{key}, ")), locator.clone()].concat()
+        }
+    ).collect::<Vec<Vec<u32>>>();
+
+    vec![
+        vec![91],  // into_v32("[")
+        items.join(&[59, 32][..]),  // into_v32("; ")
+        vec![93]  // into_v32("]")
+    ].concat()
+}
+
+// `[1] Knuth, Donald E. (1984). Literate Programming. The Computer Journal, 27, 97–111.`
+pub fn references_to_html(render_option: &RenderOption, doc_data: &DocData) -> Vec<u32> {
+    let class_prefix = &render_option.class_prefix;
+    let style = render_option.citation_style;
+
+    let mut references = doc_data.citations.iter().map(
+        |(key, reference)| (render_option.bibliography.get(key).unwrap(), reference)
+    ).collect::<Vec<(&BibEntry, &CitedReference)>>();
+
+    match style {
+        CitationStyle::Numeric => {
+            references.sort_unstable_by_key(|(_, reference)| reference.index);
+        },
+        CitationStyle::AuthorYear => {
+            references.sort_unstable_by_key(
+                |(entry, _)| (sort_name(entry), entry.year.clone(), entry.key.clone())
+            );
+        }
+    }
+
+    let mut result = Vec::with_capacity(references.len() + 2);
+
+    result.push(into_v32(&format!("<div class=\"{class_prefix}mdxt-references\">")));
+
+    for (entry, CitedReference { index, inverse_index }) in references.into_iter() {
+        result.push(into_v32(&format!("<div class=\"{class_prefix}reference\" id=\"reference-{index}\">")));
+
+        if let CitationStyle::Numeric = style {
+            result.push(into_v32(&format!("<span class=\"{class_prefix}reference-label\">[{}]</span> ", index + 1)));
+        }
+
+        for ind in inverse_index.iter() {
+            result.push(into_v32(&format!("<a href=\"#citation-ref-{ind}\">^</a> ")));
+        }
+
+        result.push(entry_to_html(entry, render_option));
+        result.push(vec![60, 47, 100, 105, 118, 62]);  // into_v32("</div>")
+    }
+
+    result.push(vec![60, 47, 100, 105, 118, 62]);  // into_v32("</div>")
+    result.concat()
+}
+
+/// `bibliography` (paths to `.bib` or `.json` files), `references` (CSL items) and `citation-style` of the metadata.
+/// Files that cannot be read are ignored, and `bibliography` is ignored unless `RenderOption.bibliography_files` is set.
+pub fn load_bibliography_from_metadata(metadata: &Yaml, render_option: &mut RenderOption) {
+    let paths = match &metadata["bibliography"] {
+        _ if !render_option.bibliography_files => vec![],
+        Yaml::String(path) => vec![path.clone()],
+        Yaml::Array(paths) => paths.iter().filter_map(|path| path.as_str().map(|path| path.to_string())).collect(),
+        _ => vec![]
+    };
+
+    for path in paths.iter() {
+
+        if let Ok(content) = std::fs::read_to_string(path) {
+            let bibliography = if path.to_lowercase().ends_with(".json") {
+                Bibliography::from_csl_json(&content)
+            } else {
+                Bibliography::from_bibtex(&content)
+            };
+
+            if let Ok(bibliography) = bibliography {
+                render_option.bibliography.merge(bibliography);
+            }
+
+        }
+
+    }
+
+    if let Ok(bibliography) = Bibliography::from_csl_yaml(&metadata["references"]) {
+        render_option.bibliography.merge(bibliography);
+    }
+
+    match metadata["citation-style"].as_str() {
+        Some("numeric") => { render_option.set_citation_style(CitationStyle::Numeric); },
+        Some("author-year") => { render_option.set_citation_style(CitationStyle::AuthorYear); },
+        _ => {}
+    }
+
+}
+
+// `Knuth 1984`, `Knuth and Lamport 1984`, `Knuth et al. 1984`
+fn citation_label(entry: &BibEntry, index: usize, style: CitationStyle) -> Vec<u32> {
+
+    match style {
+        CitationStyle::Numeric => into_v32(&(index + 1).to_string()),
+        CitationStyle::AuthorYear => {
+            let authors = match &entry.authors[..] {
+                [] if entry.title.is_empty() => entry.key.clone(),
+                [] => entry.title.clone(),
+                [author] => author.family.clone(),
+                [author1, author2] => format!("{} and {}", author1.family, author2.family),
+                [author, ..] => format!("{} et al.", author.family)
+            };
+            let year = if entry.year.is_empty() { "n.d." } else { &entry.year };
+
+            escape_htmls(&into_v32(&format!("{authors} {year}")))
+        }
+    }
+
+}
+
+fn entry_to_html(entry: &BibEntry, render_option: &RenderOption) -> Vec<u32> {
+    let escape = |s: &str| escape_htmls(&into_v32(s));
+    let mut parts = vec![];
+
+    if !entry.authors.is_empty() {
+        let authors = entry.authors.iter().map(
+            |author| if author.given.is_empty() {
+                author.family.clone()
+            } else {
+                format!("{}, {}", author.family, author.given)
+            }
+        ).collect::<Vec<String>>();
+
+        let authors = match &authors[..] {
+            [author] => author.clone(),
+            [init @ .., last] => {
+                // `Knuth, Donald; Lamport, Leslie and ...`
+                let delim = if entry.authors.iter().any(|author| !author.given.is_empty()) { "; " } else { ", " };
+
+                format!("{} and {last}", init.join(delim))
+            },
+            [] => unreachable!()
+        };
+
+        parts.push(escape(&authors));
+    }
+
+    parts.push(escape(&format!("({})", if entry.year.is_empty() { "n.d." } else { &entry.year })));
+
+    if !entry.title.is_empty() {
+        parts.push(escape(&entry.title));
+    }
+
+    if !entry.container.is_empty() {
+        let mut container = vec![
+            vec![60, 101, 109, 62],  // into_v32("<em>")
+            escape(&entry.container),
+            vec![60, 47, 101, 109, 62]  // into_v32("</em>")
+        ];
+
+        for detail in [&entry.volume, &entry.pages] {
+
+            if !detail.is_empty() {
+                container.push(escape(&format!(", {detail}")));
+            }
+
+        }
+
+        parts.push(container.concat());
+    }
+
+    if !entry.publisher.is_empty() {
+        parts.push(escape(&entry.publisher));
+    }
+
+    let link = if !entry.doi.is_empty() {
+        Some((format!("https://doi.org/{}", entry.doi), format!("doi:{}", entry.doi)))
+    } else if !entry.url.is_empty() {
+        Some((entry.url.clone(), entry.url.clone()))
+    } else {
+        None
+    };
+
+    let mut result = parts.iter().map(
+        |part| if part.last() == Some(&('.' as u32)) {
+            vec![part.clone(), vec![32]].concat()
+        } else {
+            vec![part.clone(), vec![46, 32]].concat()  // into_v32(". ")
+        }
+    ).collect::<Vec<Vec<u32>>>();
+
+    if let Some((destination, text)) = link {
+        result.push(vec![
+            into_v32("<a href=\""),
+            escape(&render_option.handle_link(&destination)),
+            into_v32("\">"),
+            escape(&text),
+            vec![60, 47, 97, 62]  // into_v32("</a>")
+        ].concat());
+    }
+
+    // trailing whitespace
+    else if let Some(last) = result.last_mut() {
+        last.pop();
+    }
+
+    result.concat()
+}
+
+fn sort_name(entry: &BibEntry) -> String {
+
+    match entry.authors.first() {
+        Some(author) => author.family.to_lowercase(),
+        None => entry.title.to_lowercase()
+    }
+
+}
@@ -0,0 +1,46 @@
+use super::bibliography::Bibliography;
+use crate::utils::{from_v32, get_bracket_end_index, strip_whitespaces};
+
+// `[@key]`, `[@key, p. 12]`, `[@key1; @key2, chapter 3]`
+// it's a citation only when all the keys are in the bibliography
+pub fn read_citation(content: &[u32], index: usize, bibliography: &Bibliography) -> Option<(Vec<(String, Vec<u32>)>, usize)> {  // (Vec<(key, locator)>, bracket_end_index)
+
+    if content[index] != '[' as u32 || content.get(index + 1) != Some(&('@' as u32)) || bibliography.is_empty() {
+        return None;
+    }
+
+    let bracket_end_index = get_bracket_end_index(content, index)?;
+    let mut result = vec![];
+
+    for item in content[(index + 1)..bracket_end_index].split(|c| *c == ';' as u32) {
+        let item = strip_whitespaces(item);
+
+        if item.first() != Some(&('@' as u32)) {
+            return None;
+        }
+
+        let (key, locator) = match item.iter().position(|c| *c == ',' as u32) {
+            Some(comma_index) => (&item[1..comma_index], strip_whitespaces(&item[(comma_index + 1)..])),
+            None => (&item[1..], vec![])
+        };
+
+        if key.is_empty() || !key.iter().all(is_valid_citation_key_character) {
+            return None;
+        }
+
+        let key = from_v32(key);
+
+        bibliography.get(&key)?;
+
+        result.push((key, locator));
+    }
+
+    Some((result, bracket_end_index))
+}
+
+fn is_valid_citation_key_character(c: &u32) -> bool {
+    '0' as u32 <= *c && *c <= '9' as u32
+    || 'a' as u32 <= *c && *c <= 'z' as u32
+    || 'A' as u32 <= *c && *c <= 'Z' as u32
+    || [45, 46, 47, 58, 95].contains(c)  // into_v32("-./:_")
+}
@@ -0,0 +1,186 @@
+use super::bibliography::Bibliography;
+use crate::render::render_option::{CitationStyle, RenderOption};
+use crate::render_to_html;
+use crate::utils::{into_v32, remove_whitespaces};
+
+const BIBTEX: &str = "
+@string{cj = {The Computer Journal}}
+
+@article{knuth84,
+    author = {Knuth, Donald E.},
+    title = {Literate {Programming}},
+    journal = {The Computer Journal},
+    year = 1984,
+    volume = {27},
+    pages = {97--111}
+}
+
+@book{lamport94,
+    author = \"Leslie Lamport and {World Health Organization}\",
+    title = \"LaTeX: A Document Preparation System\",
+    publisher = {Addison-Wesley},
+    year = {1994}
+}
+";
+
+const CSL_JSON: &str = r#"[
+    {"id": "web", "title": "Web & <Page>", "author": [{"family": "Doe"}, {"family": "Roe"}, {"family": "Poe"}], "issued": {"date-parts": [[2020, 1]]}, "URL": "https://example.com"}
+]"#;
+
+fn samples() -> Vec<(CitationStyle, String, String)> {
+    let result = vec![
+        (CitationStyle::Numeric, "
+Knuth said [@knuth84, p. 12]. See also [@lamport94; @web] and [@knuth84].
+
+Unknown [@nope] and [@knuth84 no comma].
+", "
+<p>
+    Knuth said <span class=\"citation\" id=\"citation-ref-0\">[<a href=\"#reference-0\">1</a>, p. 12]</span>.
+    See also <span class=\"citation\" id=\"citation-ref-1\">[<a href=\"#reference-1\">2</a>; <a href=\"#reference-2\">3</a>]</span>
+    and <span class=\"citation\" id=\"citation-ref-2\">[<a href=\"#reference-0\">1</a>]</span>.
+</p>
+<p>Unknown [@nope] and [@knuth84 no comma].</p>
+<div class=\"mdxt-references\">
+    <div class=\"reference\" id=\"reference-0\">
+        <span class=\"reference-label\">[1]</span> <a href=\"#citation-ref-0\">^</a> <a href=\"#citation-ref-2\">^</a>
+        Knuth, Donald E. (1984). Literate Programming. <em>The Computer Journal</em>, 27, 97–111.
+    </div>
+    <div class=\"reference\" id=\"reference-1\">
+        <span class=\"reference-label\">[2]</span> <a href=\"#citation-ref-1\">^</a>
+        Lamport, Leslie and World Health Organization. (1994). LaTeX: A Document Preparation System. Addison-Wesley.
+    </div>
+    <div class=\"reference\" id=\"reference-2\">
+        <span class=\"reference-label\">[3]</span> <a href=\"#citation-ref-1\">^</a>
+        Doe, Roe and Poe. (2020). Web &amp; &lt;Page&gt;. <a href=\"https://example.com\">https://example.com</a>
+    </div>
+</div>
+"), (CitationStyle::AuthorYear, "
+Knuth said [@knuth84, p. 12]. See also [@lamport94; @web] and [@knuth84].
+", "
+<p>
+    Knuth said <span class=\"citation\" id=\"citation-ref-0\">(<a href=\"#reference-0\">Knuth 1984</a>, p. 12)</span>.
+    See also <span class=\"citation\" id=\"citation-ref-1\">(<a href=\"#reference-1\">Lamport and World Health Organization 1994</a>; <a href=\"#reference-2\">Doe et al. 2020</a>)</span>
+    and <span class=\"citation\" id=\"citation-ref-2\">(<a href=\"#reference-0\">Knuth 1984</a>)</span>.
+</p>
+<div class=\"mdxt-references\">
+    <div class=\"reference\" id=\"reference-2\">
+        <a href=\"#citation-ref-1\">^</a>
+        Doe, Roe and Poe. (2020). Web &amp; &lt;Page&gt;. <a href=\"https://example.com\">https://example.com</a>
+    </div>
+    <div class=\"reference\" id=\"reference-0\">
+        <a href=\"#citation-ref-0\">^</a> <a href=\"#citation-ref-2\">^</a>
+        Knuth, Donald E. (1984). Literate Programming. <em>The Computer Journal</em>, 27, 97–111.
+    </div>
+    <div class=\"reference\" id=\"reference-1\">
+        <a href=\"#citation-ref-1\">^</a>
+        Lamport, Leslie and World Health Organization. (1994). LaTeX: A Document Preparation System. Addison-Wesley.
+    </div>
+</div>
+"), (CitationStyle::Numeric, "
+[@web] is in a footnote.[^a] `[@web]` is not a citation.
+
+[^a]: Cited again: [@web]
+", "
+<p>
+    <span class=\"citation\" id=\"citation-ref-0\">[<a href=\"#reference-0\">1</a>]</span> is in a footnote.<span class=\"footnote-ref\" id=\"footnote-ref-0\"><a href=\"#footnote-cite-0\">[0]</a></span>
+    <code class=\"inline-code-span\">[@web]</code> is not a citation.
+</p>
+<hr class=\"footnote-hr\"/>
+<div class=\"mdxt-footnote-cites\"><p>
+    <div class=\"footnote-cite\">
+        <a id=\"footnote-cite-0\"></a><a href=\"#footnote-ref-0\"> [0]</a>
+        Cited again: <span class=\"citation\" id=\"citation-ref-1\">[<a href=\"#reference-0\">1</a>]</span>
+    </div>
+</p></div>
+<div class=\"mdxt-references\">
+    <div class=\"reference\" id=\"reference-0\">
+        <span class=\"reference-label\">[1]</span> <a href=\"#citation-ref-0\">^</a> <a href=\"#citation-ref-1\">^</a>
+        Doe, Roe and Poe. (2020). Web &amp; &lt;Page&gt;. <a href=\"https://example.com\">https://example.com</a>
+    </div>
+</div>
+")
+    ];
+
+    result.into_iter().map(
+        |(style, case, answer)| (style, case.to_string(), answer.to_string())
+    ).collect()
+}
+
+#[test]
+fn citation_test() {
+    let mut bibliography = Bibliography::from_bibtex(BIBTEX).unwrap();
+    bibliography.merge(Bibliography::from_csl_json(CSL_JSON).unwrap());
+
+    for (style, md, html) in samples().iter() {
+        let mut render_option = RenderOption::default();
+        render_option.set_bibliography(bibliography.clone()).set_citation_style(*style);
+
+        let rendered = render_to_html(md, render_option).content;
+
+        if remove_whitespaces(&into_v32(&rendered)) != remove_whitespaces(&into_v32(html)) {
+            panic!("{md} \n\n {rendered}");
+        }
+
+    }
+
+}
+
+#[test]
+fn metadata_bibliography_test() {
+    let md = "---
+citation-style: author-year
+references:
+  - id: doe20
+    title: A Post
+    author:
+      - family: Doe
+        given: Jane
+    issued:
+      date-parts: [[2020]]
+---
+[@doe20]
+";
+
+    let rendered = render_to_html(md, RenderOption::default()).content;
+
+    assert!(rendered.contains("(<a href=\"#reference-0\">Doe 2020</a>)"));
+    assert!(rendered.contains("Doe, Jane. (2020). A Post."));
+}
+
+#[test]
+fn bibliography_files_test() {
+    let path = std::env::temp_dir().join("mdxt_bibliography_files_test.bib");
+    std::fs::write(&path, BIBTEX).unwrap();
+
+    let md = format!("---
+bibliography: {}
+---
+[@knuth84]
+", path.display());
+
+    let rendered = render_to_html(&md, RenderOption::default()).content;
+    assert!(rendered.contains("[@knuth84]"));
+
+    let mut render_option = RenderOption::default();
+    render_option.set_bibliography_files(true);
+    let rendered = render_to_html(&md, render_option).content;
+    assert!(rendered.contains("<a href=\"#reference-0\">1</a>"));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn bibtex_test() {
+    let bibliography = Bibliography::from_bibtex(BIBTEX).unwrap();
+
+    assert_eq!(bibliography.len(), 2);
+    assert_eq!(bibliography.get("knuth84").unwrap().title, "Literate Programming");
+    assert_eq!(bibliography.get("lamport94").unwrap().authors[1].family, "World Health Organization");
+
+    assert!(Bibliography::from_bibtex("@article{key, title = {unclosed").is_err());
+
+    // texts outside the entries are comments
+    let bibliography = Bibliography::from_bibtex("% contact me@x.org\n\n@book{key, title = {Title}}").unwrap();
+    assert_eq!(bibliography.get("key").unwrap().title, "Title");
+    assert!(Bibliography::from_csl_json("{\"id\": \"not an array\"}").is_err());
+}
@@ -4,44 +4,138 @@ pub mod predicate;
 mod testbench;
 
 use super::InlineNode;
+use crate::ast::{doc_data::DocData, line::Line, node::Node};
+use crate::render::render_option::RenderOption;
 use crate::utils::into_v32;
-use std::collections::HashMap;
 
 #[derive(Clone)]
 pub struct Footnote {
     pub index: usize,
     pub inverse_index: Vec<usize>,
-    pub content: InlineNode
+    pub content: Vec<Node>,
+
+    // a footnote without empty lines is rendered without `<p>` tags
+    pub tight: bool,
+
+    // the index of the top-level node that first references this footnote
+    // if the footnote is first referenced by another footnote, it follows the other footnote
+    // `FootnoteLayout::Sidenote` and `FootnoteLayout::Section` use this
+    pub node_index: Option<usize>,
+
+    // the first paragraph of the footnote, which is not parsed yet
+    // `content` is parsed later, but tooltips need an unparsed one
+    preview: InlineNode,
+    has_more_blocks: bool
 }
 
-pub fn footnotes_to_html(footnotes: &mut HashMap<Vec<u32>, Footnote>, toc_rendered: &[u32], class_prefix: &str) -> Vec<u32> {
+impl Footnote {
+
+    pub fn new(index: usize, content: Vec<Node>, tight: bool) -> Self {
+        let nodes = content.iter().filter(
+            |node| !matches!(node, Node::Empty)
+        ).collect::<Vec<&Node>>();
+
+        let (preview, has_more_blocks) = match nodes.first() {
+            Some(Node::Paragraph { content }) => (content.clone(), nodes.len() > 1),
+            Some(_) => (InlineNode::Raw(vec![]), true),
+            None => (InlineNode::Raw(vec![]), false)
+        };
+
+        Footnote {
+            index,
+            inverse_index: vec![],
+            content,
+            tight,
+            node_index: None,
+            preview,
+            has_more_blocks
+        }
+    }
+
+    // if the footnote is a single paragraph, it's rendered inline
+    pub fn inline_content(&self) -> Option<&InlineNode> {
+        let nodes = self.content.iter().filter(
+            |node| !matches!(node, Node::Empty)
+        ).collect::<Vec<&Node>>();
+
+        match nodes[..] {
+            [Node::Paragraph { content }] if self.tight => Some(content),
+            _ => None
+        }
+    }
+
+    // a tooltip only shows the first paragraph of the footnote
+    // it returns the unparsed paragraph, and whether there're more blocks or not
+    pub fn tooltip_preview(&self) -> (InlineNode, bool) {
+        (self.preview.clone(), self.has_more_blocks)
+    }
+
+}
+
+/*
+```
+[^a]: The first paragraph of the footnote.
+
+    The second paragraph of the footnote.
+
+    - a list inside the footnote
+```
+
+Like Pandoc, lines indented by 4 spaces belong to the footnote.
+`first_line` is the content of `lines[index]` after `[^a]:`.
+*/
+pub fn read_footnote_lines(lines: &[Line], index: usize, first_line: &[u32]) -> (Vec<Line>, usize) {  // (lines, end_index)
+    let mut result = vec![Line::new(first_line.to_vec(), 0)];
+    let mut curr_index = index + 1;
+    let mut end_index = index + 1;
+
+    while curr_index < lines.len() {
+
+        if lines[curr_index].is_empty() {
+            curr_index += 1;
+            continue;
+        }
+
+        else if lines[curr_index].indent >= 4 {
+
+            // empty lines between the blocks of the footnote
+            for line in lines[end_index..curr_index].iter() {
+                result.push(line.try_sub_indent(4));
+            }
 
-    let notes = footnotes.values().map(|x| x.clone()).collect::<Vec<Footnote>>();
+            result.push(lines[curr_index].try_sub_indent(4));
+        }
+
+        else {
+            break;
+        }
+
+        curr_index += 1;
+        end_index = curr_index;
+    }
+
+    (result, end_index)
+}
+
+pub fn footnotes_to_html(footnotes: &[Footnote], toc_rendered: &Vec<u32>, render_option: &RenderOption, doc_data: &mut DocData) -> Vec<u32> {
+    let class_prefix = &render_option.class_prefix;
 
     let mut result = Vec::with_capacity(3);
-    let mut footnote_cites: Vec<(Vec<u32>, usize)> = Vec::with_capacity(notes.len());
+    let mut footnote_cites: Vec<(Vec<u32>, usize)> = Vec::with_capacity(footnotes.len());
 
     result.push(into_v32(&format!("<hr class=\"{class_prefix}footnote-hr\"/><div class=\"{class_prefix}mdxt-footnote-cites\"><p>")));
 
-    for Footnote {index, inverse_index, content} in notes.into_iter() {
+    for footnote in footnotes.iter() {
+        let Footnote { index, inverse_index, .. } = footnote;
 
         if inverse_index.is_empty() {
             continue;
         }
 
-        #[cfg(test)]
-        assert_eq!(inverse_index, {let mut ii = inverse_index.clone(); ii.sort(); ii});
-
-        let inverse_indexes = inverse_index.iter().map(
-            |ind|
-            into_v32(&format!("<a href=\"#footnote-ref-{ind}\"> [{ind}]</a> "))
-        ).collect::<Vec<Vec<u32>>>().concat();
-
         footnote_cites.push((
             vec![
                 into_v32(&format!("<div class=\"footnote-cite\"><a id=\"footnote-cite-{index}\"></a>")),
-                inverse_indexes,
-                content.to_html(toc_rendered, class_prefix),
+                footnote_body_to_html(footnote, toc_rendered, render_option, doc_data),
                 into_v32("</div>")
             ].concat(),
             inverse_index[0]
@@ -62,4 +156,45 @@ pub fn footnotes_to_html(footnotes: &mut HashMap<Vec<u32>, Footnote>, toc_render
     }
 
     result.concat()
-}
\ No newline at end of file
+}
+
+// `FootnoteLayout::Sidenote`
+pub fn sidenote_to_html(footnote: &Footnote, toc_rendered: &Vec<u32>, render_option: &RenderOption, doc_data: &mut DocData) -> Vec<u32> {
+    let class_prefix = &render_option.class_prefix;
+    let index = footnote.index;
+
+    vec![
+        into_v32(&format!("<aside class=\"{class_prefix}sidenote\" id=\"footnote-cite-{index}\">")),
+        footnote_body_to_html(footnote, toc_rendered, render_option, doc_data),
+        into_v32("</aside>")
+    ].concat()
+}
+
+// backlinks to the references, and the content
+fn footnote_body_to_html(footnote: &Footnote, toc_rendered: &Vec<u32>, render_option: &RenderOption, doc_data: &mut DocData) -> Vec<u32> {
+    let class_prefix = &render_option.class_prefix;
+    let Footnote { inverse_index, content, .. } = footnote;
+
+    #[cfg(test)]
+    assert_eq!(*inverse_index, {let mut ii = inverse_index.clone(); ii.sort(); ii});
+
+    let inverse_indexes = inverse_index.iter().map(
+        |ind|
+        into_v32(&format!("<a href=\"#footnote-ref-{ind}\"> [{ind}]</a> "))
+    ).collect::<Vec<Vec<u32>>>().concat();
+
+    let content = match footnote.inline_content() {
+        Some(inline_content) => inline_content.to_html(toc_rendered, class_prefix),
+        None => {
+            let mut buffer = vec![];
+
+            for node in content.iter() {
+                node.to_html(toc_rendered, render_option, doc_data, &mut buffer);
+            }
+
+            buffer.concat()
+        }
+    };
+
+    vec![inverse_indexes, content].concat()
+}
@@ -195,10 +195,210 @@ aa[^A] bb[^B]
         </div>
     </p>
 </div>
+".to_string()), ("
+a[^a]
+
+[^a]: The first paragraph.
+
+    The second paragraph.
+
+    - a
+    - b
+
+paragraph
+".to_string(), "
+<p>
+    a<span class=\"footnote-ref\" id=\"footnote-ref-0\"><a href=\"#footnote-cite-0\">[0]</a></span>
+</p>
+
+<p>paragraph</p>
+
+<hr class=\"footnote-hr\"/>
+
+<div class=\"mdxt-footnote-cites\">
+    <p>
+        <div class=\"footnote-cite\">
+            <a id=\"footnote-cite-0\"></a><a href=\"#footnote-ref-0\"> [0]</a>
+            <p>The first paragraph.</p>
+            <p>The second paragraph.</p>
+            <ul><li>a</li><li>b</li></ul>
+        </div>
+    </p>
+</div>
+".to_string()), ("
+a[^a]
+
+[^a]: A references B.[^b]
+[^b]: B references A.[^a]
+[^c]: C references A, but C is never used.[^a]
+".to_string(), "
+<p>
+    a<span class=\"footnote-ref\" id=\"footnote-ref-0\"><a href=\"#footnote-cite-0\">[0]</a></span>
+</p>
+
+<hr class=\"footnote-hr\"/>
+
+<div class=\"mdxt-footnote-cites\">
+    <p>
+        <div class=\"footnote-cite\">
+            <a id=\"footnote-cite-0\"></a><a href=\"#footnote-ref-0\"> [0]</a> <a href=\"#footnote-ref-2\"> [2]</a>
+            A references B.<span class=\"footnote-ref\" id=\"footnote-ref-1\"><a href=\"#footnote-cite-1\">[1]</a></span>
+        </div>
+        <div class=\"footnote-cite\">
+            <a id=\"footnote-cite-1\"></a><a href=\"#footnote-ref-1\"> [1]</a>
+            B references A.<span class=\"footnote-ref\" id=\"footnote-ref-2\"><a href=\"#footnote-cite-0\">[2]</a></span>
+        </div>
+    </p>
+</div>
 ".to_string())
     ]
 }
 
+// footnotes that reference each other must not make tooltips recursively
+#[test]
+fn footnote_tooltip_cycle_test() {
+    use crate::{render_to_html, RenderOption};
+
+    let mut render_option = RenderOption::default();
+    render_option.set_footnote_tooltip(true);
+
+    let rendered = render_to_html("
+a[^a]
+
+[^a]: A references B.[^b]
+
+    It has more paragraphs.
+[^b]: B references A.[^a]
+", render_option).content;
+
+    // a tooltip message shows the first paragraph of the footnote, and the references inside the message don't have tooltips
+    assert_eq!(rendered.matches("class=\"tooltip-container\"").count(), 3);
+    assert!(rendered.contains("A references B."));
+    assert!(rendered.contains("It has more paragraphs."));
+}
+
+// the references inside tooltip messages don't have ids, and don't change the numbers of the other references
+#[test]
+fn footnote_tooltip_id_test() {
+    use crate::{render_to_html, RenderOption};
+
+    let mut render_option = RenderOption::default();
+    render_option.set_footnote_tooltip(true);
+
+    let rendered = render_to_html("
+A[^1] and B[^2]
+
+[[tooltip = 2]]hover[[/tooltip]] C[^1]
+
+[^1]: one
+[^2]: two[^1]
+", render_option).content;
+
+    let mut ids = rendered.split("id=\"").skip(1).map(
+        |s| s.split('"').next().unwrap()
+    ).collect::<Vec<&str>>();
+    let id_count = ids.len();
+    ids.sort_unstable();
+    ids.dedup();
+
+    assert_eq!(ids.len(), id_count);
+    assert_eq!(rendered.matches("id=\"footnote-ref-").count(), 4);
+    assert!(rendered.contains("two<span class=\"footnote-ref\"><a href=\"#footnote-cite-0\">[0]</a></span>"));
+}
+
+#[test]
+fn footnote_layout_test() {
+    use crate::{render_to_html, FootnoteLayout, RenderOption};
+
+    let md = "
+intro[^a]
+
+# One
+
+one[^b] again[^a]
+
+## Sub
+
+sub[^c]
+
+# Two
+
+two[^d]
+
+[^a]: A references E.[^e]
+[^b]: B
+[^c]: C
+[^d]: D
+
+    more D
+[^e]: E
+";
+
+    let sidenote = "
+<p>intro<span class=\"footnote-ref\" id=\"footnote-ref-0\"><a href=\"#footnote-cite-0\">[0]</a></span></p>
+<aside class=\"sidenote\" id=\"footnote-cite-0\">
+    <a href=\"#footnote-ref-0\"> [0]</a> <a href=\"#footnote-ref-2\"> [2]</a>
+    A references E.<span class=\"footnote-ref\" id=\"footnote-ref-5\"><a href=\"#footnote-cite-4\">[5]</a></span>
+</aside>
+<aside class=\"sidenote\" id=\"footnote-cite-4\"><a href=\"#footnote-ref-5\"> [5]</a> E</aside>
+<h1 id=\"one\">One</h1>
+<p>
+    one<span class=\"footnote-ref\" id=\"footnote-ref-1\"><a href=\"#footnote-cite-1\">[1]</a></span>
+    again<span class=\"footnote-ref\" id=\"footnote-ref-2\"><a href=\"#footnote-cite-0\">[2]</a></span>
+</p>
+<aside class=\"sidenote\" id=\"footnote-cite-1\"><a href=\"#footnote-ref-1\"> [1]</a> B</aside>
+<h2 id=\"sub\">Sub</h2>
+<p>sub<span class=\"footnote-ref\" id=\"footnote-ref-3\"><a href=\"#footnote-cite-2\">[3]</a></span></p>
+<aside class=\"sidenote\" id=\"footnote-cite-2\"><a href=\"#footnote-ref-3\"> [3]</a> C</aside>
+<h1 id=\"two\">Two</h1>
+<p>two<span class=\"footnote-ref\" id=\"footnote-ref-4\"><a href=\"#footnote-cite-3\">[4]</a></span></p>
+<aside class=\"sidenote\" id=\"footnote-cite-3\"><a href=\"#footnote-ref-4\"> [4]</a> <p>D</p><p>more D</p></aside>
+";
+
+    let section = "
+<p>intro<span class=\"footnote-ref\" id=\"footnote-ref-0\"><a href=\"#footnote-cite-0\">[0]</a></span></p>
+<hr class=\"footnote-hr\"/>
+<div class=\"mdxt-footnote-cites\"><p>
+    <div class=\"footnote-cite\">
+        <a id=\"footnote-cite-0\"></a><a href=\"#footnote-ref-0\"> [0]</a> <a href=\"#footnote-ref-2\"> [2]</a>
+        A references E.<span class=\"footnote-ref\" id=\"footnote-ref-5\"><a href=\"#footnote-cite-4\">[5]</a></span>
+    </div>
+    <div class=\"footnote-cite\"><a id=\"footnote-cite-4\"></a><a href=\"#footnote-ref-5\"> [5]</a> E</div>
+</p></div>
+<h1 id=\"one\">One</h1>
+<p>
+    one<span class=\"footnote-ref\" id=\"footnote-ref-1\"><a href=\"#footnote-cite-1\">[1]</a></span>
+    again<span class=\"footnote-ref\" id=\"footnote-ref-2\"><a href=\"#footnote-cite-0\">[2]</a></span>
+</p>
+<h2 id=\"sub\">Sub</h2>
+<p>sub<span class=\"footnote-ref\" id=\"footnote-ref-3\"><a href=\"#footnote-cite-2\">[3]</a></span></p>
+<hr class=\"footnote-hr\"/>
+<div class=\"mdxt-footnote-cites\"><p>
+    <div class=\"footnote-cite\"><a id=\"footnote-cite-1\"></a><a href=\"#footnote-ref-1\"> [1]</a> B</div>
+    <div class=\"footnote-cite\"><a id=\"footnote-cite-2\"></a><a href=\"#footnote-ref-3\"> [3]</a> C</div>
+</p></div>
+<h1 id=\"two\">Two</h1>
+<p>two<span class=\"footnote-ref\" id=\"footnote-ref-4\"><a href=\"#footnote-cite-3\">[4]</a></span></p>
+<hr class=\"footnote-hr\"/>
+<div class=\"mdxt-footnote-cites\"><p>
+    <div class=\"footnote-cite\"><a id=\"footnote-cite-3\"></a><a href=\"#footnote-ref-4\"> [4]</a> <p>D</p><p>more D</p></div>
+</p></div>
+";
+
+    for (layout, html) in [(FootnoteLayout::Sidenote, sidenote), (FootnoteLayout::Section, section)] {
+        let mut render_option = RenderOption::default();
+        render_option.set_footnote_layout(layout);
+
+        let rendered = render_to_html(md, render_option).content;
+
+        if remove_whitespaces(&into_v32(&rendered)) != remove_whitespaces(&into_v32(html)) {
+            panic!("{layout:?} \n\n {rendered}");
+        }
+
+    }
+
+}
+
 #[test]
 fn footnote_test() {
     for (md, html) in samples().iter() {
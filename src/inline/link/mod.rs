@@ -1,6 +1,7 @@
 // `[[abc]](def)` is a macro, not a link!
 
 pub mod predicate;
+pub mod wiki;
 
 #[cfg(test)]
 mod testbench;
@@ -5,8 +5,8 @@
 
 use super::normalize_link_label;
 use crate::inline::parse::undo_code_span_escapes;
-use crate::escape::{render_html_escapes, render_backslash_escapes};
-use crate::utils::{drop_while, get_bracket_end_index, get_parenthesis_end_index};
+use crate::escape::{render_html_escapes, render_backslash_escapes, HTML_ESCAPE_OFFSET};
+use crate::utils::{drop_while, get_bracket_end_index, get_parenthesis_end_index, strip_whitespaces};
 use std::collections::HashMap;
 
 // [foo](address)
@@ -14,7 +14,7 @@ pub fn read_direct_link(
     content: &[u32],
     index: usize,
     link_references: &HashMap<Vec<u32>, Vec<u32>>
-) -> Option<(Vec<u32>, Vec<u32>, usize)> {  // Option<(link_text, link_destination, last_index)>
+) -> Option<(Vec<u32>, Vec<u32>, Option<Vec<u32>>, usize)> {  // Option<(link_text, link_destination, link_title, last_index)>
 
     if content[index] == '[' as u32 {
 
@@ -28,16 +28,17 @@ pub fn read_direct_link(
                 match get_parenthesis_end_index(content, bracket_end_index + 1) {
                     Some(parenthesis_end_index) => {
                         let link_text = &content[index + 1..bracket_end_index];
+                        let (link_destination, link_title) = split_link_title(&content[bracket_end_index + 2..parenthesis_end_index]);
 
                         // TODO: `render_backslash_escapes` vs `undo_backslash_escapes`?
                         let link_destination = undo_code_span_escapes(
                             &render_backslash_escapes(
-                                &render_html_escapes(&content[bracket_end_index + 2..parenthesis_end_index])
+                                &render_html_escapes(&link_destination)
                             )
                         );
 
                         if is_valid_link_text(link_text, link_references) {
-                            Some((link_text.to_vec(), link_destination.to_vec(), parenthesis_end_index))
+                            Some((link_text.to_vec(), link_destination.to_vec(), link_title, parenthesis_end_index))
                         }
 
                         else {
@@ -60,6 +61,24 @@ pub fn read_direct_link(
 
 }
 
+// `image.png "title"` -> (`image.png`, Some(`title`))
+// the quotes are html-escaped at this point, and the title remains escaped
+fn split_link_title(content: &[u32]) -> (Vec<u32>, Option<Vec<u32>>) {  // (link_destination, link_title)
+    let content = strip_whitespaces(content);
+    let quotes = [HTML_ESCAPE_OFFSET + 34, HTML_ESCAPE_OFFSET + 39];  // `"`, `'`
+
+    if let Some(space_index) = content.iter().position(|c| *c == ' ' as u32) {
+        let title = drop_while(&content[space_index..], ' ' as u32);
+
+        if title.len() > 1 && quotes.contains(&title[0]) && title.last() == Some(&title[0]) {
+            return (content[0..space_index].to_vec(), Some(title[1..(title.len() - 1)].to_vec()));
+        }
+
+    }
+
+    (content, None)
+}
+
 // [foo][bar]
 // [foo][]
 pub fn read_reference_link(content: &[u32], index: usize, link_references: &HashMap<Vec<u32>, Vec<u32>>) -> Option<(Vec<u32>, Vec<u32>, usize)> {  // Option<(link_text, link_label, last_index)>
@@ -145,4 +145,48 @@ fn normalize_link_test() {
         assert_eq!(normalize_link_label(&case), answer);
     }
 
-}
\ No newline at end of file
+}
+
+#[test]
+fn wiki_link_test() {
+    use crate::{render_to_html, Diagnostic, RenderOption, WikiLink};
+
+    let md = "[[[Home]]], [[[Home#Getting Started|*start*]]], [[link = Missing Page]] and [[Home|home]].
+
+[[[char=9650]]](#top) [[[char=65]]] `[[[Home]]]`
+
+| wiki link |
+|-----------|
+| [[[Home\\|cell]]] |
+
+[[red|x]] [[Red | x]] [[tooltip = a|b]]";
+
+    let rendered = render_to_html(md, RenderOption::default());
+
+    assert!(rendered.content.starts_with("<p><a class=\"wiki-link\" href=\"home\">Home</a>, <a class=\"wiki-link\" href=\"home#getting-started\"><em>start</em></a>, <a class=\"wiki-link\" href=\"missing-page\">Missing Page</a> and <a class=\"wiki-link\" href=\"home\">home</a>.</p>"));
+    assert!(rendered.content.contains("<p><a href=\"#top\">&#9650;</a> [&#65;] <code class=\"inline-code-span\">[[[Home]]]</code></p>"));
+    assert!(rendered.content.contains("<td><a class=\"wiki-link\" href=\"home\">cell</a></td>"));
+    assert!(rendered.content.ends_with("<p>[[red|x]] [[Red | x]] [[tooltip = a|b]]</p>"));
+    assert!(rendered.diagnostics.is_empty());
+    assert!(!rendered.assets(&RenderOption::default()).css.contains("missing-wiki-link"));
+
+    // the resolver may capture the pages of a site
+    let pages = [String::from("Home")];
+    let mut render_option = RenderOption::default();
+    render_option.set_wiki_link_resolver(
+        move |title| if pages.iter().any(|page| page == title) { Some(String::from("/wiki/?page=home&lang=\"en\"")) } else { None }
+    ).set_class_prefix(String::from("mdxt-"));
+    let rendered = render_to_html(md, render_option.clone());
+
+    assert!(rendered.content.starts_with("<p><a class=\"mdxt-wiki-link\" href=\"/wiki/?page=home&amp;lang=&quot;en&quot;\">Home</a>, <span class=\"mdxt-missing-wiki-link\"><em>start</em></span>, <span class=\"mdxt-missing-wiki-link\">Missing Page</span>"));
+    assert_eq!(rendered.diagnostics, vec![
+        Diagnostic::MissingWikiLink { title: String::from("Home#Getting Started") },
+        Diagnostic::MissingWikiLink { title: String::from("Missing Page") },
+    ]);
+    assert_eq!(rendered.wiki_links, vec![
+        WikiLink { title: String::from("Home"), destination: Some(String::from("/wiki/?page=home&lang=\"en\"")) },
+        WikiLink { title: String::from("Home#Getting Started"), destination: None },
+        WikiLink { title: String::from("Missing Page"), destination: None },
+    ]);
+    assert!(rendered.assets(&render_option).css.contains(".mdxt-missing-wiki-link"));
+}
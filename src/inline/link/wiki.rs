@@ -0,0 +1,144 @@
+use crate::ast::doc_data::DocData;
+use crate::container::header::normalize_header;
+use crate::escape::{escape_htmls, render_backslash_escapes_raw, undo_html_escapes, BACKSLASH_ESCAPE_OFFSET, META_CHARACTER_OFFSET};
+use crate::inline::InlineNode;
+use crate::inline::macros::{get_macro, normalize_macro};
+use crate::render::diagnostic::Diagnostic;
+use crate::render::render_option::RenderOption;
+use crate::utils::{from_v32, get_bracket_end_index, into_v32, strip_whitespaces};
+use std::sync::Arc;
+
+/*
+Wiki links point to the other pages of a site by their titles. `RenderOption.wiki_link_resolver` turns the titles into urls.
+
+- `[[[Page Name]]]`
+- `[[[Page Name|label]]]`
+- `[[link = Page Name]]`, `[[link = Page Name|label]]`
+- `[[Page Name|label]]`: `|` makes it different from macros, unless `Page Name` has `=` or is a name of a macro
+
+In a table, `|` divides cells, so write `\|` instead.
+*/
+
+/// A wiki link in a document. `RenderResult.wiki_links` has one for each title, in the order of their first appearances.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WikiLink {
+    /// `Page Name` of `[[[Page Name|label]]]`
+    pub title: String,
+
+    /// What `RenderOption.wiki_link_resolver` returned. `None` if the page is missing.
+    pub destination: Option<String>
+}
+
+/// `RenderOption.wiki_link_resolver`: a title to its url, or `None` if the page is missing.
+pub type WikiLinkResolver = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+// `content[index]` is `[`
+pub fn read_wiki_link(content: &[u32], index: usize, render_option: &RenderOption) -> Option<(Vec<u32>, Option<Vec<u32>>, usize)> {  // (title, label, last_index)
+
+    if content.get(index + 1) != Some(&('[' as u32)) {
+        return None;
+    }
+
+    let end_index = get_bracket_end_index(content, index)?;
+
+    if get_bracket_end_index(content, index + 1)? + 1 != end_index {
+        return None;
+    }
+
+    // `[[[Page Name]]]`
+    // `[[[char = 9650]]](#top)` is a link with a macro, and `[[[char = 65]]]` is a macro in brackets
+    let inner = if content[index + 2] == '[' as u32 {
+
+        if get_bracket_end_index(content, index + 2)? + 2 != end_index
+            || content.get(end_index + 1) == Some(&('(' as u32))
+            || content.get(end_index + 1) == Some(&('[' as u32))
+        {
+            return None;
+        }
+
+        let inner = &content[(index + 3)..(end_index - 2)];
+
+        if inner.iter().take_while(|c| !is_pipe(c)).any(|c| *c == '=' as u32) {
+            return None;
+        }
+
+        inner
+    }
+
+    else {
+        let inner = &content[(index + 2)..(end_index - 1)];
+
+        match inner.iter().position(|c| *c == '=' as u32) {
+
+            // `[[link = Page Name]]`
+            Some(equal_index) if normalize_macro(&inner[..equal_index]) == [108, 105, 110, 107] => &inner[(equal_index + 1)..],  // into_v32("link")
+
+            // `[[Page Name|label]]`, but not `[[tooltip = message|x]]` or `[[red|x]]`
+            _ => match inner.iter().position(is_pipe) {
+                Some(pipe_index) if !inner[..pipe_index].contains(&('=' as u32))
+                    && get_macro(&normalize_macro(&inner[..pipe_index]), render_option).is_none() => inner,
+                _ => { return None; }
+            }
+        }
+
+    };
+
+    let (title, label) = match inner.iter().position(is_pipe) {
+        Some(pipe_index) => (strip_whitespaces(&inner[..pipe_index]), Some(strip_whitespaces(&inner[(pipe_index + 1)..]))),
+        None => (strip_whitespaces(inner), None)
+    };
+
+    if title.is_empty() || title.iter().any(
+        |c| *c == '[' as u32 || *c == ']' as u32 || *c == '\n' as u32 || *c >= META_CHARACTER_OFFSET
+    ) {
+        return None;
+    }
+
+    Some((title, label.filter(|label| !label.is_empty()), end_index))
+}
+
+// `\|` is also a pipe, for tables
+fn is_pipe(c: &u32) -> bool {
+    *c == '|' as u32 || *c == '|' as u32 + BACKSLASH_ESCAPE_OFFSET
+}
+
+pub fn wiki_link_to_node(title: Vec<u32>, label: Option<Vec<u32>>, doc_data: &mut DocData, render_option: &RenderOption) -> InlineNode {
+    let title_string = from_v32(&undo_html_escapes(&render_backslash_escapes_raw(&title)));
+    let destination = match &render_option.wiki_link_resolver {
+        Some(resolver) => resolver(&title_string),
+        None => default_wiki_link_resolver(&title_string)
+    };
+
+    // a paragraph may be parsed more than once
+    if !doc_data.wiki_links.iter().any(|link| link.title == title_string) {
+
+        if destination.is_none() {
+            doc_data.diagnostics.push(Diagnostic::MissingWikiLink { title: title_string.clone() });
+        }
+
+        doc_data.wiki_links.push(WikiLink { title: title_string, destination: destination.clone() });
+    }
+
+    let text = InlineNode::from_link_text(label.as_ref().unwrap_or(&title), doc_data, render_option);
+
+    InlineNode::WikiLink {
+        title,
+        text,
+        destination: destination.map(|destination| escape_htmls(&into_v32(&destination)))
+    }
+}
+
+// `Page Name#Section` -> `page-name#section`
+fn default_wiki_link_resolver(title: &str) -> Option<String> {
+    let title = into_v32(title);
+
+    match title.iter().position(|c| *c == '#' as u32) {
+        Some(sharp_index) => Some(from_v32(&[
+            normalize_header(&title[..sharp_index]),
+            vec![35],  // into_v32("#")
+            normalize_header(&title[(sharp_index + 1)..]),
+        ].concat())),
+        None => Some(from_v32(&normalize_header(&title)))
+    }
+
+}
@@ -0,0 +1,101 @@
+use super::normalize_macro;
+use crate::ast::AST;
+use crate::container::header::normalize_header;
+use crate::escape::META_CHARACTER_OFFSET;
+use crate::render::diagnostic::Diagnostic;
+use crate::utils::{from_v32, into_v32};
+use std::collections::HashMap;
+
+const CROSS_REFERENCE_BEGIN: u32 = META_CHARACTER_OFFSET + 0x10;
+const CROSS_REFERENCE_END: u32 = META_CHARACTER_OFFSET + 0x11;
+
+// `[[ref = fig-arch]]` is rendered to this placeholder, and `resolve_cross_references` replaces it with a link
+pub fn cross_reference_placeholder(label: &[u32]) -> Vec<u32> {
+    vec![
+        vec![CROSS_REFERENCE_BEGIN],
+        label.to_vec(),
+        vec![CROSS_REFERENCE_END]
+    ].concat()
+}
+
+impl AST {
+
+    // like `render_toc`, it's done after all the labels in the document are collected
+    pub fn resolve_cross_references(&mut self, html: Vec<u32>) -> Vec<u32> {
+
+        if !html.contains(&CROSS_REFERENCE_BEGIN) {
+            return html;
+        }
+
+        let class_prefix = &self.render_option.class_prefix;
+        let sections = if self.render_option.header_anchor {
+            section_labels(&self.doc_data.headers)
+        } else {
+            HashMap::new()
+        };
+
+        let mut result = Vec::with_capacity(html.len());
+        let mut index = 0;
+
+        while index < html.len() {
+
+            if html[index] != CROSS_REFERENCE_BEGIN {
+                result.push(html[index]);
+                index += 1;
+                continue;
+            }
+
+            let end_index = index + html[index..].iter().position(|c| *c == CROSS_REFERENCE_END).unwrap();
+            let label = &html[(index + 1)..end_index];
+
+            match self.doc_data.labels.get(label).or_else(|| sections.get(label)).or_else(|| self.doc_data.inline_labels.get(label)) {
+                Some((id, text)) => {
+                    result.extend(into_v32(&format!("<a class=\"{class_prefix}cross-reference\" href=\"#{}\">", from_v32(id))));
+                    result.extend(text);
+                    result.extend([60, 47, 97, 62]);  // into_v32("</a>")
+                },
+                None => {
+                    result.extend(into_v32(&format!(
+                        "<span class=\"{class_prefix}cross-reference {class_prefix}dangling-reference\">{}</span>",
+                        from_v32(label)
+                    )));
+                    let diagnostic = Diagnostic::DanglingReference { label: from_v32(label) };
+
+                    // the content, the toc and the sidebar are resolved separately, and a header is in both the content and the toc
+                    if !self.doc_data.diagnostics.contains(&diagnostic) {
+                        self.doc_data.diagnostics.push(diagnostic);
+                    }
+                }
+            }
+
+            index = end_index + 1;
+        }
+
+        result
+    }
+
+}
+
+// headers are numbered like the toc: `Section 2.1`
+fn section_labels(headers: &[(usize, Vec<u32>)]) -> HashMap<Vec<u32>, (Vec<u32>, Vec<u32>)> {
+    let min_level = headers.iter().map(|(level, _)| *level).min().unwrap_or(1);
+    let mut stack = [0; 6];
+    let mut result = HashMap::with_capacity(headers.len());
+
+    for (level, content) in headers.iter() {
+        stack[*level - 1] += 1;
+
+        for index in stack.iter_mut().skip(*level) {
+            *index = 0;
+        }
+
+        let anchor = normalize_header(content);
+        let number = stack[(min_level - 1)..*level].iter().map(
+            |index| index.to_string()
+        ).collect::<Vec<String>>().join(".");
+
+        result.entry(normalize_macro(&anchor)).or_insert((anchor, into_v32(&format!("Section {number}"))));
+    }
+
+    result
+}
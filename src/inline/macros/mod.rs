@@ -1,5 +1,6 @@
 pub mod multiline;
 pub mod predicate;
+pub mod cross_reference;
 pub mod toc;
 pub mod tooltip;
 mod character;
@@ -11,6 +12,7 @@ mod testbench;
 
 use crate::utils::{into_v32, from_v32, lowercase};
 use crate::color::COLOR_NAMES;
+use crate::RenderOption;
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 
@@ -18,18 +20,19 @@ lazy_static! {
     pub static ref MACROS: HashMap<Vec<u32>, Macro> = Macro::get_all_macros();
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Macro {
     pub name: Vec<u32>,
     macro_type: MacroType,
     pub has_closing: bool
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 enum MacroType {
     Color, Size, LineHeight, Alignment, Highlight,
     Box, Toc, Blank, Br, Char, Icon, Math,
-    HTML, Tooltip, Sidebar
+    HTML, Tooltip, Sidebar, Admonition,
+    Id, Ref
 }
 
 impl Macro {
@@ -54,11 +57,17 @@ impl Macro {
             Self::new("char", MacroType::Char, false),
             Self::new("math", MacroType::Math, true),
             Self::new("icon", MacroType::Icon, false),
+            Self::new("id", MacroType::Id, false),
+            Self::new("ref", MacroType::Ref, false),
             Self::new("div", MacroType::HTML, true),
             Self::new("span", MacroType::HTML, true),
             Self::new("anchor", MacroType::HTML, true),
             Self::new("button", MacroType::HTML, true),
-            Self::new("sidebar", MacroType::Sidebar, true)
+            Self::new("sidebar", MacroType::Sidebar, true),
+            Self::new("note", MacroType::Admonition, true),
+            Self::new("tip", MacroType::Admonition, true),
+            Self::new("warning", MacroType::Admonition, true),
+            Self::new("danger", MacroType::Admonition, true)
         ];
 
         for color in COLOR_NAMES.iter() {
@@ -113,7 +122,19 @@ impl Macro {
 
 }
 
-fn normalize_macro(content: &[u32]) -> Vec<u32> {
+// `MACROS`, and the colors registered by `RenderOption::set_colors`
+// `name` is normalized by `read_macro`
+pub fn get_macro(name: &Vec<u32>, render_option: &RenderOption) -> Option<Macro> {
+
+    match MACROS.get(name) {
+        Some(macro_) => Some(macro_.clone()),
+        None if render_option.has_color(name) => Some(Macro::new_color(&from_v32(name))),
+        None => None
+    }
+
+}
+
+pub fn normalize_macro(content: &[u32]) -> Vec<u32> {
     content.iter().filter(|c| **c != ' ' as u32 && **c != '_' as u32).map(lowercase).collect::<Vec<u32>>()
 }
 
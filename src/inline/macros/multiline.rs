@@ -1,11 +1,11 @@
 use super::{
     get_macro_name, parse_arguments,
-    Macro, MACROS, MacroType,
-    predicate::read_macro, parse::{parse_html_tag, parse_box_arguments},
-    super::math::render_math
+    Macro, MacroType, get_macro,
+    predicate::{read_macro, read_raw_macro_argument}, parse::{parse_html_tag, parse_box_arguments}
 };
 use crate::RenderOption;
 use crate::ast::{doc_data::DocData, line::Line, node::Node};
+use crate::container::admonition::{Admonition, AdmonitionType};
 use crate::inline::macros::tooltip::load_tooltip_message;
 use crate::utils::{from_v32, into_v32};
 
@@ -28,13 +28,13 @@ pub enum MultiLineMacroType {
     LineHeight(Vec<u32>),
     Alignment(Vec<u32>),
     Highlight(Vec<u32>),
-    Math(Vec<u32>),
     Tooltip {
         container: Vec<Node>,  // outside
         label: Vec<u32>,  // inside, the actual message, which is `Vec<InlineNode>` will be loaded later
         index: usize
     },
     Sidebar,
+    Admonition(Admonition),
     HTML {
         tag: Vec<u32>,
         class: Vec<u32>,
@@ -48,14 +48,14 @@ impl MultiLineMacroType {
 
         // don't use wildcard character
         match self {
-            MultiLineMacroType::Tooltip { .. } | MultiLineMacroType::Sidebar => true,
+            MultiLineMacroType::Tooltip { .. } | MultiLineMacroType::Sidebar
+            | MultiLineMacroType::Admonition(_) => true,
             MultiLineMacroType::Box{ .. } |
             MultiLineMacroType::Color(_) |
             MultiLineMacroType::Size(_) |
             MultiLineMacroType::LineHeight(_) |
             MultiLineMacroType::Highlight(_) |
             MultiLineMacroType::Alignment(_) |
-            MultiLineMacroType::Math(_) |
             MultiLineMacroType::HTML{ .. } => false
         }
 
@@ -77,6 +77,20 @@ impl MultiLineMacroType {
             MultiLineMacroType::Tooltip{ container, .. } => {
                 *container = nodes;
             }
+            MultiLineMacroType::Admonition(admonition) => {
+                admonition.set_inner_nodes(nodes);
+            }
+            _ => {}
+        }
+
+    }
+
+    pub fn parse_inlines(&mut self, doc_data: &mut DocData, render_option: &RenderOption) {
+
+        match self {
+            MultiLineMacroType::Admonition(admonition) => {
+                admonition.parse_inlines(doc_data, render_option);
+            }
             _ => {}
         }
 
@@ -88,7 +102,7 @@ impl MultiLineMacro {
 
     // all the validity checks are done before this function
     // this function assumes that everything is valid
-    pub fn from_line(line: &Line, doc_data: &mut DocData) -> Self {
+    pub fn from_line(line: &Line, doc_data: &mut DocData, render_option: &RenderOption) -> Self {
         let macro_content = read_macro(&line.content, 0).unwrap();
         let macro_arguments = parse_arguments(&macro_content);
         let mut macro_name = get_macro_name(&macro_arguments);
@@ -103,7 +117,7 @@ impl MultiLineMacro {
             name: macro_name,
             macro_type,
             has_closing: _has_closing,  // supposed to be true
-        } = MACROS.get(&macro_name).unwrap();
+        } = get_macro(&macro_name, render_option).unwrap();
 
         #[cfg(test)]
         assert!(_has_closing);
@@ -196,12 +210,31 @@ impl MultiLineMacro {
                 is_closing,
             },
 
-            // it's handled by another ParseState
+            MacroType::Admonition => MultiLineMacro {
+                macro_type: {
+                    let admonition_type = AdmonitionType::from_name(&macro_name).unwrap();
+
+                    if is_closing {
+                        MultiLineMacroType::Admonition(Admonition::new(admonition_type))
+                    }
+
+                    else {
+                        // into_v32("title") -> [116, 105, 116, 108, 101]
+                        let title = read_raw_macro_argument(&line.content, 0, &[116, 105, 116, 108, 101]);
+
+                        MultiLineMacroType::Admonition(Admonition::from_macro_arguments(admonition_type, &macro_arguments, title))
+                    }
+                },
+                is_closing,
+            },
+
+            // it's handled by another ParseState, and becomes a `MathBlock`
             MacroType::Math => unreachable!(),
 
             // macros that do not have closing tags
             MacroType::Toc | MacroType::Blank | MacroType::Br
-            | MacroType::Char | MacroType::Icon => unreachable!("{macro_type:?}")
+            | MacroType::Char | MacroType::Icon
+            | MacroType::Id | MacroType::Ref => unreachable!("{macro_type:?}")
         }
 
     }
@@ -223,11 +256,11 @@ impl MultiLineMacro {
                 | MultiLineMacroType::Alignment(_) | MultiLineMacroType::Highlight(_)
                 | MultiLineMacroType::Tooltip { .. } => vec![60, 47, 100, 105, 118, 62],  // into_v32("</div>")
 
-                // it doesn't need any closing tag because `render_math` generates both opening and closing tags
-                MultiLineMacroType::Math(_) => vec![],
-
                 // this variant should be completely ignored by this function!
-                MultiLineMacroType::Sidebar => vec![]
+                MultiLineMacroType::Sidebar => vec![],
+
+                // `Admonition::to_html` generates both opening and closing tags
+                MultiLineMacroType::Admonition(_) => vec![]
             }
 
         }
@@ -332,12 +365,12 @@ impl MultiLineMacro {
 
                     result.concat()
                 },
-                MultiLineMacroType::Math(math) => {
-                    render_math(math)
-                },
                 MultiLineMacroType::Sidebar => {
                     // handled by AST::to_html
                     vec![]
+                },
+                MultiLineMacroType::Admonition(admonition) => {
+                    admonition.to_html(toc_rendered, render_option, doc_data)
                 }
             }
 
@@ -1,5 +1,8 @@
-use super::{Macro, MacroType, character::{DIRECT_MAPPINGS, INDIRECT_MAPPINGS}, get_macro_name, tooltip::load_tooltip_message};
+use super::{Macro, MacroType, character::{DIRECT_MAPPINGS, INDIRECT_MAPPINGS}, get_macro_name, predicate::read_raw_macro_argument, tooltip::load_tooltip_message};
+use crate::container::admonition::AdmonitionType;
+use crate::container::icon::{Icon, render_icon};
 use crate::inline::{DecorationType, InlineNode, InlineMacro};
+use crate::inline::math::Math;
 use crate::render::render_option::RenderOption;
 use crate::utils::{into_v32, to_int};
 use crate::ast::doc_data::DocData;
@@ -8,6 +11,7 @@ impl Macro {
 
     // all the validity checks are done before this function
     // this function assumes that everything is valid
+    // `content` is the content between the macro and its closing, or the macro itself if it doesn't have a closing
     pub fn parse(
         &self,
         arguments: &Vec<Vec<Vec<u32>>>,
@@ -122,9 +126,25 @@ impl Macro {
                 result
             },
 
-            MacroType::Math => InlineNode::Decoration {
-                deco_type: DecorationType::Macro(InlineMacro::Math(content.to_vec())),
-                content: vec![]
+            MacroType::Math => {
+                let latex = arguments[1..].iter().any(|argument| argument[0] == into_v32("latex"));
+
+                // the errors are reported here, because `to_html` doesn't have `doc_data`
+                doc_data.add_math_errors(&if latex {
+                    Math::from_latex(content)
+                } else {
+                    Math::from_mdxt(content)
+                });
+
+                InlineNode::Decoration {
+                    deco_type: DecorationType::Macro(InlineMacro::Math {
+                        content: content.to_vec(),
+                        latex,
+                        speech: render_option.math_speech,
+                        error_span: render_option.math_error_span
+                    }),
+                    content: vec![]
+                }
             },
 
             MacroType::HTML => InlineNode::Decoration {
@@ -138,18 +158,45 @@ impl Macro {
 
             MacroType::Icon => InlineNode::Decoration {
                 deco_type: DecorationType::Macro({
-                    let name = arguments[0][1].clone();
-                    let size = if arguments.len() > 1 {
-                        to_int(&arguments[1][1]).unwrap().min(u32::MAX)
-                    } else {
-                        32
-                    };
+                    // into_v32("title") -> [116, 105, 116, 108, 101]
+                    let icon = Icon::from_macro_arguments(arguments, read_raw_macro_argument(content, 0, &[116, 105, 116, 108, 101]));
+
+                    // `is_valid` checked that the icons exist
+                    let svg = render_icon(&icon, render_option, doc_data).unwrap();
 
-                    InlineMacro::Icon { name, size }
+                    InlineMacro::Icon {
+                        #[cfg(test)] icon,
+                        svg
+                    }
                 }),
                 content: vec![]
             },
 
+            MacroType::Id => {
+                doc_data.add_inline_label(&arguments[0][1]);
+
+                InlineNode::Decoration {
+                    deco_type: DecorationType::Macro(InlineMacro::Id(arguments[0][1].clone())),
+                    content: vec![]
+                }
+            },
+
+            MacroType::Ref => InlineNode::Decoration {
+                deco_type: DecorationType::Macro(InlineMacro::Ref(arguments[0][1].clone())),
+                content: vec![]
+            },
+
+            // an admonition inside a paragraph ignores its arguments
+            MacroType::Admonition => InlineNode::Decoration {
+                deco_type: DecorationType::Macro({
+                    let admonition_type = AdmonitionType::from_name(&get_macro_name(arguments)).unwrap();
+                    let icon = render_icon(&Icon::new(admonition_type.default_icon(), 16), render_option, doc_data).unwrap_or_default();
+
+                    InlineMacro::Admonition { admonition_type, icon }
+                }),
+                content: InlineNode::from_mdxt(content, doc_data, render_option).to_vec()
+            },
+
             // an inline sidebar is ignored
             MacroType::Sidebar => InlineNode::Decoration {
                 deco_type: DecorationType::None,
@@ -1,7 +1,7 @@
-use super::{normalize_macro, parse_arguments, get_macro_name, MACROS};
+use super::{normalize_macro, parse_arguments, get_macro_name, get_macro};
 use crate::inline::InlineNode;
-use crate::utils::{get_bracket_end_index, remove_whitespaces};
-use crate::render::render_option::RenderOption;
+use crate::utils::{from_v32, get_bracket_end_index, remove_whitespaces, strip_whitespaces};
+use crate::render::{diagnostic::Diagnostic, render_option::RenderOption};
 use crate::ast::doc_data::DocData;
 
 // "[[div, id = def]]" -> "div,id=def"
@@ -36,6 +36,27 @@ pub fn read_macro(content: &[u32], index: usize) -> Option<Vec<u32>> {
 
 }
 
+// "[[note, title = Read This]]", "title" -> "Read This"
+// unlike `read_macro`, it preserves the cases and the spaces of the value
+pub fn read_raw_macro_argument(content: &[u32], index: usize, key: &[u32]) -> Option<Vec<u32>> {
+    let end_index = get_bracket_end_index(content, index)?;
+
+    for argument in content[index + 2..end_index - 1].split(|c| *c == ',' as u32) {
+        let mut key_and_value = argument.splitn(2, |c| *c == '=' as u32);
+
+        if normalize_macro(key_and_value.next().unwrap()) == key {
+
+            if let Some(value) = key_and_value.next() {
+                return Some(strip_whitespaces(value));
+            }
+
+        }
+
+    }
+
+    None
+}
+
 pub fn check_and_parse_macro_inline(
     content: &[u32],
     index: usize,
@@ -49,8 +70,8 @@ pub fn check_and_parse_macro_inline(
             let macro_name = get_macro_name(&macro_arguments);
             let macro_end_index = get_bracket_end_index(content, index).unwrap();
 
-            match MACROS.get(&macro_name) {
-                Some(macro_) if macro_.is_valid(&macro_arguments) => {
+            match get_macro(&macro_name, render_option) {
+                Some(macro_) if macro_.is_valid(&macro_arguments, render_option) => {
 
                     if !macro_.has_closing {
 
@@ -58,7 +79,7 @@ pub fn check_and_parse_macro_inline(
                             doc_data.has_toc = true;
                         }
 
-                        Some((macro_.parse(&macro_arguments, &vec![], doc_data, render_option), macro_end_index))
+                        Some((macro_.parse(&macro_arguments, &content[index..(macro_end_index + 1)], doc_data, render_option), macro_end_index))
                     }
 
                     else if doc_data.tooltip_enabled > 0 && (
@@ -119,7 +140,15 @@ pub fn check_and_parse_macro_inline(
                     }
 
                 },
-                _ => None
+                Some(macro_) => {
+
+                    if let Some(name) = macro_.get_unknown_icon(&macro_arguments, render_option) {
+                        doc_data.diagnostics.push(Diagnostic::UnknownIcon { name: from_v32(&name) });
+                    }
+
+                    None
+                },
+                None => None
             }
 
         },
@@ -133,6 +162,7 @@ pub fn is_valid_macro_character(chr: &u32) -> bool {
     || 'a' as u32 <= *chr && *chr <= 'z' as u32
     || 'A' as u32 <= *chr && *chr <= 'Z' as u32
     || ' ' as u32 == *chr || '_' as u32 == *chr
+    || '-' as u32 == *chr || '#' as u32 == *chr  // `[[icon = star, color = #ff8800]]`
     || ',' as u32 == *chr || '=' as u32 == *chr
 }
 
@@ -119,4 +119,51 @@ fn render_to_html() {
     let mut f = File::create("character_test.html").unwrap();
     f.write_all(html.as_bytes()).unwrap();
 
-}
\ No newline at end of file
+}
+
+#[test]
+fn custom_color_test() {
+    use crate::{render_to_html, Color};
+    use crate::utils::remove_whitespaces;
+
+    let mut render_option = RenderOption::default();
+    render_option.set_colors(vec![Color::new("Brand Orange", 255, 136, 0)]);
+
+    let md = "[[brand_orange]]Brand[[/brandorange]] [[highlight = brand orange]]Highlight[[/highlight]] [[icon = github, color = brandorange, size = 8]]
+
+[[brandorange]]
+
+[[box]]
+
+In a box
+
+[[/box]]
+
+[[/brandorange]]
+
+[[nope]]Nope[[/nope]]";
+
+    let result = render_to_html(md, render_option);
+
+    assert_eq!(
+        remove_whitespaces(&into_v32(&result.content)),
+        remove_whitespaces(&into_v32(&render_to_html_with_default_options(
+            "[[red]]Brand[[/red]] [[highlight = red]]Highlight[[/highlight]] [[icon = github, color = #ff8800, size = 8]]
+
+[[red]]
+
+[[box]]
+
+In a box
+
+[[/box]]
+
+[[/red]]
+
+[[nope]]Nope[[/nope]]"
+        ).replace("red", "brandorange")))
+    );
+
+    // without the palette, it's not a macro
+    assert!(render_to_html_with_default_options(md).contains("[[highlight = brand orange]]"));
+}
@@ -1,27 +1,40 @@
-use crate::ast::doc_data::DocData;
+use crate::ast::{doc_data::DocData, node::Node};
 use crate::inline::{footnote::Footnote, InlineNode};
 use crate::RenderOption;
 use crate::utils::{from_v32, into_v32};
 
 // Error message to show when referencing a tooltip that doesn't exist
 pub fn dummy_tooltip(label: &[u32]) -> Footnote {
-    Footnote {
-        index: 0,
-        inverse_index: vec![],
-        content: InlineNode::Raw(into_v32(&format!("Error! Undefined tooltip label: {}", from_v32(label))))
-    }
+    Footnote::new(
+        0,
+        vec![Node::Paragraph {
+            content: InlineNode::Raw(into_v32(&format!("Error! Undefined tooltip label: {}", from_v32(label))))
+        }],
+        true
+    )
 }
 
 pub fn load_tooltip_message(label: &[u32], doc_data: &mut DocData, render_option: &RenderOption) -> Vec<InlineNode> {
     let label_key = vec![vec![94] /* = into_v32("^") */, label.to_vec()].concat();
-    let mut message = match doc_data.footnote_references.get(&label_key) {
-        Some(f) => f.clone(),
-        None => dummy_tooltip(&label),  // print error message: "Error! Undefined tooltip label: {}"
+    let (mut message, has_more_blocks) = match doc_data.footnote_references.get(&label_key) {
+        Some(f) => f.tooltip_preview(),
+        None => dummy_tooltip(&label).tooltip_preview(),  // print error message: "Error! Undefined tooltip label: {}"
     };
 
-    message.content.parse_raw(doc_data, render_option);
+    // footnotes may reference each other
+    // the message is parsed with a copy of `doc_data`, and the references inside the message don't have their own tooltips
+    let mut doc_data_cloned = doc_data.clone();
+    doc_data_cloned.tooltip_enabled += 1;
+    doc_data_cloned.in_tooltip_message = true;
+    message.parse_raw(&mut doc_data_cloned, render_option);
+
+    let mut result = message.to_vec();
+
+    if has_more_blocks {
+        result.push(InlineNode::Raw(vec![32, 8230]));  // into_v32(" …")
+    }
 
-    message.content.clone().to_vec()
+    result
 }
 
 /// You can also write your own version.
@@ -54,29 +67,34 @@ pub fn load_tooltip_message(label: &[u32], doc_data: &mut DocData, render_option
 /// }
 /// ```
 pub fn tooltip_javascript() -> String {
-"let tooltips = document.querySelectorAll(\".tooltip-container\");
+    prefixed_tooltip_javascript("")
+}
+
+// `RenderOption.class_prefix` goes to the class names
+pub(crate) fn prefixed_tooltip_javascript(class_prefix: &str) -> String {
+format!("let tooltips = document.querySelectorAll(\".{class_prefix}tooltip-container\");
 
-for (let i = 0; i < tooltips.length; i++) {
+for (let i = 0; i < tooltips.length; i++) {{
     let child = document.getElementById(\"tooltip-message-\" + i);
 
-    document.getElementById(\"tooltip-container-\" + i).addEventListener(\"mousemove\", e => {
+    document.getElementById(\"tooltip-container-\" + i).addEventListener(\"mousemove\", e => {{
 
-        if (e.clientX + child.clientWidth > window.innerWidth) {
+        if (e.clientX + child.clientWidth > window.innerWidth) {{
             child.style.left = e.clientX - child.clientWidth + \"px\";
-        }
+        }}
 
-        else {
+        else {{
             child.style.left = e.clientX + \"px\";
-        }
+        }}
 
-        if (e.clientY < child.clientHeight + 8) {
+        if (e.clientY < child.clientHeight + 8) {{
             child.style.top = e.clientY + 8 + \"px\";
-        }
+        }}
 
-        else {
+        else {{
             child.style.top = (e.clientY - child.clientHeight - 8) + \"px\";
-        }
+        }}
 
-    });
-}".to_string()
+    }});
+}}")
 }
\ No newline at end of file
@@ -1,18 +1,20 @@
 use super::{character::CHAR_NAMES, Macro, MacroType};
-use crate::color::COLOR_NAMES;
-use crate::container::icon::ICONS;
+use crate::color::{parse_color, COLOR_NAMES};
+use crate::render::render_option::RenderOption;
 use crate::utils::{into_v32, to_int};
 
 impl Macro {
 
-    pub fn is_valid(&self, arguments: &Vec<Vec<Vec<u32>>>) -> bool {
+    pub fn is_valid(&self, arguments: &Vec<Vec<Vec<u32>>>, render_option: &RenderOption) -> bool {
 
         match self.macro_type {
 
             // no args, only a name
             MacroType::Color | MacroType::Size
-            | MacroType::Alignment | MacroType::Toc
-            | MacroType::Math => arguments.len() == 1 && arguments[0].len() == 1,
+            | MacroType::Alignment | MacroType::Toc => arguments.len() == 1 && arguments[0].len() == 1,
+
+            // the arguments are only for block math
+            MacroType::Math => arguments[0].len() == 1 && is_valid_math_arguments(arguments),
 
             MacroType::Br | MacroType::Blank => {
                 arguments.len() == 1 && (
@@ -23,6 +25,8 @@ impl Macro {
 
             MacroType::Box => arguments[0].len() == 1 && is_valid_box_arguments(&arguments),
 
+            MacroType::Admonition => arguments[0].len() == 1 && is_valid_admonition_arguments(&arguments, render_option),
+
             MacroType::Char => arguments.len() == 1 && arguments[0].len() == 2 && (
                 to_int(&arguments[0][1]).is_some() || CHAR_NAMES.contains(&arguments[0][1])
             ),
@@ -64,19 +68,40 @@ impl Macro {
             // -> it cannot check that
             MacroType::Tooltip => arguments.len() == 1 && arguments[0].len() == 2,
 
-            MacroType::Highlight => arguments.len() == 1 && arguments[0].len() == 2 && COLOR_NAMES.contains(&arguments[0][1]),
+            // `[[ref = aabb]]` may refer to a label that's defined later, so it's checked after the whole document is parsed
+            MacroType::Id | MacroType::Ref => arguments.len() == 1 && arguments[0].len() == 2 && !arguments[0][1].is_empty(),
 
-            MacroType::Icon => arguments[0].len() == 2 && ICONS.contains_key(&arguments[0][1]) && (
-                arguments.len() == 1  // no size
-                || arguments.len() == 2 && arguments[1].len() == 2 && arguments[1][0] == into_v32("size") && match to_int(&arguments[1][1]) {
-                    Some(n) if n < u32::MAX => true,
-                    _ => false
-                }
-            )
+            MacroType::Highlight => arguments.len() == 1 && arguments[0].len() == 2 && (
+                COLOR_NAMES.contains(&arguments[0][1]) || render_option.has_color(&arguments[0][1])
+            ),
+
+            MacroType::Icon => arguments[0].len() == 2 && render_option.icons.contains(&arguments[0][1]) && is_valid_icon_arguments(arguments, render_option)
         }
 
     }
 
+    // `[[icon = name]]`, `[[icon = file, badge = name]]` and `[[note, icon = name]]` whose icon is not found
+    // they're invalid macros, so they're rendered as plain texts, but the name is reported
+    pub fn get_unknown_icon(&self, arguments: &[Vec<Vec<u32>>], render_option: &RenderOption) -> Option<Vec<u32>> {
+        let icon_keys: &[&[u32]] = match self.macro_type {
+            MacroType::Icon => &[
+                &[105, 99, 111, 110],                  // into_v32("icon")
+                &[98, 97, 100, 103, 101],              // into_v32("badge")
+                &[111, 118, 101, 114, 108, 97, 121],   // into_v32("overlay")
+            ],
+            MacroType::Admonition => &[&[105, 99, 111, 110]],  // into_v32("icon")
+            _ => &[]
+        };
+
+        arguments.iter().filter(
+            |argument| argument.len() == 2 && icon_keys.contains(&argument[0].as_slice())
+        ).map(
+            |argument| &argument[1]
+        ).find(
+            |name| !name.is_empty() && !render_option.icons.contains(name)
+        ).cloned()
+    }
+
 }
 
 fn is_valid_box_arguments(arguments: &Vec<Vec<Vec<u32>>>) -> bool {
@@ -117,3 +142,91 @@ fn is_valid_box_arguments(arguments: &Vec<Vec<Vec<u32>>>) -> bool {
 
     result
 }
+
+// `[[note, title = Read This First, icon = lock, collapsible, default = hidden]]`
+fn is_valid_admonition_arguments(arguments: &Vec<Vec<Vec<u32>>>, render_option: &RenderOption) -> bool {
+
+    for argument in arguments[1..].iter() {
+
+        if (
+            argument[0] == [99, 111, 108, 108, 97, 112, 115, 105, 98, 108, 101] ||  // into_v32("collapsible")
+            argument[0] == [110, 111, 105, 99, 111, 110]  // into_v32("noicon")
+        ) && argument.len() == 1 {
+            //
+        }
+
+        // into_v32("title")
+        else if argument[0] == [116, 105, 116, 108, 101] && argument.len() == 2 && !argument[1].is_empty() {
+            //
+        }
+
+        // into_v32("icon")
+        else if argument[0] == [105, 99, 111, 110] && argument.len() == 2 && render_option.icons.contains(&argument[1]) {
+            //
+        }
+
+        // into_v32("default")
+        else if argument[0] == [100, 101, 102, 97, 117, 108, 116] && argument.len() == 2 && (
+            argument[1] == [104, 105, 100, 100, 101, 110] ||  // into_v32("hidden")
+            argument[1] == [115, 104, 111, 119, 110]  // into_v32("shown")
+        ) {
+            //
+        }
+
+        else {
+            return false;
+        }
+
+    }
+
+    true
+}
+
+// `size = 24`, `badge = plus`, `overlay = lock`, `color = red`, `title = GitHub`, `decorative`, `rotate = 90` and `flip = horizontal`, each at most once
+fn is_valid_icon_arguments(arguments: &[Vec<Vec<u32>>], render_option: &RenderOption) -> bool {
+    let mut keys = vec![];
+
+    for argument in arguments[1..].iter() {
+
+        if keys.contains(&argument[0]) {
+            return false;
+        }
+
+        let is_valid = match (argument[0].as_slice(), argument.get(1)) {
+            ([115, 105, 122, 101], Some(size)) => matches!(to_int(size), Some(n) if n < u32::MAX),  // into_v32("size")
+            ([98, 97, 100, 103, 101], Some(name))  // into_v32("badge")
+            | ([111, 118, 101, 114, 108, 97, 121], Some(name)) => render_option.icons.contains(name),  // into_v32("overlay")
+            ([99, 111, 108, 111, 114], Some(color)) => parse_color(color, &render_option.colors).is_some(),  // into_v32("color")
+            ([116, 105, 116, 108, 101], Some(title)) => !title.is_empty(),  // into_v32("title")
+            ([100, 101, 99, 111, 114, 97, 116, 105, 118, 101], None) => true,  // into_v32("decorative")
+            ([114, 111, 116, 97, 116, 101], Some(degree)) => matches!(to_int(degree), Some(n) if n < u32::MAX),  // into_v32("rotate")
+            ([102, 108, 105, 112], Some(direction)) => [  // into_v32("flip")
+                vec![104, 111, 114, 105, 122, 111, 110, 116, 97, 108],  // into_v32("horizontal")
+                vec![118, 101, 114, 116, 105, 99, 97, 108],             // into_v32("vertical")
+                vec![98, 111, 116, 104],                                // into_v32("both")
+            ].contains(direction),
+            _ => false
+        } && argument.len() <= 2;
+
+        if !is_valid {
+            return false;
+        }
+
+        keys.push(argument[0].clone());
+    }
+
+    // a decorative icon is hidden from the screen readers, so it cannot have a title
+    // into_v32("decorative"), into_v32("title")
+    !(keys.contains(&vec![100, 101, 99, 111, 114, 97, 116, 105, 118, 101]) && keys.contains(&vec![116, 105, 116, 108, 101]))
+}
+
+// `number`, `nonumber` and `id = ...`
+fn is_valid_math_arguments(arguments: &[Vec<Vec<u32>>]) -> bool {
+    arguments[1..].iter().all(
+        |argument| match argument.len() {
+            1 => argument[0] == into_v32("number") || argument[0] == into_v32("nonumber") || argument[0] == into_v32("latex"),
+            2 => argument[0] == into_v32("id") && !argument[1].is_empty(),
+            _ => false
+        }
+    )
+}
@@ -0,0 +1,276 @@
+use super::entity::Entity;
+use super::is_whitespace;
+use crate::utils::{get_curly_brace_end_index, is_alphabet, is_numeric, lowercase};
+
+/*
+A subset of mhchem: `ce{2H2 + O2 -> 2H2O}`, `ce{SO4^2-}`, `ce{CuSO4*5H2O}`
+
+- Elements, states and the other letters are upright: `<mtext>`.
+- Digits right after an element or a `)` are subscripts: `H2O`, `Ca(OH)2`. Digits at the beginning of a term are coefficients: `2H2O`.
+- `^2-`, `^{2-}` and a trailing `+` or `-` are charges: `SO4^2-`, `Na+`.
+- `_{...}` is an explicit subscript.
+- `*` and `.` are the dots of addition compounds: `CuSO4*5H2O`.
+- `->`, `<-`, `<->` and `<=>` are arrows, and `->[heat]` or `->[above][below]` writes on them.
+- `^` and `v` between spaces are a gas and a precipitate: `ce{CaCO3 v}`.
+
+Terms are separated by whitespaces, so `A + B` is a sum, but `A+` is a cation.
+*/
+
+pub fn parse_chemistry(content: &[u32]) -> Vec<Entity> {
+    let mut result = vec![];
+
+    for term in split_terms(content).into_iter() {
+
+        if let Some(arrow) = parse_arrow(term) {
+            result.push(arrow);
+        }
+
+        else {
+
+            match term {
+                [43] => { result.push(Entity::new_operator(vec![43])); },  // '+'
+                [61] => { result.push(Entity::new_operator(vec![61])); },  // '='
+                [94] => { result.push(Entity::new_operator(vec![8593])); },  // '^' -> '↑'
+                [118] => { result.push(Entity::new_operator(vec![8595])); },  // 'v' -> '↓'
+                _ => { result.extend(parse_term(term)); }
+            }
+
+        }
+
+    }
+
+    result
+}
+
+// whitespaces inside `[...]` and `{...}` don't split terms: `->[500 K]`
+fn split_terms(content: &[u32]) -> Vec<&[u32]> {
+    let mut result = vec![];
+    let mut depth = 0;
+    let mut last_index = 0;
+
+    for (index, c) in content.iter().enumerate() {
+
+        if *c == '[' as u32 || *c == '{' as u32 {
+            depth += 1;
+        }
+
+        else if (*c == ']' as u32 || *c == '}' as u32) && depth > 0 {
+            depth -= 1;
+        }
+
+        else if is_whitespace(c) && depth == 0 {
+            result.push(&content[last_index..index]);
+            last_index = index + 1;
+        }
+
+    }
+
+    result.push(&content[last_index..]);
+
+    result.into_iter().filter(|term| !term.is_empty()).collect()
+}
+
+// `->`, `->[heat]`, `<=>[above][below]`
+fn parse_arrow(term: &[u32]) -> Option<Entity> {
+    let arrow_end_index = term.iter().position(|c| *c == '[' as u32).unwrap_or(term.len());
+
+    let arrow = match &term[..arrow_end_index] {
+        [45, 62] => 8594,  // `->` -> '→'
+        [60, 45] => 8592,  // `<-` -> '←'
+        [60, 45, 62] => 8596,  // `<->` -> '↔'
+        [60, 61, 62] => 8652,  // `<=>` -> '⇌'
+        _ => { return None; }
+    };
+
+    let mut texts = vec![];
+    let mut index = arrow_end_index;
+
+    while index < term.len() && term[index] == '[' as u32 {
+
+        match term[index..].iter().position(|c| *c == ']' as u32) {
+            Some(end_index) => {
+                texts.push(parse_term(&term[(index + 1)..(index + end_index)]));
+                index += end_index + 1;
+            },
+            None => { return None; }
+        }
+
+    }
+
+    if index < term.len() {
+        return None;
+    }
+
+    let arrow = Entity::new_operator(vec![arrow]);
+
+    match texts.len() {
+        0 => Some(arrow),
+        1 => Some(Entity::new_underover(vec![arrow], vec![], texts.remove(0), false)),
+        _ => {
+            let above = texts.remove(0);
+
+            Some(Entity::new_underover(vec![arrow], texts.remove(0), above, false))
+        }
+    }
+
+}
+
+fn parse_term(term: &[u32]) -> Vec<Entity> {
+    let mut result = vec![];
+    let mut index = 0;
+
+    // a coefficient: `2H2O`, `0.5O2`
+    while index < term.len() && (is_numeric(&term[index]) || (index > 0 && term[index] == '.' as u32)) {
+        index += 1;
+    }
+
+    if index > 0 {
+        result.push(Entity::new_number(term[..index].to_vec()));
+    }
+
+    let mut base = vec![];
+    let mut sub = vec![];
+    let mut sup = vec![];
+
+    while index < term.len() {
+        let c = term[index];
+
+        // an element: `H`, `Na`, or the letters of a state: `aq`
+        // `CO` is two elements, and `Co` is one
+        if is_alphabet(&c) {
+            flush(&mut result, &mut base, &mut sub, &mut sup);
+            let mut end_index = index + 1;
+
+            while end_index < term.len() && is_lowercase(&term[end_index]) {
+                end_index += 1;
+            }
+
+            base.push(Entity::RawString(term[index..end_index].to_vec()));
+            index = end_index;
+        }
+
+        else if is_numeric(&c) {
+            let end_index = term[index..].iter().position(|c| !is_numeric(c)).map(|i| index + i).unwrap_or(term.len());
+            let number = Entity::new_number(term[index..end_index].to_vec());
+
+            if !base.is_empty() && sup.is_empty() {
+                sub.push(number);
+            }
+
+            else {
+                flush(&mut result, &mut base, &mut sub, &mut sup);
+                result.push(number);
+            }
+
+            index = end_index;
+        }
+
+        // `_{2}`
+        else if c == '_' as u32 {
+            let (argument, end_index) = read_script(term, index + 1);
+            sub.extend(parse_term(&argument));
+            index = end_index;
+        }
+
+        // `^2-`, `^{2-}`
+        else if c == '^' as u32 {
+            let (argument, end_index) = read_script(term, index + 1);
+            sup.push(charge(&argument));
+            index = end_index;
+        }
+
+        // `Na+`, `Cl-`: only at the end of a term, or before a state: `Na+(aq)`
+        else if (c == '+' as u32 || c == '-' as u32)
+            && !base.is_empty()
+            && (index + 1 == term.len() || term[index + 1] == '(' as u32)
+        {
+            sup.push(charge(&[c]));
+            index += 1;
+        }
+
+        // `->[500 K]`
+        else if is_whitespace(&c) {
+            flush(&mut result, &mut base, &mut sub, &mut sup);
+            result.push(Entity::Space(1));
+            index += 1;
+        }
+
+        // `CuSO4*5H2O`: `5` is a coefficient, because it doesn't have an element before it
+        else if c == '*' as u32 || c == '.' as u32 {
+            flush(&mut result, &mut base, &mut sub, &mut sup);
+            result.push(Entity::new_operator(vec![8901]));  // '⋅'
+            index += 1;
+        }
+
+        // `Ca(OH)2`: the subscript is attached to `)`
+        else if c == ')' as u32 || c == ']' as u32 {
+            flush(&mut result, &mut base, &mut sub, &mut sup);
+            base.push(Entity::new_operator(vec![c]));
+            index += 1;
+        }
+
+        else {
+            flush(&mut result, &mut base, &mut sub, &mut sup);
+            result.push(Entity::new_operator(vec![c]));
+            index += 1;
+        }
+
+    }
+
+    flush(&mut result, &mut base, &mut sub, &mut sup);
+
+    result
+}
+
+fn flush(result: &mut Vec<Entity>, base: &mut Vec<Entity>, sub: &mut Vec<Entity>, sup: &mut Vec<Entity>) {
+
+    if sub.is_empty() && sup.is_empty() {
+        result.append(base);
+    }
+
+    else {
+        result.push(Entity::new_script(
+            std::mem::take(base),
+            vec![],
+            std::mem::take(sup),
+            vec![],
+            std::mem::take(sub)
+        ));
+    }
+
+}
+
+// `{2-}` or `2-`: (argument, end_index)
+fn read_script(term: &[u32], index: usize) -> (Vec<u32>, usize) {
+
+    if term.get(index) == Some(&('{' as u32)) {
+
+        if let Some(end_index) = get_curly_brace_end_index(term, index) {
+            return (term[(index + 1)..end_index].to_vec(), end_index + 1);
+        }
+
+    }
+
+    let mut end_index = index;
+
+    while end_index < term.len() && is_numeric(&term[end_index]) {
+        end_index += 1;
+    }
+
+    if end_index < term.len() && (term[end_index] == '+' as u32 || term[end_index] == '-' as u32) {
+        end_index += 1;
+    }
+
+    (term[index..end_index].to_vec(), end_index)
+}
+
+// `2-` -> `2−`
+fn charge(charge: &[u32]) -> Entity {
+    Entity::RawString(charge.iter().map(
+        |c| if *c == '-' as u32 { 8722 } else { *c }
+    ).collect())
+}
+
+fn is_lowercase(c: &u32) -> bool {
+    is_alphabet(c) && lowercase(c) == *c
+}
@@ -0,0 +1,78 @@
+use super::{Entity, vec_to_math_ml};
+use super::speech::{vec_to_plain_text, vec_to_speech};
+use crate::utils::into_v32;
+
+// `overbrace{a + b}{n}`, `underbrace{a + b}{n}`
+// the label is optional
+#[derive(Clone)]
+pub struct Brace {
+    content: Vec<Entity>,
+    label: Vec<Entity>,
+    over: bool
+}
+
+impl Brace {
+
+    pub fn new(content: Vec<Entity>, label: Vec<Entity>, over: bool) -> Self {
+        Brace { content, label, over }
+    }
+
+    pub fn to_math_ml(&self) -> Vec<u32> {
+        let (tag, accent, brace) = if self.over {
+            ("mover", "accent", 9182)  // '⏞'
+        } else {
+            ("munder", "accentunder", 9183)  // '⏟'
+        };
+
+        let braced = vec![
+            into_v32(&format!("<{tag} {accent}=\"true\">")),
+            vec_to_math_ml(&self.content, true),  // `<mover>` has exactly two children, so `single_element` is true.
+            into_v32(&format!("<mo stretchy=\"true\">&#{brace};</mo></{tag}>")),
+        ].concat();
+
+        if self.label.is_empty() {
+            braced
+        }
+
+        else {
+            vec![
+                into_v32(&format!("<{tag}>")),
+                braced,
+                vec_to_math_ml(&self.label, true),
+                into_v32(&format!("</{tag}>")),
+            ].concat()
+        }
+
+    }
+
+    pub fn to_plain_text(&self) -> String {
+        let name = if self.over { "overbrace" } else { "underbrace" };
+
+        if self.label.is_empty() {
+            format!("{name}({})", vec_to_plain_text(&self.content))
+        }
+
+        else {
+            format!("{name}({}, {})", vec_to_plain_text(&self.content), vec_to_plain_text(&self.label))
+        }
+
+    }
+
+    pub fn to_speech(&self) -> String {
+
+        if self.label.is_empty() {
+            vec_to_speech(&self.content)
+        }
+
+        else {
+            format!(
+                "{}, {} labeled {}",
+                vec_to_speech(&self.content),
+                if self.over { "with an overbrace" } else { "with an underbrace" },
+                vec_to_speech(&self.label)
+            )
+        }
+
+    }
+
+}
@@ -0,0 +1,66 @@
+use super::Entity;
+use super::fenced::fence_to_math_ml;
+use super::speech::{vec_to_plain_text, vec_to_speech};
+use crate::utils::into_v32;
+
+// `cases{{x}{x >= 0}}{{-x}{x < 0}}`
+// the rows are left-aligned, with a stretchy curly brace on the left
+#[derive(Clone)]
+pub struct Cases {
+    rows: Vec<Vec<Vec<Entity>>>
+}
+
+impl Cases {
+
+    pub fn new(rows: Vec<Vec<Vec<Entity>>>) -> Self {
+        Cases { rows }
+    }
+
+    pub fn to_math_ml(&self) -> Vec<u32> {
+        let mut result = vec![
+            vec![60, 109, 114, 111, 119, 62],  // into_v32("<mrow>")
+            fence_to_math_ml(Some(123)),  // '{'
+            into_v32("<mtable columnalign=\"left left\" columnspacing=\"1em\">"),
+        ];
+
+        for row in self.rows.iter() {
+            result.push(vec![60, 109, 116, 114, 62]);  // into_v32("<mtr>")
+
+            for cell in row.iter() {
+                result.push(vec![60, 109, 116, 100, 62]);  // into_v32("<mtd>")
+
+                for entity in cell.iter() {
+                    result.push(entity.to_math_ml());
+                }
+
+                result.push(vec![60, 47, 109, 116, 100, 62]);  // into_v32("</mtd>")
+            }
+
+            result.push(vec![60, 47, 109, 116, 114, 62]);  // into_v32("</mtr>")
+        }
+
+        result.push(vec![60, 47, 109, 116, 97, 98, 108, 101, 62]);  // into_v32("</mtable>")
+        result.push(vec![60, 47, 109, 114, 111, 119, 62]);  // into_v32("</mrow>")
+
+        result.concat()
+    }
+
+    pub fn to_plain_text(&self) -> String {
+        format!(
+            "cases({})",
+            self.rows.iter().map(
+                |row| row.iter().map(|cell| vec_to_plain_text(cell)).collect::<Vec<String>>().join(", ")
+            ).collect::<Vec<String>>().join("; ")
+        )
+    }
+
+    pub fn to_speech(&self) -> String {
+        format!(
+            "piecewise; {}; end piecewise",
+            self.rows.iter().map(
+                |row| row.iter().map(|cell| vec_to_speech(cell)).collect::<Vec<String>>().join(", ")
+            ).collect::<Vec<String>>().join("; ")
+        )
+    }
+
+}
@@ -0,0 +1,87 @@
+use super::{Entity, vec_to_math_ml};
+use super::speech::{character_to_speech, end_of, vec_to_plain_text, vec_to_speech};
+use crate::utils::into_v32;
+
+// `abs{x}`, `norm{x}`, `ceil{x}`, `floor{x}`, `binom{n}{k}`
+// the delimiters stretch to the height of the content
+#[derive(Clone)]
+pub struct Fenced {
+    open: Option<u32>,
+    close: Option<u32>,
+    content: Vec<Entity>
+}
+
+impl Fenced {
+
+    pub fn new(open: Option<u32>, close: Option<u32>, content: Vec<Entity>) -> Self {
+        Fenced { open, close, content }
+    }
+
+    pub fn to_math_ml(&self) -> Vec<u32> {
+        vec![
+            vec![60, 109, 114, 111, 119, 62],  // into_v32("<mrow>")
+            fence_to_math_ml(self.open),
+            vec_to_math_ml(&self.content, false),
+            fence_to_math_ml(self.close),
+            vec![60, 47, 109, 114, 111, 119, 62],  // into_v32("</mrow>")
+        ].concat()
+    }
+
+    // `binom{n}{k}` is read `n choose k`, without the parentheses
+    fn is_binomial(&self) -> bool {
+
+        match &self.content[..] {
+            [Entity::Fraction(fraction)] => fraction.is_binomial() && self.open == Some(40) && self.close == Some(41),
+            _ => false
+        }
+
+    }
+
+    pub fn to_plain_text(&self) -> String {
+
+        if self.is_binomial() {
+            return vec_to_plain_text(&self.content);
+        }
+
+        format!(
+            "{}{}{}",
+            self.open.and_then(char::from_u32).map(String::from).unwrap_or_default(),
+            vec_to_plain_text(&self.content),
+            self.close.and_then(char::from_u32).map(String::from).unwrap_or_default(),
+        )
+    }
+
+    pub fn to_speech(&self) -> String {
+        let content = vec_to_speech(&self.content);
+
+        if self.is_binomial() {
+            return content;
+        }
+
+        let name = match (self.open, self.close) {
+            (Some(124), Some(124)) => "absolute value",
+            (Some(8214), Some(8214)) => "norm",
+            (Some(8970), Some(8971)) => "floor",
+            (Some(8968), Some(8969)) => "ceiling",
+            _ => {
+                return [
+                    self.open.map(character_to_speech).unwrap_or_default(),
+                    content,
+                    self.close.map(character_to_speech).unwrap_or_default(),
+                ].iter().filter(|s| !s.is_empty()).map(|s| s.as_str()).collect::<Vec<&str>>().join(" ");
+            }
+        };
+
+        format!("the {name} of {content}{}", end_of(&self.content, name))
+    }
+
+}
+
+pub fn fence_to_math_ml(fence: Option<u32>) -> Vec<u32> {
+
+    match fence {
+        Some(fence) => into_v32(&format!("<mo fence=\"true\" stretchy=\"true\">&#{fence};</mo>")),
+        None => vec![]
+    }
+
+}
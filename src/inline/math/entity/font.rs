@@ -0,0 +1,128 @@
+use crate::utils::{into_v32, is_numeric};
+
+// `mathbb{R}` -> `ℝ`
+// MathML Core doesn't support `mathvariant` except `normal`, so the characters are mapped to the Mathematical Alphanumeric Symbols of Unicode
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FontVariant {
+    Bold,          // `mathbf`
+    DoubleStruck,  // `mathbb`
+    Script,        // `mathcal`
+    Fraktur,       // `mathfrak`
+}
+
+#[derive(Clone)]
+pub struct Font {
+    variant: FontVariant,
+    content: Vec<u32>
+}
+
+impl FontVariant {
+
+    pub fn from_name(name: &[u32]) -> Option<Self> {
+
+        match name {
+            [109, 97, 116, 104, 98, 102] => Some(FontVariant::Bold),  // into_v32("mathbf")
+            [109, 97, 116, 104, 98, 98] => Some(FontVariant::DoubleStruck),  // into_v32("mathbb")
+            [109, 97, 116, 104, 99, 97, 108] => Some(FontVariant::Script),  // into_v32("mathcal")
+            [109, 97, 116, 104, 102, 114, 97, 107] => Some(FontVariant::Fraktur),  // into_v32("mathfrak")
+            _ => None
+        }
+
+    }
+
+    // (A, a, 0)
+    fn offsets(&self) -> (u32, u32, Option<u32>) {
+
+        match self {
+            FontVariant::Bold => (0x1D400, 0x1D41A, Some(0x1D7CE)),
+            FontVariant::DoubleStruck => (0x1D538, 0x1D552, Some(0x1D7D8)),
+            FontVariant::Script => (0x1D49C, 0x1D4B6, None),
+            FontVariant::Fraktur => (0x1D504, 0x1D51E, None),
+        }
+
+    }
+
+    // some characters were in Unicode before the Mathematical Alphanumeric Symbols, and their code points are reserved
+    fn exception(&self, c: u32) -> Option<u32> {
+        let exceptions: &[(char, u32)] = match self {
+            FontVariant::Bold => &[],
+            FontVariant::DoubleStruck => &[('C', 0x2102), ('H', 0x210D), ('N', 0x2115), ('P', 0x2119), ('Q', 0x211A), ('R', 0x211D), ('Z', 0x2124)],
+            FontVariant::Script => &[
+                ('B', 0x212C), ('E', 0x2130), ('F', 0x2131), ('H', 0x210B), ('I', 0x2110), ('L', 0x2112),
+                ('M', 0x2133), ('R', 0x211B), ('e', 0x212F), ('g', 0x210A), ('o', 0x2134)
+            ],
+            FontVariant::Fraktur => &[('C', 0x212D), ('H', 0x210C), ('I', 0x2111), ('R', 0x211C), ('Z', 0x2128)],
+        };
+
+        exceptions.iter().find(|(from, _)| *from as u32 == c).map(|(_, to)| *to)
+    }
+
+    pub fn map_character(&self, c: u32) -> u32 {
+        let (upper, lower, digit) = self.offsets();
+
+        if let Some(c) = self.exception(c) {
+            c
+        }
+
+        else if 'A' as u32 <= c && c <= 'Z' as u32 {
+            upper + c - 'A' as u32
+        }
+
+        else if 'a' as u32 <= c && c <= 'z' as u32 {
+            lower + c - 'a' as u32
+        }
+
+        else if let Some(digit) = digit.filter(|_| is_numeric(&c)) {
+            digit + c - '0' as u32
+        }
+
+        else {
+            c
+        }
+
+    }
+
+}
+
+impl Font {
+
+    pub fn new(variant: FontVariant, content: Vec<u32>) -> Self {
+        Font { variant, content }
+    }
+
+    pub fn to_math_ml(&self) -> Vec<u32> {
+        let content = self.content.iter().filter(
+            |c| **c != ' ' as u32
+        ).map(
+            |c| format!("&#{};", self.variant.map_character(*c))
+        ).collect::<Vec<String>>().concat();
+
+        into_v32(&format!("<mi>{content}</mi>"))
+    }
+
+    pub fn to_plain_text(&self) -> String {
+        self.content.iter().filter(
+            |c| **c != ' ' as u32
+        ).filter_map(
+            |c| char::from_u32(self.variant.map_character(*c))
+        ).collect()
+    }
+
+    // `mathbb{R}` -> `double-struck R`
+    pub fn to_speech(&self) -> String {
+        let variant = match self.variant {
+            FontVariant::Bold => "bold",
+            FontVariant::DoubleStruck => "double-struck",
+            FontVariant::Script => "script",
+            FontVariant::Fraktur => "fraktur",
+        };
+        let letters = self.content.iter().filter(
+            |c| **c != ' ' as u32
+        ).filter_map(
+            |c| char::from_u32(*c).map(String::from)
+        ).collect::<Vec<String>>().join(" ");
+
+        format!("{variant} {letters}")
+    }
+
+}
@@ -1,4 +1,5 @@
-use super::{Entity, vec_to_math_ml};
+use super::{Entity, count_entity, vec_to_math_ml};
+use super::speech::{group_to_plain_text, vec_to_plain_text, vec_to_speech};
 use crate::utils::into_v32;
 
 #[derive(Clone)]
@@ -36,4 +37,37 @@ impl Fraction {
         ].concat()
     }
 
+    // `binom{n}{k}` and `bincoeff{n}{k}`
+    pub fn is_binomial(&self) -> bool {
+        self.no_line
+    }
+
+    pub fn to_plain_text(&self) -> String {
+
+        if self.no_line {
+            format!("binom({}, {})", vec_to_plain_text(&self.numer), vec_to_plain_text(&self.denom))
+        }
+
+        else {
+            format!("{}/{}", group_to_plain_text(&self.numer), group_to_plain_text(&self.denom))
+        }
+
+    }
+
+    pub fn to_speech(&self) -> String {
+
+        if self.no_line {
+            format!("{} choose {}", vec_to_speech(&self.numer), vec_to_speech(&self.denom))
+        }
+
+        else if count_entity(&self.numer) > 1 || count_entity(&self.denom) > 1 {
+            format!("the fraction {} over {} end fraction", vec_to_speech(&self.numer), vec_to_speech(&self.denom))
+        }
+
+        else {
+            format!("{} over {}", vec_to_speech(&self.numer), vec_to_speech(&self.denom))
+        }
+
+    }
+
 }
\ No newline at end of file
@@ -1,20 +1,47 @@
 use super::super::{md_to_math, parse::get_arguments};
 use super::Entity;
-use crate::utils::from_v32;
+use super::speech::{vec_to_plain_text, vec_to_speech};
+use crate::utils::{from_v32, into_v32};
 
 #[derive(Clone)]
 pub struct Matrix {
     elements: Vec<Vec<Vec<Entity>>>,
+
+    // rows of an aligned equation: the columns are aligned right and left alternately, like `align` of LaTeX
+    aligned: bool,
 }
 
 impl Matrix {
     pub fn new(elements: Vec<Vec<Vec<Entity>>>) -> Self {
-        Matrix { elements }
+        Matrix { elements, aligned: false }
+    }
+
+    pub fn new_aligned(elements: Vec<Vec<Vec<Entity>>>) -> Self {
+        Matrix { elements, aligned: true }
     }
 
     pub fn to_math_ml(&self) -> Vec<u32> {
         let mut result = vec![];
-        result.push(vec![60, 109, 116, 97, 98, 108, 101, 62]);  // into_v32("<mtable>")
+
+        if self.aligned {
+            let cols = self.elements.iter().map(|row| row.len()).max().unwrap_or(0);
+            let column_align = (0..cols).map(|i| if i % 2 == 0 { "right" } else { "left" }).collect::<Vec<&str>>().join(" ");
+            let column_spacing = (1..cols).map(|i| if i % 2 == 1 { "0em" } else { "2em" }).collect::<Vec<&str>>().join(" ");
+
+            // a single column doesn't have spacings
+            if column_spacing.is_empty() {
+                result.push(into_v32(&format!("<mtable displaystyle=\"true\" columnalign=\"{column_align}\">")));
+            }
+
+            else {
+                result.push(into_v32(&format!("<mtable displaystyle=\"true\" columnalign=\"{column_align}\" columnspacing=\"{column_spacing}\">")));
+            }
+
+        }
+
+        else {
+            result.push(vec![60, 109, 116, 97, 98, 108, 101, 62]);  // into_v32("<mtable>")
+        }
 
         for row in self.elements.iter() {
             result.push(vec![60, 109, 116, 114, 62]);  // into_v32("<mtr>")
@@ -36,27 +63,78 @@ impl Matrix {
 
         result.concat()
     }
+
+    // `[1, 2; 3, 4]`
+    // the cells of an aligned equation are not separated: `a = b; = c`
+    pub fn to_plain_text(&self) -> String {
+        let separator = if self.aligned { " " } else { ", " };
+        let rows = self.elements.iter().map(
+            |row| row.iter().map(|cell| vec_to_plain_text(cell)).collect::<Vec<String>>().join(separator)
+        ).collect::<Vec<String>>().join("; ");
+
+        if self.aligned {
+            rows
+        }
+
+        else {
+            format!("[{rows}]")
+        }
+
+    }
+
+    pub fn to_speech(&self) -> String {
+
+        if self.aligned {
+            self.elements.iter().map(
+                |row| row.iter().map(|cell| vec_to_speech(cell)).filter(|cell| !cell.is_empty()).collect::<Vec<String>>().join(" ")
+            ).collect::<Vec<String>>().join(" new line ")
+        }
+
+        else {
+            let cols = self.elements.iter().map(|row| row.len()).max().unwrap_or(0);
+            let rows = self.elements.iter().enumerate().map(
+                |(index, row)| format!(
+                    "row {}: {}",
+                    index + 1,
+                    row.iter().map(|cell| vec_to_speech(cell)).collect::<Vec<String>>().join(", ")
+                )
+            ).collect::<Vec<String>>().join("; ");
+
+            format!("the {} by {cols} matrix; {rows}; end matrix", self.elements.len())
+        }
+
+    }
 }
 
-pub fn try_parse_matrix(arguments: &Vec<Vec<u32>>) -> Result<Vec<Vec<Vec<Entity>>>, String> {
+// the error has the index of the row that has a problem
+pub fn try_parse_matrix(arguments: &Vec<Vec<u32>>) -> Result<Vec<Vec<Vec<Entity>>>, (usize, String)> {
     let mut result = vec![];
     let mut cols = usize::MAX;
 
-    for row in arguments.iter() {
+    for (row_index, row) in arguments.iter().enumerate() {
+
+        // `cases{x}{y}`: a row is `{x}{y}`, not `x`
+        match row.iter().find(|c| **c != ' ' as u32 && **c != '\n' as u32) {
+            Some(c) if *c != '{' as u32 => {
+                return Err((row_index, format!("A row has to start with `{{`: {}", from_v32(row))));
+            },
+            _ => {}
+        }
+
         let (elements, end_index) = get_arguments(row, 0);
 
         if let Some(e) = find_extra_stuff(row, end_index) {
-            return Err(format!("Unexpected input: {}", from_v32(&e)));
+            return Err((row_index, format!("Unexpected input: {}", from_v32(&e))));
         }
 
         if cols == usize::MAX {
             cols = elements.len();
 
             if cols == 0 {
-                return Err(String::from("Empty Row"));
+                return Err((row_index, String::from("Empty Row")));
             }
         } else if cols != elements.len() {
-            return Err(format!("Expected {cols} elements, but got {} elements", elements.len()));
+            return Err((row_index, format!("Expected {cols} elements, but got {} elements", elements.len())));
         }
 
         result.push(elements.iter().map(|el| md_to_math(el)).collect());
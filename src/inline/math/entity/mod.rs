@@ -1,15 +1,26 @@
+mod brace;
+mod cases;
+mod fenced;
+pub mod font;
 mod fraction;
 pub mod matrix;
 mod root;
 mod script;
+mod speech;
 mod underover;
 
 use crate::escape::{escape_htmls, render_html_escapes};
-use crate::utils::{into_v32, is_alphabet, is_numeric};
+use crate::utils::{from_v32, into_v32, is_alphabet, is_numeric};
+use brace::Brace;
+use cases::Cases;
+use fenced::Fenced;
+use font::{Font, FontVariant};
 use fraction::Fraction;
 use matrix::Matrix;
 use root::Root;
 use script::Script;
+pub use speech::{vec_to_plain_text, vec_to_speech};
+use speech::{character_to_speech, function_to_speech, operator_to_plain_text, operator_to_speech};
 use underover::UnderOver;
 
 #[derive(Clone)]
@@ -19,9 +30,15 @@ pub enum Entity {
     Script(Script),
     UnderOver(UnderOver),
     Matrix(Matrix),
-    Space(usize),
+    Cases(Cases),
+    Fenced(Fenced),
+    Brace(Brace),
+    Font(Font),
+    Space(usize),            // in thirds of `em`
+    ThinSpace,               // `0.1667em`, between a number and its unit
     Br,
     Identifier(Vec<u32>),    // <mi>
+    Function(Vec<u32>),      // <mi> followed by an invisible function application
     Operator(Vec<u32>),      // <mo>
     Number(Vec<u32>),        // <mn>
     RawString(Vec<u32>),     // string inside `<mtext>`
@@ -60,6 +77,26 @@ impl Entity {
         Entity::Matrix(Matrix::new(elements))
     }
 
+    pub fn new_cases(rows: Vec<Vec<Vec<Entity>>>) -> Self {
+        Entity::Cases(Cases::new(rows))
+    }
+
+    pub fn new_fenced(open: Option<u32>, close: Option<u32>, content: Vec<Entity>) -> Self {
+        Entity::Fenced(Fenced::new(open, close, content))
+    }
+
+    pub fn new_brace(content: Vec<Entity>, label: Vec<Entity>, over: bool) -> Self {
+        Entity::Brace(Brace::new(content, label, over))
+    }
+
+    pub fn new_font(variant: FontVariant, content: Vec<u32>) -> Self {
+        Entity::Font(Font::new(variant, content))
+    }
+
+    pub fn new_function(name: Vec<u32>) -> Self {
+        Entity::Function(name)
+    }
+
     pub fn new_character(character: u32) -> Self {
         Entity::Character(character)
     }
@@ -80,11 +117,16 @@ impl Entity {
 
         match self {
             Entity::Space(space) => into_v32(&format!("<mspace width=\"{}\"/>", calc_space(*space))),
+            Entity::ThinSpace => into_v32("<mspace width=\"0.1667em\"/>"),
             Entity::Root(root) => root.to_math_ml(),
             Entity::Fraction(fraction) => fraction.to_math_ml(),
             Entity::UnderOver(underover) => underover.to_math_ml(),
             Entity::Script(script) => script.to_math_ml(),
             Entity::Matrix(matrix) => matrix.to_math_ml(),
+            Entity::Cases(cases) => cases.to_math_ml(),
+            Entity::Fenced(fenced) => fenced.to_math_ml(),
+            Entity::Brace(brace) => brace.to_math_ml(),
+            Entity::Font(font) => font.to_math_ml(),
             Entity::Character(character) => {
                 let tag = if 913 <= *character && *character <= 969 {  // Alpha to omega
                     "mi"
@@ -100,6 +142,12 @@ impl Entity {
                 identifier.clone(),
                 vec![60, 47, 109, 105, 62],  // into_v32("</mi>")
             ].concat(),
+            // `sin x` -> `<mi>sin</mi><mo>&#8289;</mo><mi>x</mi>`
+            Entity::Function(name) => vec![
+                vec![60, 109, 105, 62],  // into_v32("<mi>")
+                name.clone(),
+                vec![60, 47, 109, 105, 62, 60, 109, 111, 62, 38, 35, 56, 50, 56, 57, 59, 60, 47, 109, 111, 62],  // into_v32("</mi><mo>&#8289;</mo>")
+            ].concat(),
             Entity::Number(number) => vec![
                 vec![60, 109, 110, 62],  // into_v32("<mn>")
                 number.clone(),
@@ -131,6 +179,52 @@ impl Entity {
 
     }
 
+    // `sqrt{sup{a}{2} + sup{b}{2}}` -> `sqrt(a^2 + b^2)`
+    pub fn to_plain_text(&self) -> String {
+
+        match self {
+            Entity::Space(_) | Entity::ThinSpace => String::from(" "),
+            Entity::Root(root) => root.to_plain_text(),
+            Entity::Fraction(fraction) => fraction.to_plain_text(),
+            Entity::UnderOver(underover) => underover.to_plain_text(),
+            Entity::Script(script) => script.to_plain_text(),
+            Entity::Matrix(matrix) => matrix.to_plain_text(),
+            Entity::Cases(cases) => cases.to_plain_text(),
+            Entity::Fenced(fenced) => fenced.to_plain_text(),
+            Entity::Brace(brace) => brace.to_plain_text(),
+            Entity::Font(font) => font.to_plain_text(),
+            Entity::Character(character) => operator_to_plain_text(*character, true),
+            Entity::Identifier(identifier) | Entity::Number(identifier) | Entity::RawString(identifier) => from_v32(identifier),
+            Entity::Function(name) => format!("{} ", from_v32(name)),
+            Entity::Operator(operator) => operator.iter().map(|op| operator_to_plain_text(*op, true)).collect::<Vec<String>>().concat(),
+            Entity::Br => String::from("; "),
+        }
+
+    }
+
+    // `sqrt{sup{a}{2} + sup{b}{2}}` -> `the square root of a squared plus b squared end root`
+    pub fn to_speech(&self) -> String {
+
+        match self {
+            Entity::Space(_) | Entity::ThinSpace => String::new(),
+            Entity::Root(root) => root.to_speech(),
+            Entity::Fraction(fraction) => fraction.to_speech(),
+            Entity::UnderOver(underover) => underover.to_speech(),
+            Entity::Script(script) => script.to_speech(),
+            Entity::Matrix(matrix) => matrix.to_speech(),
+            Entity::Cases(cases) => cases.to_speech(),
+            Entity::Fenced(fenced) => fenced.to_speech(),
+            Entity::Brace(brace) => brace.to_speech(),
+            Entity::Font(font) => font.to_speech(),
+            Entity::Character(character) => character_to_speech(*character),
+            Entity::Identifier(identifier) | Entity::Number(identifier) | Entity::RawString(identifier) => from_v32(identifier),
+            Entity::Function(name) => function_to_speech(name),
+            Entity::Operator(operator) => operator_to_speech(operator),
+            Entity::Br => String::from("new line"),
+        }
+
+    }
+
 }
 
 fn calc_space(space: usize) -> String {
@@ -250,7 +344,7 @@ pub fn vec_to_math_ml(vec: &Vec<Entity>, single_element: bool) -> Vec<u32> {
 }
 
 // `vec.len()` and `count_entity(vec)` are different sometimes
-fn count_entity(vec: &Vec<Entity>) -> usize {
+fn count_entity(vec: &[Entity]) -> usize {
     let mut result = 0;
 
     for entity in vec.iter() {
@@ -259,6 +353,10 @@ fn count_entity(vec: &Vec<Entity>) -> usize {
             Entity::Operator(operator) => {
                 result += operator.len();
             }
+            // `<mi>sin</mi><mo>&#8289;</mo>`
+            Entity::Function(_) => {
+                result += 2;
+            }
             _ => {
                 result += 1;
             }
@@ -1,4 +1,5 @@
 use super::{Entity, vec_to_math_ml};
+use super::speech::{end_of, vec_to_plain_text, vec_to_speech};
 
 #[derive(Clone)]
 pub struct Root {
@@ -33,4 +34,26 @@ impl Root {
 
     }
 
-}
\ No newline at end of file
+    pub fn to_plain_text(&self) -> String {
+
+        if self.index.is_empty() {
+            format!("sqrt({})", vec_to_plain_text(&self.content))
+        }
+
+        else {
+            format!("root({}, {})", vec_to_plain_text(&self.index), vec_to_plain_text(&self.content))
+        }
+
+    }
+
+    pub fn to_speech(&self) -> String {
+        let root = match vec_to_plain_text(&self.index).as_str() {
+            "" | "2" => String::from("square root"),
+            "3" => String::from("cube root"),
+            _ => format!("{}-th root", vec_to_speech(&self.index))
+        };
+
+        format!("the {root} of {}{}", vec_to_speech(&self.content), end_of(&self.content, "root"))
+    }
+
+}
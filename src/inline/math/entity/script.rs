@@ -1,4 +1,5 @@
 use super::{Entity, vec_to_math_ml};
+use super::speech::{end_of, group_to_plain_text, vec_to_plain_text, vec_to_speech};
 use crate::utils::into_v32;
 
 #[derive(Clone)]
@@ -71,6 +72,58 @@ impl Script {
 
     }
 
+    // `x_i^2`, `(a + b)^(n + 1)`
+    pub fn to_plain_text(&self) -> String {
+        let mut result = vec![];
+
+        if !self.pre_sub.is_empty() {
+            result.push(format!("_{} ", group_to_plain_text(&self.pre_sub)));
+        }
+
+        if !self.pre_sup.is_empty() {
+            result.push(format!("^{} ", group_to_plain_text(&self.pre_sup)));
+        }
+
+        result.push(group_to_plain_text(&self.content));
+
+        if !self.post_sub.is_empty() {
+            result.push(format!("_{}", group_to_plain_text(&self.post_sub)));
+        }
+
+        if !self.post_sup.is_empty() {
+            result.push(format!("^{}", group_to_plain_text(&self.post_sup)));
+        }
+
+        result.concat()
+    }
+
+    // `x sub i squared`
+    pub fn to_speech(&self) -> String {
+        let mut result = vec![vec_to_speech(&self.content)];
+
+        if !self.pre_sub.is_empty() {
+            result.push(format!("with pre-subscript {}", vec_to_speech(&self.pre_sub)));
+        }
+
+        if !self.pre_sup.is_empty() {
+            result.push(format!("with pre-superscript {}", vec_to_speech(&self.pre_sup)));
+        }
+
+        if !self.post_sub.is_empty() {
+            result.push(format!("sub {}{}", vec_to_speech(&self.post_sub), end_of(&self.post_sub, "sub")));
+        }
+
+        if !self.post_sup.is_empty() {
+            result.push(match vec_to_plain_text(&self.post_sup).as_str() {
+                "2" => String::from("squared"),
+                "3" => String::from("cubed"),
+                _ => format!("to the power of {}{}", vec_to_speech(&self.post_sup), end_of(&self.post_sup, "power"))
+            });
+        }
+
+        result.join(" ")
+    }
+
 }
 
 fn script_or_none(vec: &Vec<Entity>) -> Vec<u32> {
@@ -0,0 +1,289 @@
+use super::{Entity, count_entity};
+use crate::utils::from_v32;
+
+/*
+Two plain-text forms of an `Entity` tree, for the readers that don't understand MathML.
+
+- plain text: `sqrt(a^2 + b^2)`. It's used for `alttext`, and the non-HTML outputs.
+- speech: `the square root of a squared plus b squared end root`. It's read by screen readers.
+*/
+
+pub fn vec_to_plain_text(vec: &[Entity]) -> String {
+    let mut result = vec![];
+    let mut after_operand = false;
+
+    for entity in vec.iter() {
+
+        match entity {
+            Entity::Operator(operator) => {
+
+                for c in operator.iter() {
+                    result.push(operator_to_plain_text(*c, after_operand));
+                    after_operand = is_closing(*c, after_operand);
+                }
+
+            },
+            Entity::Character(c) => {
+                result.push(operator_to_plain_text(*c, after_operand));
+                after_operand = is_closing(*c, after_operand);
+            },
+            _ => {
+                result.push(entity.to_plain_text());
+                after_operand = true;
+            }
+        }
+
+    }
+
+    // binary operators are surrounded by spaces, and the spaces are collapsed here
+    result.concat().split(' ').filter(|word| !word.is_empty()).collect::<Vec<&str>>().join(" ")
+}
+
+// `a + b` -> `(a + b)`, so that `(a + b)/2` is not `a + b/2`
+pub fn group_to_plain_text(vec: &[Entity]) -> String {
+    let result = vec_to_plain_text(vec);
+
+    if count_entity(vec) > 1 && !is_parenthesized(&result) {
+        format!("({result})")
+    }
+
+    else {
+        result
+    }
+
+}
+
+// `(a + b)` is, but `(a) + (b)` is not
+fn is_parenthesized(string: &str) -> bool {
+
+    if !string.starts_with('(') {
+        return false;
+    }
+
+    let mut depth = 0;
+
+    for (index, c) in string.char_indices() {
+
+        if c == '(' {
+            depth += 1;
+        }
+
+        else if c == ')' {
+            depth -= 1;
+
+            if depth == 0 {
+                return index == string.len() - 1;
+            }
+
+        }
+
+    }
+
+    false
+}
+
+pub fn vec_to_speech(vec: &[Entity]) -> String {
+    vec.iter().map(
+        |entity| entity.to_speech()
+    ).filter(
+        |speech| !speech.is_empty()
+    ).collect::<Vec<String>>().join(" ")
+}
+
+// `end root`, `end fraction`, ... are only read when the content is not a single entity
+pub fn end_of(vec: &[Entity], name: &str) -> String {
+
+    if count_entity(vec) > 1 {
+        format!(" end {name}")
+    }
+
+    else {
+        String::new()
+    }
+
+}
+
+// `-` after an operand is a binary operator (`a - b`), otherwise it's unary (`-b`)
+// `*` is not a binary operator without an operand, either: `X^**`
+pub fn operator_to_plain_text(operator: u32, after_operand: bool) -> String {
+    let operator = char::from_u32(operator).unwrap_or('?');
+
+    // `&` of `x &= 1` aligns the rows
+    if operator == '&' {
+        String::new()
+    }
+
+    else if is_binary_operator(operator) && (after_operand || !"+-*±∓".contains(operator)) {
+        format!(" {operator} ")
+    }
+
+    else if operator == ',' {
+        String::from(", ")
+    }
+
+    else {
+        operator.to_string()
+    }
+
+}
+
+// `(a + b) - c`, `n! - 1`
+// `|` may open or close a group, so it doesn't change anything
+fn is_closing(operator: u32, after_operand: bool) -> bool {
+
+    match char::from_u32(operator) {
+        Some(')' | ']' | '}' | '!' | '\'' | '′' | '⌋' | '⌉') => true,
+        Some('|' | '‖') => after_operand,
+        _ => false
+    }
+
+}
+
+fn is_binary_operator(operator: char) -> bool {
+    "+-*=<>×÷±".contains(operator)
+
+    // arrows and mathematical operators, except the ones that are not binary
+    || (('\u{2190}'..='\u{22ff}').contains(&operator) && !"∀∂∃∄∅∇∞¬".contains(operator))
+}
+
+pub fn character_to_speech(character: u32) -> String {
+
+    if let Some(name) = greek_letter_name(character) {
+        return name.to_string();
+    }
+
+    let speech = match character {
+        43 => "plus",
+        45 | 8722 => "minus",
+        61 => "equals",
+        60 => "is less than",
+        62 => "is greater than",
+        42 | 215 | 8901 => "times",
+        47 | 247 => "divided by",
+        177 => "plus or minus",
+        8723 => "minus or plus",
+        33 => "factorial",
+        44 => "comma",
+        40 => "open paren",
+        41 => "close paren",
+        91 => "open bracket",
+        93 => "close bracket",
+        123 => "open brace",
+        125 => "close brace",
+        124 => "vertical bar",
+        8242 => "prime",
+        8804 => "is less than or equal to",
+        8805 => "is greater than or equal to",
+        8800 => "is not equal to",
+        8776 => "is approximately equal to",
+        8801 => "is equivalent to",
+        8733 => "is proportional to",
+        8810 => "is much less than",
+        8811 => "is much greater than",
+        8712 => "is in",
+        8713 => "is not in",
+        8715 => "contains",
+        8834 => "is a subset of",
+        8835 => "is a superset of",
+        8838 => "is a subset of or equal to",
+        8839 => "is a superset of or equal to",
+        8745 => "intersection",
+        8746 => "union",
+        8743 => "and",
+        8744 => "or",
+        172 => "not",
+        8704 => "for all",
+        8707 => "there exists",
+        8709 => "the empty set",
+        8706 => "partial",
+        8711 => "nabla",
+        8734 => "infinity",
+        8594 => "goes to",
+        8592 => "left arrow",
+        8596 => "left right arrow",
+        8652 => "is in equilibrium with",
+        8593 => "up arrow",
+        8595 => "down arrow",
+        8658 => "implies",
+        8660 => "if and only if",
+        8756 => "therefore",
+        8757 => "because",
+        8230 | 8943 => "dot dot dot",
+        8728 => "composed with",
+        8853 => "circled plus",
+        8855 => "circled times",
+        8214 => "double vertical bar",
+        _ => {
+            return char::from_u32(character).map(|c| c.to_string()).unwrap_or_default();
+        }
+    };
+
+    speech.to_string()
+}
+
+// `&` is not read, like `operator_to_plain_text`
+pub fn operator_to_speech(operator: &[u32]) -> String {
+    operator.iter().filter(
+        |c| **c != '&' as u32
+    ).map(
+        |c| character_to_speech(*c)
+    ).collect::<Vec<String>>().join(" ")
+}
+
+fn greek_letter_name(character: u32) -> Option<&'static str> {
+    let names = [
+        "alpha", "beta", "gamma", "delta", "epsilon", "zeta", "eta", "theta", "iota", "kappa", "lambda", "mu",
+        "nu", "xi", "omicron", "pi", "rho", "final sigma", "sigma", "tau", "upsilon", "phi", "chi", "psi", "omega"
+    ];
+
+    match character {
+        945..=969 => Some(names[(character - 945) as usize]),
+
+        // there's no capital final sigma
+        913..=937 if character != 930 => Some(capital_greek_letter_name(names[(character - 913) as usize])),
+        977 => Some("theta"),
+        981 => Some("phi"),
+        1013 => Some("epsilon"),
+        _ => None
+    }
+
+}
+
+fn capital_greek_letter_name(name: &str) -> &'static str {
+
+    match name {
+        "alpha" => "capital alpha", "beta" => "capital beta", "gamma" => "capital gamma", "delta" => "capital delta",
+        "epsilon" => "capital epsilon", "zeta" => "capital zeta", "eta" => "capital eta", "theta" => "capital theta",
+        "iota" => "capital iota", "kappa" => "capital kappa", "lambda" => "capital lambda", "mu" => "capital mu",
+        "nu" => "capital nu", "xi" => "capital xi", "omicron" => "capital omicron", "pi" => "capital pi",
+        "rho" => "capital rho", "sigma" => "capital sigma", "tau" => "capital tau", "upsilon" => "capital upsilon",
+        "phi" => "capital phi", "chi" => "capital chi", "psi" => "capital psi", _ => "capital omega"
+    }
+
+}
+
+// `sin` -> `sine`
+pub fn function_to_speech(name: &[u32]) -> String {
+    let name = from_v32(name);
+
+    match name.as_str() {
+        "sin" => "sine",
+        "cos" => "cosine",
+        "tan" => "tangent",
+        "sec" => "secant",
+        "csc" => "cosecant",
+        "cot" => "cotangent",
+        "ln" => "natural log",
+        "log" => "log",
+        "exp" => "exponential",
+        "det" => "determinant",
+        "gcd" => "greatest common divisor",
+        "lcm" => "least common multiple",
+        "lim" => "limit",
+        "max" => "maximum",
+        "min" => "minimum",
+        "argmax" => "arg max",
+        "argmin" => "arg min",
+        _ => { return name; }
+    }.to_string()
+}
@@ -1,4 +1,5 @@
 use super::{Entity, vec_to_math_ml};
+use super::speech::{function_to_speech, group_to_plain_text, vec_to_plain_text, vec_to_speech};
 use crate::utils::into_v32;
 
 #[derive(Clone)]
@@ -53,4 +54,103 @@ impl UnderOver {
 
     }
 
+    // `hat{x}`, `bar{x}`, ...
+    fn accent(&self) -> Option<&'static str> {
+
+        match (&self.under[..], &self.over[..]) {
+            ([], [Entity::Character(accent)]) => match accent {
+                94 => Some("hat"),  // '^'
+                45 => Some("bar"),  // '-'
+                8901 => Some("dot"),
+                126 => Some("tilde"),  // '~'
+                8594 => Some("vec"),
+                _ => None
+            },
+            _ => None
+        }
+
+    }
+
+    pub fn to_plain_text(&self) -> String {
+
+        if let Some(accent) = self.accent() {
+            return format!("{accent}({})", vec_to_plain_text(&self.content));
+        }
+
+        let mut result = vec![group_to_plain_text(&self.content)];
+
+        if !self.under.is_empty() {
+            result.push(format!("_{}", group_to_plain_text(&self.under)));
+        }
+
+        if !self.over.is_empty() {
+            result.push(format!("^{}", group_to_plain_text(&self.over)));
+        }
+
+        // `sum_(i = 1)^n i`, not `sum_(i = 1)^ni`
+        result.push(String::from(" "));
+
+        result.concat()
+    }
+
+    // `the sum from i equals 1 to n of`, `the limit as x goes to 0 of`
+    pub fn to_speech(&self) -> String {
+        let content = vec_to_speech(&self.content);
+
+        if let Some(accent) = self.accent() {
+            return if accent == "vec" {
+                format!("vector {content}")
+            } else {
+                format!("{content} {accent}")
+            };
+        }
+
+        let operator = match &self.content[..] {
+            [Entity::Operator(operator)] => match &operator[..] {
+                [8721] => Some(String::from("sum")),
+                [8719] => Some(String::from("product")),
+                [8720] => Some(String::from("coproduct")),
+                [8747] => Some(String::from("integral")),
+                [8748] => Some(String::from("double integral")),
+                [8749] => Some(String::from("triple integral")),
+                [8750] => Some(String::from("contour integral")),
+                [8898] => Some(String::from("intersection")),
+                [8899] => Some(String::from("union")),
+                _ => None
+            },
+            [Entity::Identifier(name)] => Some(function_to_speech(name)),
+            _ => None
+        };
+
+        match operator {
+            Some(operator) => {
+                let under = vec_to_speech(&self.under);
+                let over = vec_to_speech(&self.over);
+
+                match (under.is_empty(), over.is_empty()) {
+                    (true, true) => format!("the {operator} of"),
+                    (false, true) if operator == "limit" => format!("the limit as {under} of"),
+                    (false, true) => format!("the {operator} over {under} of"),
+                    (true, false) => format!("the {operator} to {over} of"),
+                    (false, false) => format!("the {operator} from {under} to {over} of"),
+                }
+
+            },
+            None => {
+                let mut result = vec![content];
+
+                if !self.under.is_empty() {
+                    result.push(format!("with {} under", vec_to_speech(&self.under)));
+                }
+
+                if !self.over.is_empty() {
+                    result.push(format!("with {} over", vec_to_speech(&self.over)));
+                }
+
+                result.join(" ")
+            }
+        }
+
+    }
+
 }
\ No newline at end of file
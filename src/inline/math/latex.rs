@@ -0,0 +1,816 @@
+use super::chemistry::parse_chemistry;
+use super::entity::{Entity, font::FontVariant, matrix::Matrix};
+use super::unit::{parse_quantity, parse_unit};
+use super::validate::MathError;
+use crate::utils::{from_v32, into_v32, is_alphabet, is_numeric};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+/*
+A subset of LaTeX math, which is parsed to the same `Entity` tree as the MDxt math.
+
+- `\frac{a}{b}`, `\sqrt[n]{x}`, `\binom{n}{k}`
+- `x^2`, `x_{i}`, `\sum_{i=1}^{n}`, `\int_{a}^{b}`, `\lim_{x \to 0}`
+- `\begin{pmatrix} a & b \\ c & d \end{pmatrix}`, `\begin{aligned} ... \end{aligned}`, `\begin{cases} ... \end{cases}`
+- `\overbrace{a + b}^{n}`, `\mathbb{R}`, `\operatorname*{argmax}_{x}`
+- `\left( ... \right)`
+- Greek letters and common symbols
+- `\ce{2H2 + O2 -> 2H2O}`, `\qty{9.8}{m/s^2}`, `\unit{m/s}`, with the syntax of `ce` and `unit` of the MDxt math
+*/
+
+lazy_static! {
+
+    // `\alpha` -> `α`
+    static ref LATEX_SYMBOLS: HashMap<Vec<u32>, u32> = {
+        let symbols = vec![
+            ("alpha", 945), ("beta", 946), ("gamma", 947), ("delta", 948), ("epsilon", 1013), ("varepsilon", 949),
+            ("zeta", 950), ("eta", 951), ("theta", 952), ("vartheta", 977), ("iota", 953), ("kappa", 954),
+            ("lambda", 955), ("mu", 956), ("nu", 957), ("xi", 958), ("omicron", 959), ("pi", 960), ("varpi", 982),
+            ("rho", 961), ("varrho", 1009), ("sigma", 963), ("varsigma", 962), ("tau", 964), ("upsilon", 965),
+            ("phi", 981), ("varphi", 966), ("chi", 967), ("psi", 968), ("omega", 969),
+            ("Gamma", 915), ("Delta", 916), ("Theta", 920), ("Lambda", 923), ("Xi", 926), ("Pi", 928),
+            ("Sigma", 931), ("Upsilon", 933), ("Phi", 934), ("Psi", 936), ("Omega", 937),
+
+            ("times", 215), ("div", 247), ("pm", 177), ("mp", 8723), ("cdot", 8901), ("ast", 42), ("star", 8902),
+            ("circ", 8728), ("bullet", 8729), ("oplus", 8853), ("ominus", 8854), ("otimes", 8855), ("odot", 8857),
+            ("cap", 8745), ("cup", 8746), ("setminus", 8726), ("wedge", 8743), ("land", 8743), ("vee", 8744), ("lor", 8744),
+            ("neg", 172), ("lnot", 172), ("dagger", 8224),
+
+            ("ne", 8800), ("neq", 8800), ("le", 8804), ("leq", 8804), ("ge", 8805), ("geq", 8805),
+            ("ll", 8810), ("gg", 8811), ("approx", 8776), ("sim", 8764), ("simeq", 8771), ("asymp", 8781),
+            ("equiv", 8801), ("propto", 8733), ("in", 8712), ("notin", 8713), ("ni", 8715),
+            ("subset", 8834), ("supset", 8835), ("subseteq", 8838), ("supseteq", 8839), ("perp", 8869),
+            ("parallel", 8741), ("mid", 124),
+
+            ("to", 8594), ("rightarrow", 8594), ("gets", 8592), ("leftarrow", 8592), ("uparrow", 8593),
+            ("downarrow", 8595), ("leftrightarrow", 8596), ("Rightarrow", 8658), ("Leftarrow", 8656),
+            ("Leftrightarrow", 8660), ("implies", 8658), ("iff", 8660), ("mapsto", 8614),
+
+            ("forall", 8704), ("exists", 8707), ("nexists", 8708), ("partial", 8706), ("nabla", 8711),
+            ("infty", 8734), ("emptyset", 8709), ("varnothing", 8709), ("therefore", 8756), ("because", 8757),
+            ("angle", 8736), ("triangle", 9651), ("ell", 8467), ("hbar", 8463), ("aleph", 8501), ("Re", 8476), ("Im", 8465),
+            ("prime", 8242), ("qed", 8718),
+            ("ldots", 8230), ("dots", 8230), ("cdots", 8943), ("vdots", 8942), ("ddots", 8945),
+
+            ("langle", 10216), ("rangle", 10217), ("lfloor", 8970), ("rfloor", 8971), ("lceil", 8968), ("rceil", 8969),
+            ("lvert", 124), ("rvert", 124), ("vert", 124), ("lVert", 8214), ("rVert", 8214), ("Vert", 8214),
+        ];
+        let mut result = HashMap::with_capacity(symbols.len());
+
+        for (name, character) in symbols.into_iter() {
+            result.insert(into_v32(name), character);
+        }
+
+        result
+    };
+
+    // `\sum_{i=1}^{n}` -> `∑` with limits
+    static ref LARGE_OPERATORS: HashMap<Vec<u32>, u32> = {
+        let operators = vec![
+            ("sum", 8721), ("prod", 8719), ("coprod", 8720),
+            ("int", 8747), ("iint", 8748), ("iiint", 8749), ("oint", 8750),
+            ("bigcup", 8899), ("bigcap", 8898)
+        ];
+        let mut result = HashMap::with_capacity(operators.len());
+
+        for (name, character) in operators.into_iter() {
+            result.insert(into_v32(name), character);
+        }
+
+        result
+    };
+
+    // `\sin` -> `<mi>sin</mi><mo>&#8289;</mo>`
+    static ref NAMED_FUNCTIONS: Vec<Vec<u32>> = vec![
+        "sin", "cos", "tan", "sec", "csc", "cot", "arcsin", "arccos", "arctan", "sinh", "cosh", "tanh",
+        "log", "ln", "lg", "exp", "det", "dim", "ker", "gcd", "deg", "arg", "hom",
+        "max", "min", "sup", "inf", "Pr"
+    ].into_iter().map(into_v32).collect();
+
+    // `\max_{x}` has its subscript under the operator, like `\lim_{x}`
+    static ref LIMIT_FUNCTIONS: Vec<Vec<u32>> = vec![
+        "det", "gcd", "max", "min", "sup", "inf", "Pr"
+    ].into_iter().map(into_v32).collect();
+
+    // the commands that `read_command` handles by their names
+    // it's only used by `find_latex_errors`
+    static ref COMMANDS: Vec<Vec<u32>> = vec![
+        "quad", "qquad", "lim", "frac", "dfrac", "tfrac", "cfrac", "binom", "sqrt",
+        "text", "textrm", "mbox", "mathrm", "operatorname", "mathbb", "mathcal", "mathfrak", "mathbf",
+        "overbrace", "underbrace", "hat", "widehat", "bar", "overline", "dot", "tilde", "widetilde", "vec",
+        "left", "right", "begin", "end", "ce", "unit", "qty", "SI"
+    ].into_iter().map(into_v32).collect();
+
+    static ref ENVIRONMENTS: Vec<Vec<u32>> = vec![
+        "matrix", "smallmatrix", "cases", "pmatrix", "bmatrix", "Bmatrix", "vmatrix", "Vmatrix",
+        "aligned", "align", "align*", "split", "gathered", "gather", "gather*"
+    ].into_iter().map(into_v32).collect();
+
+}
+
+pub fn latex_to_math(content: &[u32]) -> Vec<Entity> {
+    let mut result = vec![];
+    let mut index = 0;
+
+    while index < content.len() {
+        let (atom, end_index) = read_atom(content, index);
+        index = end_index;
+
+        let (sup, sub, end_index) = read_scripts(content, index);
+        index = end_index;
+
+        if sup.is_empty() && sub.is_empty() {
+            result.extend(atom);
+        }
+
+        else {
+            result.push(Entity::new_script(atom, vec![], sup, vec![], sub));
+        }
+
+    }
+
+    result
+}
+
+// `content` is what `latex_to_math` takes
+// `latex_to_math` never fails: it renders what it doesn't understand as a raw string. This function tells why.
+pub fn find_latex_errors(content: &[u32]) -> Vec<MathError> {
+    let mut errors = vec![];
+    let mut braces = vec![];  // indices of `{`
+    let mut environments = vec![];  // (name, index of `\begin`)
+    let mut lefts = vec![];  // indices of `\left`
+    let mut index = 0;
+
+    while index < content.len() {
+
+        if content[index] == '\\' as u32 {
+            let (name, end_index) = read_command_name(content, index);
+
+            if name.is_empty() {
+                errors.push(MathError::new(String::from("`\\` is not followed by a command"), index));
+                break;
+            }
+
+            // `\\`, `\{`, `\,`, ...
+            if !is_alphabet(&name[0]) {
+                index = end_index;
+                continue;
+            }
+
+            match from_v32(&name).as_str() {
+                "begin" => {
+                    let (environment, end_index) = read_raw_argument(content, end_index);
+
+                    if !ENVIRONMENTS.contains(&environment) {
+                        errors.push(MathError::new(format!("unknown environment `{}`", from_v32(&environment)), index));
+                    }
+
+                    environments.push((environment, index));
+                    index = end_index;
+                    continue;
+                },
+                "end" => {
+                    let (environment, end_index) = read_raw_argument(content, end_index);
+
+                    match environments.pop() {
+                        Some((begin, _)) if begin == environment => {},
+                        Some((begin, _)) => {
+                            errors.push(MathError::new(format!("`\\end{{{}}}` doesn't match `\\begin{{{}}}`", from_v32(&environment), from_v32(&begin)), index));
+                        },
+                        None => {
+                            errors.push(MathError::new(format!("`\\end{{{}}}` without `\\begin`", from_v32(&environment)), index));
+                        }
+                    }
+
+                    index = end_index;
+                    continue;
+                },
+                "left" => {
+                    lefts.push(index);
+                },
+                "right" if lefts.pop().is_none() => {
+                    errors.push(MathError::new(String::from("`\\right` without `\\left`"), index));
+                },
+                _ if !LATEX_SYMBOLS.contains_key(&name)
+                    && !LARGE_OPERATORS.contains_key(&name)
+                    && !NAMED_FUNCTIONS.contains(&name)
+                    && !COMMANDS.contains(&name) => {
+                    errors.push(MathError::new(format!("unknown command `\\{}`", from_v32(&name)), index));
+                },
+                _ => {}
+            }
+
+            index = end_index;
+            continue;
+        }
+
+        // `read_scripts` reads an empty argument: `x^`, `{x_}`, `x^_{i}`
+        if (content[index] == '^' as u32 || content[index] == '_' as u32) && !has_script_argument(content, index + 1) {
+            errors.push(MathError::new(format!("`{}` is not followed by an argument", from_v32(&content[index..(index + 1)])), index));
+        }
+
+        else if content[index] == '{' as u32 {
+            braces.push(index);
+        }
+
+        else if content[index] == '}' as u32 && braces.pop().is_none() {
+            errors.push(MathError::new(String::from("unexpected `}`"), index));
+        }
+
+        index += 1;
+    }
+
+    for index in braces.into_iter() {
+        errors.push(MathError::new(String::from("`{` is not closed"), index));
+    }
+
+    for (environment, index) in environments.into_iter() {
+        errors.push(MathError::new(format!("`\\begin{{{}}}` is not closed", from_v32(&environment)), index));
+    }
+
+    for index in lefts.into_iter() {
+        errors.push(MathError::new(String::from("`\\left` without `\\right`"), index));
+    }
+
+    errors.sort_by_key(|error| error.position);
+
+    errors
+}
+
+fn has_script_argument(content: &[u32], index: usize) -> bool {
+
+    match content.get(skip_whitespaces(content, index)) {
+        Some(c) => *c != '}' as u32 && *c != '^' as u32 && *c != '_' as u32 && *c != '&' as u32,
+        None => false
+    }
+
+}
+
+// (entities, end_index), where `end_index` is the index right after the atom
+fn read_atom(content: &[u32], index: usize) -> (Vec<Entity>, usize) {
+    let c = content[index];
+
+    if c == ' ' as u32 || c == '\n' as u32 || c == '\t' as u32 {
+        (vec![], index + 1)
+    }
+
+    else if c == '{' as u32 {
+
+        match get_group_end_index(content, index, '{' as u32, '}' as u32) {
+            Some(end_index) => (latex_to_math(&content[(index + 1)..end_index]), end_index + 1),
+
+            // an unclosed brace is ignored
+            None => (vec![], index + 1)
+        }
+
+    }
+
+    // an unmatched closing brace
+    else if c == '}' as u32 {
+        (vec![], index + 1)
+    }
+
+    else if c == '\\' as u32 {
+        read_command(content, index)
+    }
+
+    else if is_numeric(&c) {
+        let mut end_index = index + 1;
+
+        while end_index < content.len() && (is_numeric(&content[end_index]) || content[end_index] == '.' as u32) {
+            end_index += 1;
+        }
+
+        (vec![Entity::new_number(content[index..end_index].to_vec())], end_index)
+    }
+
+    // unlike the MDxt math, each alphabet is a separate identifier
+    else if is_alphabet(&c) {
+        (vec![Entity::new_identifier(vec![c])], index + 1)
+    }
+
+    else if c == '\'' as u32 {
+        (vec![Entity::new_character(8242)], index + 1)
+    }
+
+    // a script without a base
+    else if c == '^' as u32 || c == '_' as u32 {
+        (vec![], index)
+    }
+
+    else {
+        (vec![Entity::new_operator(vec![c])], index + 1)
+    }
+
+}
+
+// `^{..}` and `_{..}`, in any order
+fn read_scripts(content: &[u32], mut index: usize) -> (Vec<Entity>, Vec<Entity>, usize) {  // (sup, sub, end_index)
+    let mut sup = vec![];
+    let mut sub = vec![];
+
+    loop {
+        index = skip_whitespaces(content, index);
+
+        match content.get(index) {
+            Some(c) if *c == '^' as u32 && sup.is_empty() => {
+                let (argument, end_index) = read_argument(content, index + 1);
+                sup = argument;
+                index = end_index;
+            },
+            Some(c) if *c == '_' as u32 && sub.is_empty() => {
+                let (argument, end_index) = read_argument(content, index + 1);
+                sub = argument;
+                index = end_index;
+            },
+            _ => { break; }
+        }
+
+    }
+
+    (sup, sub, index)
+}
+
+// `{...}` or a single token: `x^23` is `x^{2}3`
+fn read_argument(content: &[u32], index: usize) -> (Vec<Entity>, usize) {
+    let index = skip_whitespaces(content, index);
+
+    match content.get(index) {
+        None => (vec![], index),
+        Some(c) if is_numeric(c) => (vec![Entity::new_number(vec![*c])], index + 1),
+        Some(c) if *c == '^' as u32 || *c == '_' as u32 => (vec![], index + 1),
+        Some(_) => read_atom(content, index)
+    }
+
+}
+
+// `{...}` as a raw string: `\text{...}`, `\begin{...}`
+fn read_raw_argument(content: &[u32], index: usize) -> (Vec<u32>, usize) {
+    let index = skip_whitespaces(content, index);
+
+    if content.get(index) == Some(&('{' as u32)) {
+
+        if let Some(end_index) = get_group_end_index(content, index, '{' as u32, '}' as u32) {
+            return (content[(index + 1)..end_index].to_vec(), end_index + 1);
+        }
+
+    }
+
+    (vec![], index)
+}
+
+fn read_command(content: &[u32], index: usize) -> (Vec<Entity>, usize) {
+    let (name, mut end_index) = read_command_name(content, index);
+
+    if name.is_empty() {
+        return (vec![], end_index);
+    }
+
+    // `\\`
+    if name == [92] {
+        return (vec![Entity::new_br()], end_index);
+    }
+
+    if name.len() == 1 && !is_alphabet(&name[0]) {
+        let entity = match name[0] {
+            // spaces
+            44 => Entity::Space(1),  // ','
+            58 | 59 => Entity::Space(2),  // ':', ';'
+            32 => Entity::Space(1),  // ' '
+            33 => { return (vec![], end_index); },  // '!'
+
+            // `\&` is not a column separator
+            38 => Entity::new_character(38),
+            124 => Entity::new_character(8214),  // `\|`
+            c => Entity::new_operator(vec![c])  // `\{`, `\}`, `\%`, `\$`, `\#`, `\_`
+        };
+
+        return (vec![entity], end_index);
+    }
+
+    if let Some(character) = LATEX_SYMBOLS.get(&name) {
+        // `Entity::Character` renders only `Α` ~ `ω` to `<mi>`
+        let entity = if is_greek(&name) {
+            Entity::new_identifier(vec![*character])
+        } else {
+            Entity::new_character(*character)
+        };
+
+        return (vec![entity], end_index);
+    }
+
+    if NAMED_FUNCTIONS.contains(&name) {
+        let limits = LIMIT_FUNCTIONS.contains(&name);
+
+        return read_named_function(content, end_index, name, limits);
+    }
+
+    if let Some(operator) = LARGE_OPERATORS.get(&name) {
+        let (over, under, end_index) = read_scripts(content, end_index);
+
+        return (vec![Entity::new_underover(vec![Entity::new_operator(vec![*operator])], under, over, true)], end_index);
+    }
+
+    match from_v32(&name).as_str() {
+        "quad" => (vec![Entity::Space(3)], end_index),
+        "qquad" => (vec![Entity::Space(6)], end_index),
+        "lim" => {
+            let (_, under, end_index) = read_scripts(content, end_index);
+
+            (vec![Entity::new_underover(vec![Entity::new_identifier(name)], under, vec![], false)], end_index)
+        },
+        "frac" | "dfrac" | "tfrac" | "cfrac" | "binom" => {
+            let (numer, end_index) = read_argument(content, end_index);
+            let (denom, end_index) = read_argument(content, end_index);
+            let display_style = name == into_v32("dfrac") || name == into_v32("cfrac");
+
+            if name == into_v32("binom") {
+                (vec![Entity::new_fenced(
+                    Some(40),  // '('
+                    Some(41),  // ')'
+                    vec![Entity::new_fraction(numer, denom, false, true)]
+                )], end_index)
+            }
+
+            else {
+                (vec![Entity::new_fraction(numer, denom, display_style, false)], end_index)
+            }
+
+        },
+        "sqrt" => {
+            let mut root_index = vec![];
+            end_index = skip_whitespaces(content, end_index);
+
+            // `\sqrt[3]{x}`
+            if content.get(end_index) == Some(&('[' as u32)) {
+
+                if let Some(bracket_end_index) = get_group_end_index(content, end_index, '[' as u32, ']' as u32) {
+                    root_index = latex_to_math(&content[(end_index + 1)..bracket_end_index]);
+                    end_index = bracket_end_index + 1;
+                }
+
+            }
+
+            let (radicand, end_index) = read_argument(content, end_index);
+
+            (vec![Entity::new_root(root_index, radicand)], end_index)
+        },
+        "text" | "textrm" | "mbox" => {
+            let (text, end_index) = read_raw_argument(content, end_index);
+
+            (vec![Entity::RawString(text)], end_index)
+        },
+        "mathrm" => {
+            let (text, end_index) = read_raw_argument(content, end_index);
+
+            (vec![Entity::new_identifier(text.into_iter().filter(|c| *c != ' ' as u32).collect())], end_index)
+        },
+
+        // `\operatorname*{argmax}_{x}` has its subscript under the operator
+        "operatorname" => {
+            let limits = content.get(end_index) == Some(&('*' as u32));
+            let (text, end_index) = read_raw_argument(content, end_index + limits as usize);
+
+            read_named_function(content, end_index, text.into_iter().filter(|c| *c != ' ' as u32).collect(), limits)
+        },
+        "mathbb" | "mathcal" | "mathfrak" | "mathbf" => {
+            let (text, end_index) = read_raw_argument(content, end_index);
+
+            (vec![Entity::new_font(FontVariant::from_name(&name).unwrap(), text)], end_index)
+        },
+        "overbrace" | "underbrace" => {
+            let (argument, end_index) = read_argument(content, end_index);
+            let (sup, sub, end_index) = read_scripts(content, end_index);
+            let over = name == into_v32("overbrace");
+
+            (vec![Entity::new_brace(argument, if over { sup } else { sub }, over)], end_index)
+        },
+        "hat" | "widehat" | "bar" | "overline" | "dot" | "tilde" | "widetilde" | "vec" => {
+            let (argument, end_index) = read_argument(content, end_index);
+            let operator = match from_v32(&name).as_str() {
+                "hat" | "widehat" => '^' as u32,
+                "bar" | "overline" => '-' as u32,
+                "dot" => 8901,
+                "tilde" | "widetilde" => '~' as u32,
+                _ => 8594
+            };
+
+            (vec![Entity::new_underover(argument, vec![], vec![Entity::new_character(operator)], false)], end_index)
+        },
+
+        // mhchem and siunitx, but with the syntax of `ce{...}` and `unit{...}{...}` of MDxt: `\qty{9.8}{m/s^2}`, not `\qty{9.8}{\meter\per\second\squared}`
+        "ce" => {
+            let (formula, end_index) = read_raw_argument(content, end_index);
+
+            (parse_chemistry(&formula), end_index)
+        },
+        "unit" => {
+            let (unit, end_index) = read_raw_argument(content, end_index);
+
+            (parse_unit(&unit), end_index)
+        },
+        "qty" | "SI" => {
+            let (value, end_index) = read_argument(content, end_index);
+            let (unit, end_index) = read_raw_argument(content, end_index);
+
+            (parse_quantity(value, &unit), end_index)
+        },
+        "left" => read_left_right(content, end_index),
+
+        // `\right` without `\left`
+        "right" => {
+            let (delimiter, end_index) = read_delimiter(content, end_index);
+
+            (delimiter.map(|c| vec![Entity::new_operator(vec![c])]).unwrap_or_default(), end_index)
+        },
+        "begin" => read_environment(content, end_index),
+        _ => (vec![Entity::RawString(vec![vec![92], name].concat())], end_index)
+    }
+
+}
+
+// `content[index]` is `\`
+fn read_command_name(content: &[u32], index: usize) -> (Vec<u32>, usize) {
+    let mut end_index = index + 1;
+
+    if end_index == content.len() {
+        return (vec![], end_index);
+    }
+
+    if !is_alphabet(&content[end_index]) {
+        return (vec![content[end_index]], end_index + 1);
+    }
+
+    while end_index < content.len() && is_alphabet(&content[end_index]) {
+        end_index += 1;
+    }
+
+    (content[(index + 1)..end_index].to_vec(), end_index)
+}
+
+// `\sin x`, `\max_{x \in S}`
+fn read_named_function(content: &[u32], index: usize, name: Vec<u32>, limits: bool) -> (Vec<Entity>, usize) {
+    let script_index = skip_whitespaces(content, index);
+
+    if limits && content.get(script_index) == Some(&('_' as u32)) {
+        let (under, end_index) = read_argument(content, script_index + 1);
+
+        (vec![Entity::new_underover(vec![Entity::new_identifier(name)], under, vec![], false)], end_index)
+    }
+
+    else {
+        (vec![Entity::new_function(name)], index)
+    }
+
+}
+
+// `(`, `\{`, `\langle`, `.` (no delimiter)
+fn read_delimiter(content: &[u32], index: usize) -> (Option<u32>, usize) {
+    let index = skip_whitespaces(content, index);
+
+    match content.get(index) {
+        None => (None, index),
+        Some(c) if *c == '.' as u32 => (None, index + 1),
+        Some(c) if *c == '\\' as u32 => {
+            let (name, end_index) = read_command_name(content, index);
+
+            match &name[..] {
+                [124] => (Some(8214), end_index),  // `\|`
+                [c] => (Some(*c), end_index),  // `\{`, `\}`
+                _ => (LATEX_SYMBOLS.get(&name).copied(), end_index)
+            }
+
+        },
+        Some(c) => (Some(*c), index + 1)
+    }
+
+}
+
+// `\left( ... \right)`
+fn read_left_right(content: &[u32], index: usize) -> (Vec<Entity>, usize) {
+    let (opening, index) = read_delimiter(content, index);
+    let mut depth = 0;
+    let mut curr_index = index;
+
+    while curr_index < content.len() {
+
+        if content[curr_index] == '\\' as u32 {
+            let (name, end_index) = read_command_name(content, curr_index);
+
+            if name == into_v32("left") {
+                depth += 1;
+            }
+
+            else if name == into_v32("right") {
+
+                if depth == 0 {
+                    let (closing, end_index) = read_delimiter(content, end_index);
+
+                    return (vec![Entity::new_fenced(opening, closing, latex_to_math(&content[index..curr_index]))], end_index);
+                }
+
+                depth -= 1;
+            }
+
+            curr_index = end_index;
+        }
+
+        else {
+            curr_index += 1;
+        }
+
+    }
+
+    // `\left(` without `\right`
+    (vec![Entity::new_fenced(opening, None, latex_to_math(&content[index..]))], content.len())
+}
+
+// `\begin{pmatrix} a & b \\ c & d \end{pmatrix}`
+fn read_environment(content: &[u32], index: usize) -> (Vec<Entity>, usize) {
+    let (name, index) = read_raw_argument(content, index);
+    let mut depth = 0;
+    let mut curr_index = index;
+
+    while curr_index < content.len() {
+
+        if content[curr_index] == '\\' as u32 {
+            let (command, end_index) = read_command_name(content, curr_index);
+
+            if command == into_v32("begin") {
+                depth += 1;
+            }
+
+            else if command == into_v32("end") {
+                let (end_name, end_index) = read_raw_argument(content, end_index);
+
+                if depth == 0 && end_name == name {
+                    return (environment_to_entities(&name, &content[index..curr_index]), end_index);
+                }
+
+                depth -= 1;
+                curr_index = end_index;
+                continue;
+            }
+
+            curr_index = end_index;
+        }
+
+        else {
+            curr_index += 1;
+        }
+
+    }
+
+    (vec![Entity::RawString(into_v32(&format!("Error: `\\begin{{{}}}` is not closed", from_v32(&name))))], content.len())
+}
+
+fn environment_to_entities(name: &[u32], body: &[u32]) -> Vec<Entity> {
+    let rows = split_environment_body(body);
+
+    let (fences, aligned) = match from_v32(name).as_str() {
+        "matrix" | "smallmatrix" | "cases" => (None, false),
+        "pmatrix" => (Some((40, 41)), false),  // '(', ')'
+        "bmatrix" => (Some((91, 93)), false),  // '[', ']'
+        "Bmatrix" => (Some((123, 125)), false),  // '{', '}'
+        "vmatrix" => (Some((124, 124)), false),  // '|'
+        "Vmatrix" => (Some((8214, 8214)), false),  // '‖'
+        "aligned" | "align" | "align*" | "split" | "gathered" | "gather" | "gather*" => (None, true),
+        _ => {
+            return vec![Entity::RawString(into_v32(&format!("Error: unknown environment `{}`", from_v32(name))))];
+        }
+    };
+
+    let cols = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let elements = rows.iter().map(
+        |row| {
+            let mut row = row.iter().map(|cell| latex_to_math(cell)).collect::<Vec<Vec<Entity>>>();
+            row.resize(cols, vec![]);
+
+            row
+        }
+    ).collect::<Vec<Vec<Vec<Entity>>>>();
+
+    let matrix = if name == into_v32("cases") {
+        Entity::new_cases(elements)
+    } else if aligned {
+        Entity::Matrix(Matrix::new_aligned(elements))
+    } else {
+        Entity::new_matrix(elements)
+    };
+
+    match fences {
+        Some((open, close)) => vec![Entity::new_fenced(Some(open), Some(close), vec![matrix])],
+        None => vec![matrix]
+    }
+
+}
+
+// rows are separated by `\\`, and cells by `&`
+// separators inside braces or nested environments are ignored
+fn split_environment_body(body: &[u32]) -> Vec<Vec<Vec<u32>>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut cell_begin = 0;
+    let mut brace_depth = 0;
+    let mut environment_depth = 0;
+    let mut index = 0;
+
+    while index < body.len() {
+        let c = body[index];
+
+        if c == '\\' as u32 {
+            let (name, end_index) = read_command_name(body, index);
+
+            if name == into_v32("begin") {
+                environment_depth += 1;
+            }
+
+            else if name == into_v32("end") {
+                environment_depth -= 1;
+            }
+
+            // `\\`
+            else if name == [92] && brace_depth == 0 && environment_depth == 0 {
+                row.push(body[cell_begin..index].to_vec());
+                rows.push(std::mem::take(&mut row));
+                cell_begin = end_index;
+            }
+
+            index = end_index;
+            continue;
+        }
+
+        if c == '{' as u32 {
+            brace_depth += 1;
+        }
+
+        else if c == '}' as u32 {
+            brace_depth -= 1;
+        }
+
+        else if c == '&' as u32 && brace_depth == 0 && environment_depth == 0 {
+            row.push(body[cell_begin..index].to_vec());
+            cell_begin = index + 1;
+        }
+
+        index += 1;
+    }
+
+    row.push(body[cell_begin..].to_vec());
+
+    // `a \\ b \\` doesn't have an empty row at the end
+    if row.iter().any(|cell| cell.iter().any(|c| *c != ' ' as u32 && *c != '\n' as u32)) || rows.is_empty() {
+        rows.push(row);
+    }
+
+    rows
+}
+
+// `content[index]` is an opening character
+// `\{` and `\}` are not counted
+fn get_group_end_index(content: &[u32], index: usize, opening: u32, closing: u32) -> Option<usize> {
+    let mut depth = 0;
+    let mut curr_index = index;
+
+    while curr_index < content.len() {
+
+        if content[curr_index] == '\\' as u32 {
+            curr_index += 2;
+            continue;
+        }
+
+        if content[curr_index] == opening {
+            depth += 1;
+        }
+
+        else if content[curr_index] == closing {
+            depth -= 1;
+
+            if depth == 0 {
+                return Some(curr_index);
+            }
+
+        }
+
+        curr_index += 1;
+    }
+
+    None
+}
+
+fn skip_whitespaces(content: &[u32], mut index: usize) -> usize {
+
+    while index < content.len() && (content[index] == ' ' as u32 || content[index] == '\n' as u32 || content[index] == '\t' as u32) {
+        index += 1;
+    }
+
+    index
+}
+
+// `\varepsilon` and the others that are not in `α` ~ `ω`
+fn is_greek(name: &[u32]) -> bool {
+    [
+        into_v32("epsilon"), into_v32("vartheta"), into_v32("varpi"),
+        into_v32("varrho"), into_v32("phi")
+    ].contains(&name.to_vec())
+}
@@ -1,5 +1,8 @@
+mod chemistry;
 mod entity;
+mod latex;
 mod parse;
+mod unit;
 mod validate;
 
 #[cfg(test)]
@@ -7,12 +10,20 @@ mod testbench;
 
 use super::macros::predicate::read_macro;
 use super::parse::{get_code_span_marker_end_index, is_code_span_marker_begin, undo_code_span_escapes};
-use crate::escape::{render_backslash_escapes_raw, undo_html_escapes, BACKSLASH_ESCAPE_OFFSET};
-use crate::utils::{get_bracket_end_index, into_v32};
-use entity::Entity;
+use crate::escape::{escape_htmls, render_backslash_escapes_raw, render_html_escapes, undo_html_escapes, BACKSLASH_ESCAPE_OFFSET, META_CHARACTER_OFFSET};
+use crate::utils::{from_v32, get_bracket_end_index, into_v32, is_numeric};
+use entity::{Entity, matrix::Matrix};
+use latex::{find_latex_errors, latex_to_math};
 use lazy_static::lazy_static;
 use parse::md_to_math;
 use std::collections::HashSet;
+use validate::find_errors;
+
+pub use validate::MathError;
+
+// ASCII punctuations inside `[[math, latex]]` are escaped to this range, so that the other inline elements don't touch them
+// backslash escapes are not used, because `\frac` is already a backslash escape
+const LATEX_ESCAPE_OFFSET: u32 = META_CHARACTER_OFFSET + 0x100;
 
 lazy_static! {
 
@@ -48,7 +59,12 @@ lazy_static! {
     pub static ref ONE_ARG_FUNCTIONS: HashSet<Vec<u32>> = {
         let vec = vec![
             "text", "sqrt", "lim", "limit",
-            "hat", "bar", "dot", "tilde", "vec"
+            "hat", "bar", "dot", "tilde", "vec",
+            "abs", "norm", "ceil", "floor",
+            "overbrace", "underbrace",
+            "mathbb", "mathcal", "mathfrak", "mathbf",
+            "max", "min", "argmax", "argmin",
+            "ce", "unit"
         ];
         let mut result = HashSet::with_capacity(vec.len());
 
@@ -63,8 +79,28 @@ lazy_static! {
         let vec = vec![
             "sum", "prod", "sqrt", "root",
             "sup", "sub",
-            "frac", "cfrac", "bincoeff",
-            "int", "oint", "iint", "iiint"
+            "frac", "cfrac", "bincoeff", "binom",
+            "int", "oint", "iint", "iiint",
+            "overbrace", "underbrace",
+            "unit"
+        ];
+        let mut result = HashSet::with_capacity(vec.len());
+
+        for func in vec.into_iter() {
+            result.insert(into_v32(func));
+        }
+
+        result
+    };
+
+    // `sin x` -> `<mi>sin</mi><mo>&#8289;</mo><mi>x</mi>`
+    // they're not in `ZERO_ARG_FUNCTIONS` because the branches for `ZERO_ARG_FUNCTIONS` are generated by `parse.py`
+    pub static ref NAMED_OPERATORS: HashSet<Vec<u32>> = {
+        let vec = vec![
+            "sin", "cos", "tan", "sec", "csc", "cot",
+            "arcsin", "arccos", "arctan", "sinh", "cosh", "tanh",
+            "log", "ln", "lg", "exp", "det", "dim", "ker", "gcd", "lcm", "deg", "hom", "Pr",
+            "max", "min", "argmax", "argmin"
         ];
         let mut result = HashSet::with_capacity(vec.len());
 
@@ -104,15 +140,36 @@ lazy_static! {
 }
 
 pub struct Math {
-    entities: Vec<Entity>
+    entities: Vec<Entity>,
+
+    // the formula that the user wrote, and the positions of `errors` are in it
+    source: Vec<u32>,
+    errors: Vec<MathError>
 }
 
 impl Math {
 
     pub fn from_mdxt(content: &[u32]) -> Self {
         let entities = md_to_math(&render_backslash_escapes_raw(content));
+        let source = formula_source(content, false);
+        let errors = find_errors(&source);
+
+        Math { entities, source, errors }
+    }
+
+    pub fn from_latex(content: &[u32]) -> Self {
+        let source = formula_source(content, true);
+        let errors = find_latex_errors(&source);
+
+        Math { entities: latex_to_math(&source), source, errors }
+    }
+
+    pub fn errors(&self) -> &[MathError] {
+        &self.errors
+    }
 
-        Math { entities }
+    pub fn source(&self) -> String {
+        from_v32(&self.source)
     }
 
     pub fn to_math_ml(&self, xmlns: bool) -> Vec<u32> {
@@ -123,13 +180,171 @@ impl Math {
         };
 
         vec![
-            into_v32(&format!("<math{xmlns}>")),
+            into_v32(&format!("<math{xmlns} alttext=\"")),
+            alt_text(&self.entities),
+            vec![34, 62],  // into_v32("\">")
             self.entities.iter().map(
                 |entity| entity.to_math_ml()
             ).collect::<Vec<Vec<u32>>>().concat(),
             vec![60, 47, 109, 97, 116, 104, 62],  // into_v32("</math>")
         ].concat()
     }
+
+    // each `br` starts a new row, and `&` splits a row into columns that are aligned
+    pub fn to_block_math_ml(&self) -> Vec<u32> {
+        let rows = split_rows(&self.entities);
+
+        let entities = match &rows[..] {
+            [] => vec![],
+            [row] if row.len() == 1 => row[0].clone(),
+            _ => vec![Entity::Matrix(Matrix::new_aligned(rows))]
+        };
+
+        vec![
+            into_v32("<math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"block\" alttext=\""),
+            alt_text(&entities),
+            vec![34, 62],  // into_v32("\">")
+            entities.iter().map(
+                |entity| entity.to_math_ml()
+            ).collect::<Vec<Vec<u32>>>().concat(),
+            vec![60, 47, 109, 97, 116, 104, 62],  // into_v32("</math>")
+        ].concat()
+    }
+
+    /// `sqrt{sup{a}{2} + sup{b}{2}}` -> `sqrt(a^2 + b^2)`
+    pub fn to_plain_text(&self) -> String {
+        entity::vec_to_plain_text(&self.entities)
+    }
+
+    /// `sqrt{sup{a}{2} + sup{b}{2}}` -> `the square root of a squared plus b squared end root`
+    pub fn to_speech(&self) -> String {
+        entity::vec_to_speech(&self.entities)
+    }
+
+    // The screen readers read the hidden `<span>` instead of the MathML.
+    // `math_ml` is the result of `to_math_ml` or `to_block_math_ml`: `<math ...>...</math>`
+    pub fn with_speech(&self, math_ml: Vec<u32>, class_prefix: &str) -> Vec<u32> {
+        vec![
+            vec![60, 109, 97, 116, 104, 32, 97, 114, 105, 97, 45, 104, 105, 100, 100, 101, 110, 61, 34, 116, 114, 117, 101, 34],  // into_v32("<math aria-hidden=\"true\"")
+            math_ml[5..].to_vec(),
+            into_v32(&format!("<span class=\"{class_prefix}sr-only\">")),
+            render_html_escapes(&escape_htmls(&into_v32(&self.to_speech()))),
+            vec![60, 47, 115, 112, 97, 110, 62],  // into_v32("</span>")
+        ].concat()
+    }
+
+    // `math_ml` is wrapped in a `<span>` that shows the errors when hovered
+    // it's the same as `math_ml` if there's no error
+    pub fn with_error_span(&self, math_ml: Vec<u32>, class_prefix: &str) -> Vec<u32> {
+
+        if self.errors.is_empty() {
+            return math_ml;
+        }
+
+        let messages = self.errors.iter().map(
+            |error| error.to_string()
+        ).collect::<Vec<String>>().join("; ");
+
+        vec![
+            into_v32(&format!("<span class=\"{class_prefix}math-error\" title=\"")),
+            render_html_escapes(&escape_htmls(&into_v32(&messages))),
+            vec![34, 62],  // into_v32("\">")
+            math_ml,
+            vec![60, 47, 115, 112, 97, 110, 62],  // into_v32("</span>")
+        ].concat()
+    }
+
+}
+
+// undoes the escapes of the engine
+// backslash escapes are undone differently: MDxt math renders `\{` to `{`, but LaTeX uses `\{` as a command
+fn formula_source(content: &[u32], latex: bool) -> Vec<u32> {
+    let content = undo_html_escapes(content);
+
+    if latex {
+        content.iter().map(
+            |c| if LATEX_ESCAPE_OFFSET <= *c && *c < LATEX_ESCAPE_OFFSET + 128 {
+                vec![*c - LATEX_ESCAPE_OFFSET]
+            }
+
+            else if BACKSLASH_ESCAPE_OFFSET <= *c && *c < META_CHARACTER_OFFSET {
+                vec!['\\' as u32, *c - BACKSLASH_ESCAPE_OFFSET]
+            }
+
+            else {
+                vec![*c]
+            }
+        ).collect::<Vec<Vec<u32>>>().concat()
+    }
+
+    else {
+        render_backslash_escapes_raw(&content)
+    }
+
+}
+
+fn alt_text(entities: &[Entity]) -> Vec<u32> {
+    render_html_escapes(&escape_htmls(&into_v32(&entity::vec_to_plain_text(entities))))
+}
+
+// rows without any entity are ignored, so `br br` doesn't make an empty row
+fn split_rows(entities: &[Entity]) -> Vec<Vec<Vec<Entity>>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut cell = vec![];
+
+    for entity in entities.iter() {
+
+        match entity {
+            Entity::Br => {
+                row.push(std::mem::take(&mut cell));
+
+                if row.iter().any(|cell| !cell.is_empty()) {
+                    rows.push(std::mem::take(&mut row));
+                }
+
+                else {
+                    row.clear();
+                }
+
+            },
+
+            // `&=` is parsed to a single operator
+            Entity::Operator(operator) if operator.contains(&('&' as u32)) => {
+
+                for (index, part) in operator.split(|c| *c == '&' as u32).enumerate() {
+
+                    if index > 0 {
+                        row.push(std::mem::take(&mut cell));
+                    }
+
+                    if !part.is_empty() {
+                        cell.push(Entity::new_operator(part.to_vec()));
+                    }
+
+                }
+
+            },
+            _ => {
+                cell.push(entity.clone());
+            }
+        }
+
+    }
+
+    row.push(cell);
+
+    if row.iter().any(|cell| !cell.is_empty()) {
+        rows.push(row);
+    }
+
+    let cols = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+
+    for row in rows.iter_mut() {
+        row.resize(cols, vec![]);
+    }
+
+    rows
 }
 
 // This escape only works inside `[[math]]` macros
@@ -154,6 +369,20 @@ fn escape_special_characters(content: &[u32]) -> Vec<u32> {
     undo_code_span_escapes(&result)
 }
 
+// `[[math, latex]]` uses `\` for its commands, so it doesn't touch backslash escapes
+// it's idempotent: the math blocks are escaped again when the inline elements inside a table are parsed
+fn escape_latex_characters(content: &[u32]) -> Vec<u32> {
+    let content = undo_code_span_escapes(&undo_html_escapes(content));
+
+    content.iter().map(
+        |c| if *c < 128 && (*c as u8).is_ascii_punctuation() {
+            LATEX_ESCAPE_OFFSET + *c
+        } else {
+            *c
+        }
+    ).collect()
+}
+
 pub fn escape_inside_math_blocks(content: Vec<u32>) -> Vec<u32> {
     let mut result = vec![];
     let mut index = 0;
@@ -168,9 +397,10 @@ pub fn escape_inside_math_blocks(content: Vec<u32>) -> Vec<u32> {
 
         match read_macro(&content, index) {
 
-            // it met `[[math]]`
+            // it met `[[math]]` or `[[math, latex]]`
             // into_v32("math") -> [109, 97, 116, 104]
-            Some(macro_name) if macro_name == &[109, 97, 116, 104] => {
+            Some(macro_content) if macro_content.split(|c| *c == ',' as u32).next() == Some(&[109, 97, 116, 104]) => {
+                let is_latex = macro_content.split(|c| *c == ',' as u32).any(|argument| argument == into_v32("latex"));
                 let mut end_index = index + 5;
 
                 // seek `[[/math]]`
@@ -179,7 +409,11 @@ pub fn escape_inside_math_blocks(content: Vec<u32>) -> Vec<u32> {
                     match read_macro(&content, end_index) {
                         Some(macro_name) if macro_name == &[47, 109, 97, 116, 104] => {  // into_v32("/math") -> [47, 109, 97, 116, 104]
                             let math_begin_index = get_bracket_end_index(&content, index).unwrap() + 1;
-                            let escaped_math = escape_special_characters(&content[math_begin_index..end_index]);
+                            let escaped_math = if is_latex {
+                                escape_latex_characters(&content[math_begin_index..end_index])
+                            } else {
+                                escape_special_characters(&content[math_begin_index..end_index])
+                            };
 
                             result.push(content[last_index..math_begin_index].to_vec());
                             result.push(escaped_math);
@@ -211,6 +445,150 @@ pub fn escape_inside_math_blocks(content: Vec<u32>) -> Vec<u32> {
     }
 }
 
-pub fn render_math(content: &[u32]) -> Vec<u32> {
-    Math::from_mdxt(content).to_math_ml(true)
+// `$x^2$` -> `[[math, latex]]x^2[[/math]]`
+// like pandoc, the opening `$` must be followed by a non-space character, and the closing `$` must be preceded by a non-space character and not followed by a digit
+// so that `$5 and $10` is not a math
+pub fn dollar_signs_to_math_macros(content: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(content.len());
+    let mut index = 0;
+
+    while index < content.len() {
+
+        if is_code_span_marker_begin(content, index) {
+            let end_index = get_code_span_marker_end_index(content, index);
+            result.extend_from_slice(&content[index..end_index]);
+            index = end_index;
+            continue;
+        }
+
+        if content[index] == '$' as u32 {
+
+            match get_closing_dollar_sign_index(content, index) {
+                Some(end_index) => {
+                    result.extend(into_v32("[[math, latex]]"));
+                    result.extend_from_slice(&content[(index + 1)..end_index]);
+                    result.extend(into_v32("[[/math]]"));
+                    index = end_index + 1;
+                },
+
+                // `$$` is not an inline math
+                None if content.get(index + 1) == Some(&('$' as u32)) => {
+                    result.extend_from_slice(&content[index..(index + 2)]);
+                    index += 2;
+                },
+                None => {
+                    result.push(content[index]);
+                    index += 1;
+                }
+            }
+
+            continue;
+        }
+
+        result.push(content[index]);
+        index += 1;
+    }
+
+    result
+}
+
+fn get_closing_dollar_sign_index(content: &[u32], index: usize) -> Option<usize> {
+
+    match content.get(index + 1) {
+        Some(c) if *c == '$' as u32 || is_whitespace(c) => { return None; },
+        None => { return None; },
+        _ => {}
+    }
+
+    let mut end_index = index + 2;
+
+    while end_index < content.len() {
+
+        // dollar signs inside code spans are not closings
+        if is_code_span_marker_begin(content, end_index) {
+            end_index = get_code_span_marker_end_index(content, end_index);
+            continue;
+        }
+
+        if content[end_index] == '$' as u32
+            && !is_whitespace(&content[end_index - 1])
+            && !content.get(end_index + 1).map(is_numeric).unwrap_or(false)
+        {
+            return Some(end_index);
+        }
+
+        end_index += 1;
+    }
+
+    None
+}
+
+fn is_whitespace(c: &u32) -> bool {
+    *c == ' ' as u32 || *c == '\n' as u32 || *c == '\t' as u32
+}
+
+/// The plain-text form of a math, for the outputs that don't support MathML: `sqrt{sup{a}{2} + sup{b}{2}}` -> `sqrt(a^2 + b^2)`.
+/// `content` is the inside of a `[[math]]` macro, or a LaTeX formula if `latex` is true.
+pub fn math_to_plain_text(content: &str, latex: bool) -> String {
+
+    if latex {
+        Math::from_latex(&into_v32(content)).to_plain_text()
+    }
+
+    else {
+        Math::from_mdxt(&into_v32(content)).to_plain_text()
+    }
+
+}
+
+/// An English reading of a math: `sqrt{sup{a}{2} + sup{b}{2}}` -> `the square root of a squared plus b squared end root`.
+/// `content` is the inside of a `[[math]]` macro, or a LaTeX formula if `latex` is true.
+pub fn math_to_speech(content: &str, latex: bool) -> String {
+
+    if latex {
+        Math::from_latex(&into_v32(content)).to_speech()
+    }
+
+    else {
+        Math::from_mdxt(&into_v32(content)).to_speech()
+    }
+
+}
+
+/// Problems in a math formula: unbalanced braces, wrong numbers of arguments, unknown LaTeX commands, ...
+/// `content` is the inside of a `[[math]]` macro, or a LaTeX formula if `latex` is true.
+/// An empty vector means the formula is fine.
+pub fn find_math_errors(content: &str, latex: bool) -> Vec<MathError> {
+
+    if latex {
+        Math::from_latex(&into_v32(content)).errors
+    }
+
+    else {
+        Math::from_mdxt(&into_v32(content)).errors
+    }
+
+}
+
+pub fn render_math(content: &[u32], latex: bool, speech: bool, error_span: bool, class_prefix: &str) -> Vec<u32> {
+    let math = if latex {
+        Math::from_latex(content)
+    } else {
+        Math::from_mdxt(content)
+    };
+
+    let math_ml = if speech {
+        math.with_speech(math.to_math_ml(true), class_prefix)
+    } else {
+        math.to_math_ml(true)
+    };
+
+    if error_span {
+        math.with_error_span(math_ml, class_prefix)
+    }
+
+    else {
+        math_ml
+    }
+
 }
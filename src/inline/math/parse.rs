@@ -1,6 +1,8 @@
-use super::entity::{Entity, matrix::try_parse_matrix, parse_raw_data};
+use super::chemistry::parse_chemistry;
+use super::entity::{Entity, font::FontVariant, matrix::try_parse_matrix, parse_raw_data};
+use super::unit::{parse_quantity, parse_unit};
 use super::validate::is_valid;
-use super::{ZERO_ARG_FUNCTIONS, ONE_ARG_FUNCTIONS, TWO_ARG_FUNCTIONS, THREE_ARG_FUNCTIONS, FIVE_ARG_FUNCTIONS};
+use super::{ZERO_ARG_FUNCTIONS, ONE_ARG_FUNCTIONS, TWO_ARG_FUNCTIONS, THREE_ARG_FUNCTIONS, FIVE_ARG_FUNCTIONS, NAMED_OPERATORS};
 use crate::utils::{get_curly_brace_end_index, into_v32, is_alphabet, remove_whitespaces};
 
 pub fn md_to_math(content: &[u32]) -> Vec<Entity> {
@@ -101,10 +103,22 @@ pub fn parse(word: &[u32], arguments: &Vec<Vec<u32>>) -> Entity {
     else if word == &[109, 97, 116] {
         match try_parse_matrix(arguments) {
             Ok(elements) => Entity::new_matrix(elements),
-            Err(e) => Entity::RawString(into_v32(&format!("Error: {e}"))),
+            Err((_, e)) => Entity::RawString(into_v32(&format!("Error: {e}"))),
         }
     }
 
+    // "cases"
+    else if word == &[99, 97, 115, 101, 115] {
+        match try_parse_matrix(arguments) {
+            Ok(rows) => Entity::new_cases(rows),
+            Err((_, e)) => Entity::RawString(into_v32(&format!("Error: {e}"))),
+        }
+    }
+
+    else if NAMED_OPERATORS.contains(word) && arguments.is_empty() {
+        Entity::new_function(word.to_vec())
+    }
+
     else if ZERO_ARG_FUNCTIONS.contains(word) && arguments.is_empty() {
 
         if *word == into_v32("br") {
@@ -508,6 +522,15 @@ pub fn parse(word: &[u32], arguments: &Vec<Vec<u32>>) -> Entity {
             Entity::RawString(arguments[0].clone())
         }
 
+        // `parse` returns a single entity, so the entities of `ce{H2O}` and `unit{m/s}` are grouped in a `Fenced` without fences: `<mrow>`
+        else if *word == into_v32("ce") {
+            Entity::new_fenced(None, None, parse_chemistry(&arguments[0]))
+        }
+
+        else if *word == into_v32("unit") {
+            Entity::new_fenced(None, None, parse_unit(&arguments[0]))
+        }
+
         else if *word == into_v32("lim") || *word == into_v32("limit") {
             Entity::new_underover(
                 vec![Entity::new_identifier(into_v32("lim"))],
@@ -517,6 +540,38 @@ pub fn parse(word: &[u32], arguments: &Vec<Vec<u32>>) -> Entity {
             )
         }
 
+        // `argmax{x in S}`
+        else if NAMED_OPERATORS.contains(word) {
+            Entity::new_underover(
+                vec![Entity::new_identifier(word.to_vec())],
+                md_to_math(&arguments[0]),
+                vec![],
+                false
+            )
+        }
+
+        else if let Some(variant) = FontVariant::from_name(word) {
+            Entity::new_font(variant, arguments[0].clone())
+        }
+
+        else if *word == into_v32("overbrace") || *word == into_v32("underbrace") {
+            Entity::new_brace(md_to_math(&arguments[0]), vec![], *word == into_v32("overbrace"))
+        }
+
+        else if *word == into_v32("abs") || *word == into_v32("norm") || *word == into_v32("ceil") || *word == into_v32("floor") {
+            let (open, close) = if *word == into_v32("abs") {
+                (124, 124)  // '|'
+            } else if *word == into_v32("norm") {
+                (8214, 8214)  // '‖'
+            } else if *word == into_v32("ceil") {
+                (8968, 8969)  // '⌈', '⌉'
+            } else {
+                (8970, 8971)  // '⌊', '⌋'
+            };
+
+            Entity::new_fenced(Some(open), Some(close), md_to_math(&arguments[0]))
+        }
+
         else {
             let operator = if *word == into_v32("hat") {
                 '^' as u32
@@ -575,6 +630,28 @@ pub fn parse(word: &[u32], arguments: &Vec<Vec<u32>>) -> Entity {
             )
         }
 
+        else if *word == into_v32("binom") {
+            Entity::new_fenced(
+                Some(40),  // '('
+                Some(41),  // ')'
+                vec![Entity::new_fraction(
+                    md_to_math(&arguments[0]),
+                    md_to_math(&arguments[1]),
+                    false,
+                    true
+                )]
+            )
+        }
+
+        else if *word == into_v32("overbrace") || *word == into_v32("underbrace") {
+            Entity::new_brace(md_to_math(&arguments[0]), md_to_math(&arguments[1]), *word == into_v32("overbrace"))
+        }
+
+        // `unit{9.8}{m/s^2}`
+        else if *word == into_v32("unit") {
+            Entity::new_fenced(None, None, parse_quantity(md_to_math(&arguments[0]), &arguments[1]))
+        }
+
         else if *word == into_v32("sub") {
             Entity::new_script(
                 md_to_math(&arguments[0]),
@@ -664,7 +741,19 @@ pub fn parse(word: &[u32], arguments: &Vec<Vec<u32>>) -> Entity {
 
 }
 
-pub fn get_arguments(content: &[u32], mut index: usize) -> (Vec<Vec<u32>>, usize) {  // (Vec<argument>, end_index)
+pub fn get_arguments(content: &[u32], index: usize) -> (Vec<Vec<u32>>, usize) {  // (Vec<argument>, end_index)
+    let (indices, end_index) = get_argument_indices(content, index);
+
+    (
+        indices.into_iter().map(
+            |(start_index, end_index)| content[start_index..end_index].to_vec()
+        ).collect(),
+        end_index
+    )
+}
+
+// `content[start_index..end_index]` is an argument, without the curly braces
+pub fn get_argument_indices(content: &[u32], mut index: usize) -> (Vec<(usize, usize)>, usize) {  // (Vec<(start_index, end_index)>, end_index)
 
     let mut result = vec![];
 
@@ -690,7 +779,7 @@ pub fn get_arguments(content: &[u32], mut index: usize) -> (Vec<Vec<u32>>, usize
                 }
             };
 
-            result.push(content[(index + 1)..arg_end_index].to_vec());
+            result.push((index + 1, arg_end_index));
             index = arg_end_index + 1;
         }
 
@@ -1,5 +1,5 @@
 use super::{Math, md_to_math};
-use super::{ZERO_ARG_FUNCTIONS, ONE_ARG_FUNCTIONS, TWO_ARG_FUNCTIONS, THREE_ARG_FUNCTIONS, FIVE_ARG_FUNCTIONS};
+use super::{ZERO_ARG_FUNCTIONS, ONE_ARG_FUNCTIONS, TWO_ARG_FUNCTIONS, THREE_ARG_FUNCTIONS, FIVE_ARG_FUNCTIONS, NAMED_OPERATORS};
 use crate::utils::{from_v32, into_v32, remove_whitespaces};
 use crate::{render_to_html, RenderOption, RenderResult};
 use crate::testbench::HXML_LOCK;
@@ -7,14 +7,14 @@ use crate::testbench::HXML_LOCK;
 fn samples() -> Vec<(Vec<u32>, Vec<u32>)> {  // Vec<(test_case, answer)>
     let result = vec![
 ("sum{x}", "
-<math>
+<math alttext=\"sum{x}\">
     <mi>sum</mi>
     <mo>{</mo>
     <mi>x</mi>
     <mo>}</mo>
 </math>
 "), ("sqrt{3}{4}{5}", "
-<math>
+<math alttext=\"sqrt{3}{4}{5}\">
     <mi>sqrt</mi>
     <mo>{</mo>
     <mn>3</mn>
@@ -27,7 +27,7 @@ fn samples() -> Vec<(Vec<u32>, Vec<u32>)> {  // Vec<(test_case, answer)>
     <mo>}</mo>
 </math>
 "), ("frac{1}{1}{1}", "
-<math>
+<math alttext=\"frac{1}{1}{1}\">
     <mi>frac</mi>
     <mo>{</mo>
     <mn>1</mn>
@@ -40,7 +40,7 @@ fn samples() -> Vec<(Vec<u32>, Vec<u32>)> {  // Vec<(test_case, answer)>
     <mo>}</mo>
 </math>
 "), ("sum{n=1}{+inf} frac{1}{sup{n}{2}} = frac{sup{pi}{2}}{6}", "
-<math>
+<math alttext=\"∑_(n = 1)^(+∞) 1/n^2 = π^2/6\">
     <munderover displaystyle=\"true\">
         <mo>∑</mo>
         <mrow><mi>n</mi><mo>=</mo><mn>1</mn></mrow>
@@ -63,7 +63,7 @@ fn samples() -> Vec<(Vec<u32>, Vec<u32>)> {  // Vec<(test_case, answer)>
     </mfrac>
 </math>
 "), ("frac{-b pm sqrt{sup{b}{2} - 4 a c}}{2 a}", "
-<math>
+<math alttext=\"(-b ± sqrt(b^2 - 4ac))/(2a)\">
     <mfrac displaystyle=\"false\">
         <mrow>
             <mo>-</mo>
@@ -87,7 +87,7 @@ fn samples() -> Vec<(Vec<u32>, Vec<u32>)> {  // Vec<(test_case, answer)>
     </mfrac>
 </math>
 "), ("lim{n rightarrow +inf} sup{(1 + frac{1}{n})}{n} = e simeq 2.718", "
-<math>
+<math alttext=\"lim_(n → +∞) (1 + 1/n)^n = e ≃ 2.718\">
     <munder>
         <mi>lim</mi>
         <mrow>
@@ -116,7 +116,7 @@ fn samples() -> Vec<(Vec<u32>, Vec<u32>)> {  // Vec<(test_case, answer)>
     <mn>2.718</mn>
 </math>
 "), ("(bincoeff{5}{2}) = multiscript{C}{}{}{5}{2} = frac{5!}{2!3!} = 10", "
-<math>
+<math alttext=\"(binom(5, 2)) = _5 C_2 = (5!)/(2!3!) = 10\">
     <mo>(</mo>
     <mfrac displaystyle=\"false\" linethickness=\"0\">
         <mn>5</mn>
@@ -149,7 +149,7 @@ fn samples() -> Vec<(Vec<u32>, Vec<u32>)> {  // Vec<(test_case, answer)>
     <mn>10</mn>
 </math>
 "), ("a circ b = |a| space |b| space cos theta", "
-<math>
+<math alttext=\"a ∘ b = |a| |b| cos θ\">
     <mi>a</mi>
     <mo>&#8728;</mo>
     <mi>b</mi>
@@ -163,10 +163,11 @@ fn samples() -> Vec<(Vec<u32>, Vec<u32>)> {  // Vec<(test_case, answer)>
     <mo>|</mo>
     <mspace width=\"0.333em\"/>
     <mi>cos</mi>
+    <mo>&#8289;</mo>
     <mi>&#952;</mi>
 </math>
 "), ("alpha beta gamma Alpha Beta Gamma", "
-<math>
+<math alttext=\"αβγΑΒΓ\">
     <mi>&#945;</mi>
     <mi>&#946;</mi>
     <mi>&#947;</mi>
@@ -175,19 +176,19 @@ fn samples() -> Vec<(Vec<u32>, Vec<u32>)> {  // Vec<(test_case, answer)>
     <mi>&#915;</mi>
 </math>
 "), ("broken sqrt{1", "
-<math>
+<math alttext=\"brokensqrt{1\">
     <mi>broken</mi>
     <mi>sqrt</mi>
     <mo>{</mo>
     <mn>1</mn>
 </math>
 "), ("text{delta} delta", "
-<math>
+<math alttext=\"deltaδ\">
     <mtext>delta</mtext>
     <mi>&#948;</mi>
 </math>
 "), ("sub{Phi}{E} = oint{S}{} E circ d A", "
-<math>
+<math alttext=\"Φ_E = ∮_S E ∘ dA\">
     <msub>
         <mi>&#934;</mi>
         <mi>E</mi>
@@ -203,7 +204,7 @@ fn samples() -> Vec<(Vec<u32>, Vec<u32>)> {  // Vec<(test_case, answer)>
     <mi>A</mi>
 </math>
 "), ("1+1=2", "
-<math>
+<math alttext=\"1 + 1 = 2\">
     <mn>1</mn>
     <mo>+</mo>
     <mn>1</mn>
@@ -211,7 +212,7 @@ fn samples() -> Vec<(Vec<u32>, Vec<u32>)> {  // Vec<(test_case, answer)>
     <mn>2</mn>
 </math>
 "), ("a space b sspace c ssspace d", "
-<math>
+<math alttext=\"a b c d\">
     <mi>a</mi>
     <mspace width=\"0.333em\"/>
     <mi>b</mi>
@@ -221,7 +222,7 @@ fn samples() -> Vec<(Vec<u32>, Vec<u32>)> {  // Vec<(test_case, answer)>
     <mi>d</mi>
 </math>
 "), ("root{4}{sup{|a|}{4} + sup{|b|}{4} + sup{|c|}{4} + sup{|d|}{4}} leq sup{(a+b+c+d)}{4} < inf", "
-<math>
+<math alttext=\"root(4, (|a|)^4 + (|b|)^4 + (|c|)^4 + (|d|)^4) ≤ (a + b + c + d)^4 &lt; ∞\">
     <mroot>
         <mrow>
             <msup>
@@ -281,7 +282,7 @@ fn samples() -> Vec<(Vec<u32>, Vec<u32>)> {  // Vec<(test_case, answer)>
     <mn>&#8734;</mn>
 </math>
 "), ("hat{1} bar{1} dot{1} tilde{1} vec{1}", "
-<math>
+<math alttext=\"hat(1)bar(1)dot(1)tilde(1)vec(1)\">
     <mover>
         <mn>1</mn>
         <mo>&#94;</mo>
@@ -304,11 +305,11 @@ fn samples() -> Vec<(Vec<u32>, Vec<u32>)> {  // Vec<(test_case, answer)>
     </mover>
 </math>
 "), ("mat{}{}{}", "
-<math>
+<math alttext=\"Error: Empty Row\">
     <mtext>Error: Empty Row</mtext>
 </math>
 "), ("(mat{{1}{2}{3}}{{4}{5}{6}}{{7}{9}{10}})", "
-<math>
+<math alttext=\"([1, 2, 3; 4, 5, 6; 7, 9, 10])\">
     <mo>(</mo>
     <mtable>
         <mtr>
@@ -328,7 +329,88 @@ fn samples() -> Vec<(Vec<u32>, Vec<u32>)> {  // Vec<(test_case, answer)>
         </mtr>
     </mtable>
     <mo>)</mo>
-</math>"), ("", "<math></math>")
+</math>"), ("abs{x} + norm{v} + floor{y} + ceil{z}", "
+<math alttext=\"|x| + ‖v‖ + ⌊y⌋ + ⌈z⌉\">
+    <mrow><mo fence=\"true\" stretchy=\"true\">&#124;</mo><mi>x</mi><mo fence=\"true\" stretchy=\"true\">&#124;</mo></mrow>
+    <mo>+</mo>
+    <mrow><mo fence=\"true\" stretchy=\"true\">&#8214;</mo><mi>v</mi><mo fence=\"true\" stretchy=\"true\">&#8214;</mo></mrow>
+    <mo>+</mo>
+    <mrow><mo fence=\"true\" stretchy=\"true\">&#8970;</mo><mi>y</mi><mo fence=\"true\" stretchy=\"true\">&#8971;</mo></mrow>
+    <mo>+</mo>
+    <mrow><mo fence=\"true\" stretchy=\"true\">&#8968;</mo><mi>z</mi><mo fence=\"true\" stretchy=\"true\">&#8969;</mo></mrow>
+</math>
+"), ("f(x) = cases{{x}{x geq 0}}{{-x}{text{otherwise}}}", "
+<math alttext=\"f(x) = cases(x, x ≥ 0; -x, otherwise)\">
+    <mi>f</mi>
+    <mo>(</mo>
+    <mi>x</mi>
+    <mo>)</mo>
+    <mo>=</mo>
+    <mrow>
+        <mo fence=\"true\" stretchy=\"true\">&#123;</mo>
+        <mtable columnalign=\"left left\" columnspacing=\"1em\">
+            <mtr>
+                <mtd><mi>x</mi></mtd>
+                <mtd><mi>x</mi><mo>&#8805;</mo><mn>0</mn></mtd>
+            </mtr>
+            <mtr>
+                <mtd><mo>-</mo><mi>x</mi></mtd>
+                <mtd><mtext>otherwise</mtext></mtd>
+            </mtr>
+        </mtable>
+    </mrow>
+</math>
+"), ("cases{x}{y}", "
+<math alttext=\"Error: A row has to start with `{`: x\">
+    <mtext>Error: A row has to start with `{`: x</mtext>
+</math>
+"), ("overbrace{a + b}{n} = underbrace{c}", "
+<math alttext=\"overbrace(a + b, n) = underbrace(c)\">
+    <mover>
+        <mover accent=\"true\">
+            <mrow><mi>a</mi><mo>+</mo><mi>b</mi></mrow>
+            <mo stretchy=\"true\">&#9182;</mo>
+        </mover>
+        <mi>n</mi>
+    </mover>
+    <mo>=</mo>
+    <munder accentunder=\"true\">
+        <mi>c</mi>
+        <mo stretchy=\"true\">&#9183;</mo>
+    </munder>
+</math>
+"), ("x in mathbb{R}, F in mathcal{F}, mathfrak{g} mathbf{v1}", "
+<math alttext=\"x ∈ ℝ, F ∈ ℱ, 𝔤𝐯𝟏\">
+    <mi>x</mi>
+    <mo>&#8712;</mo>
+    <mi>&#8477;</mi>
+    <mo>,</mo>
+    <mi>F</mi>
+    <mo>&#8712;</mo>
+    <mi>&#8497;</mi>
+    <mo>,</mo>
+    <mi>&#120100;</mi>
+    <mi>&#119855;&#120783;</mi>
+</math>
+"), ("binom{n}{k} argmax{x in S} log x", "
+<math alttext=\"binom(n, k)argmax_(x ∈ S) log x\">
+    <mrow>
+        <mo fence=\"true\" stretchy=\"true\">&#40;</mo>
+        <mfrac displaystyle=\"false\" linethickness=\"0\">
+            <mi>n</mi>
+            <mi>k</mi>
+        </mfrac>
+        <mo fence=\"true\" stretchy=\"true\">&#41;</mo>
+    </mrow>
+    <munder>
+        <mi>argmax</mi>
+        <mrow><mi>x</mi><mo>&#8712;</mo><mi>S</mi></mrow>
+    </munder>
+    <mi>log</mi>
+    <mo>&#8289;</mo>
+    <mi>x</mi>
+</math>
+"), ("", "<math alttext=\"\"></math>")
     ];
 
     result.iter().map(
@@ -468,6 +550,22 @@ fn render_math_reference() -> Vec<u32> {
         )));
     }
 
+    result.push(into_v32("\n\n## Named Operators\n\n"));
+    result.push(into_v32("They're rendered upright, with a space between the operator and its argument. `max`, `min`, `argmax` and `argmin` take an optional argument, which is rendered under the operator."));
+
+    let mut entities = NAMED_OPERATORS.iter().map(|e| from_v32(e)).collect::<Vec<String>>();
+    entities.sort();
+
+    for entity in entities.iter() {
+        result.push(into_v32(&format!(
+            "\n\n### {entity}\n\n`[[math]]{entity} x[[/math]]` [[char=rarr]] [[math]]{entity} x[[/math]]"
+        )));
+    }
+
+    result.push(into_v32("\n\n## Cases\n\n"));
+    result.push(into_v32("Each row of `cases` has two cells, like `mat`.\n\n"));
+    result.push(into_v32("`[[math]]abs{x} = cases{{x}{x geq 0}}{{-x}{text{otherwise}}}[[/math]]` [[char=rarr]] [[math]]abs{x} = cases{{x}{x geq 0}}{{-x}{text{otherwise}}}[[/math]]"));
+
     result.push(into_v32("\n\n## Matrices\n\n"));
     let mut mats = vec![];
     mats.push("{{1}{2}{3}}{{4}{5}{6}}{{7}{8}{9}}".to_string());
@@ -542,3 +640,273 @@ fn md_to_math_test() {
     assert!(md_to_math(&[]).is_empty());
     assert!(md_to_math(&into_v32("   ")).is_empty());
 }
+
+// (LaTeX, MDxt): both must be parsed to the same tree
+fn latex_samples() -> Vec<(Vec<u32>, Vec<u32>)> {
+    let result = vec![
+        ("\\frac{a+b}{2}", "frac{a+b}{2}"),
+        ("\\dfrac 1 2", "cfrac{1}{2}"),
+        ("x^2 + y_{i}", "sup{x}{2} + sub{y}{i}"),
+        ("x_i^{n}", "subsup{x}{i}{n}"),
+        ("\\sqrt{x} + \\sqrt[3]{y}", "sqrt{x} + root{3}{y}"),
+        ("\\sum_{i=1}^{n} i", "sum{i=1}{n} i"),
+        ("\\int_0^1 x", "int{0}{1} x"),
+        ("\\hat{x} \\vec y", "hat{x} vec{y}"),
+        ("\\alpha \\Omega \\infty \\times", "alpha Omega infty times"),
+        ("\\begin{matrix} a & b \\\\ c & d \\end{matrix}", "mat{{a}{b}}{{c}{d}}"),
+        ("\\left| x \\right| + \\left\\lceil y \\right\\rceil", "abs{x} + ceil{y}"),
+        ("\\binom{n}{k} \\sin x", "binom{n}{k} sin x"),
+        ("\\mathbb{R} \\mathcal{L}", "mathbb{R} mathcal{L}"),
+        ("\\overbrace{a+b}^{n} \\underbrace{c}", "overbrace{a+b}{n} underbrace{c}"),
+        ("\\max_{x} \\operatorname*{argmax}_{y}", "max{x} argmax{y}"),
+        ("\\begin{cases} x & x > 0 \\\\ -x & \\text{otherwise} \\end{cases}", "cases{{x}{x > 0}}{{-x}{text{otherwise}}}"),
+        ("\\text{if } x", "text{if } x"),
+    ];
+
+    result.into_iter().map(
+        |(latex, mdxt)| (into_v32(latex), into_v32(mdxt))
+    ).collect()
+}
+
+#[test]
+fn latex_test() {
+
+    for (latex, mdxt) in latex_samples() {
+        let rendered = Math::from_latex(&latex).to_math_ml(false);
+        let answer = Math::from_mdxt(&mdxt).to_math_ml(false);
+
+        if rendered != answer {
+            panic!(
+                "input: {}\nanswer: {}\noutput: {}",
+                from_v32(&latex),
+                from_v32(&answer),
+                from_v32(&rendered),
+            );
+        }
+
+    }
+
+}
+
+#[test]
+fn dollar_math_test() {
+    let md = "
+[[math, latex]]\\frac{a}{b} < c[[/math]], $x^*$ costs $5 and `$x$`.
+
+$$
+a &= b \\\\
+  &= c
+$$
+";
+    let mut render_option = RenderOption::default();
+    render_option.set_dollar_math(true);
+
+    let answer = "
+<p>
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"a/b &lt; c\"><mfrac displaystyle=\"false\"><mi>a</mi><mi>b</mi></mfrac><mo>&lt;</mo><mi>c</mi></math>,
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"x^*\"><msup><mi>x</mi><mo>*</mo></msup></math>
+    costs $5 and <code class=\"inline-code-span\">$x$</code>.
+</p>
+<div class=\"math-block\">
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"block\" alttext=\"a = b; = c\">
+        <mtable displaystyle=\"true\" columnalign=\"right left\" columnspacing=\"0em\">
+            <mtr><mtd><mi>a</mi></mtd><mtd><mo>=</mo><mi>b</mi></mtd></mtr>
+            <mtr><mtd></mtd><mtd><mo>=</mo><mi>c</mi></mtd></mtr>
+        </mtable>
+    </math>
+</div>
+";
+    let rendered = render_to_html(md, render_option.clone()).content;
+
+    if remove_whitespaces(&into_v32(&rendered)) != remove_whitespaces(&into_v32(answer)) {
+        panic!("{rendered}");
+    }
+
+    // without the option, dollar signs are just characters
+    render_option.set_dollar_math(false);
+    assert!(!render_to_html("$x$", render_option).content.contains("<math"));
+}
+
+#[test]
+fn speech_test() {
+    let samples = vec![
+        // (mdxt, plain text, speech)
+        ("sup{a}{2} + sup{b}{2} = sup{c}{2}", "a^2 + b^2 = c^2", "a squared plus b squared equals c squared"),
+        ("sqrt{sup{a}{2} + sup{b}{2}}", "sqrt(a^2 + b^2)", "the square root of a squared plus b squared end root"),
+        ("root{3}{x}", "root(3, x)", "the cube root of x"),
+        ("frac{1}{2} + frac{a + b}{c}", "1/2 + (a + b)/c", "1 over 2 plus the fraction a plus b over c end fraction"),
+        ("sum{i = 1}{n} i", "∑_(i = 1)^n i", "the sum from i equals 1 to n of i"),
+        ("lim{x rightarrow 0} frac{sin x}{x}", "lim_(x → 0) (sin x)/x", "the limit as x goes to 0 of the fraction sine x over x end fraction"),
+        ("abs{x} + norm{v} + floor{y}", "|x| + ‖v‖ + ⌊y⌋", "the absolute value of x plus the norm of v plus the floor of y"),
+        ("binom{n}{k}", "binom(n, k)", "n choose k"),
+        ("mat{{a}{b}}{{c}{d}}", "[a, b; c, d]", "the 2 by 2 matrix; row 1: a, b; row 2: c, d; end matrix"),
+        ("hat{x} + vec{v}", "hat(x) + vec(v)", "x hat plus vector v"),
+        ("subsup{x}{i}{2} - 1", "x_i^2 - 1", "x sub i squared minus 1"),
+        ("-x lt mathbb{R}", "-x < ℝ", "minus x is less than double-struck R"),
+        ("x &= 1 br y &= 2", "x = 1; y = 2", "x equals 1 new line y equals 2"),
+    ];
+
+    for (mdxt, plain_text, speech) in samples.iter() {
+        let math = Math::from_mdxt(&into_v32(mdxt));
+
+        assert_eq!(math.to_plain_text(), *plain_text, "plain text of `{mdxt}`");
+        assert_eq!(math.to_speech(), *speech, "speech of `{mdxt}`");
+    }
+
+    assert_eq!(crate::math_to_plain_text("\\frac{-b \\pm \\sqrt{b^2 - 4ac}}{2a}", true), "(-b ± sqrt(b^2 - 4ac))/(2a)");
+    assert_eq!(crate::math_to_speech("x_{i+1}^{n}", true), "x sub i plus 1 end sub to the power of n");
+
+    let mut render_option = RenderOption::default();
+    render_option.set_math_speech(true);
+    render_option.set_class_prefix(String::from("mdxt-"));
+
+    let md = "[[math]]sup{a}{2} lt b[[/math]]
+
+[[math]]
+x = 1 br
+y = 2
+[[/math]]
+";
+    let answer = "
+<p>
+    <math aria-hidden=\"true\" xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"a^2 &lt; b\"><msup><mi>a</mi><mn>2</mn></msup><mo>&#60;</mo><mi>b</mi></math>
+    <span class=\"mdxt-sr-only\">a squared is less than b</span>
+</p>
+<div class=\"mdxt-math-block\">
+    <math aria-hidden=\"true\" xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"block\" alttext=\"x = 1; y = 2\">
+        <mtable displaystyle=\"true\" columnalign=\"right\">
+            <mtr><mtd><mi>x</mi><mo>=</mo><mn>1</mn></mtd></mtr>
+            <mtr><mtd><mi>y</mi><mo>=</mo><mn>2</mn></mtd></mtr>
+        </mtable>
+    </math>
+    <span class=\"mdxt-sr-only\">x equals 1 new line y equals 2</span>
+</div>
+";
+    let rendered = render_to_html(md, render_option).content;
+
+    if remove_whitespaces(&into_v32(&rendered)) != remove_whitespaces(&into_v32(answer)) {
+        panic!("{rendered}");
+    }
+
+}
+
+#[test]
+fn math_error_test() {
+    let samples = vec![
+        // (formula, latex, [(position, message)])
+        ("frac{a}{b}", false, vec![]),
+        ("frac{a}{b}{c}", false, vec![(0, "`frac` takes 2 arguments, but 3 were given")]),
+        ("x + sqrt{y", false, vec![(8, "`{` is not closed")]),
+        ("a} + b", false, vec![(1, "unexpected `}`")]),
+        ("frac{1}{sin{x}}", false, vec![(8, "`sin` takes no arguments, but 1 was given")]),
+        ("sup{x} + text{frac{a}}", false, vec![(0, "`sup` takes 0 or 2 arguments, but 1 was given")]),
+        ("cases{x}{y}", false, vec![(5, "`cases`: A row has to start with `{`: x")]),
+        ("x + mat{{a}{b}}{c}", false, vec![(15, "`mat`: A row has to start with `{`: c")]),
+        ("mat{{a}{b}}{{c}}", false, vec![(11, "`mat`: Expected 2 elements, but got 1 elements")]),
+        ("\\frac{a}{b} + \\alpha", true, vec![]),
+        ("\\frac{a}{b", true, vec![(8, "`{` is not closed")]),
+        ("x + \\foo", true, vec![(4, "unknown command `\\foo`")]),
+        ("\\begin{pmatrix} a \\end{bmatrix}", true, vec![(18, "`\\end{bmatrix}` doesn't match `\\begin{pmatrix}`")]),
+        ("\\left( x \\right) + y \\right)", true, vec![(21, "`\\right` without `\\left`")]),
+        ("\\{ x \\}", true, vec![]),
+        ("x^", true, vec![(1, "`^` is not followed by an argument")]),
+        ("{x_} + y^{2}_", true, vec![(2, "`_` is not followed by an argument"), (12, "`_` is not followed by an argument")]),
+    ];
+
+    for (formula, latex, answer) in samples.iter() {
+        let errors = crate::find_math_errors(formula, *latex).into_iter().map(
+            |error| (error.position, error.message)
+        ).collect::<Vec<(usize, String)>>();
+        let answer = answer.iter().map(
+            |(position, message)| (*position, message.to_string())
+        ).collect::<Vec<(usize, String)>>();
+
+        assert_eq!(errors, answer, "errors of `{formula}`");
+    }
+
+    let md = "[[math]]frac{a}[[/math]] [[math]]frac{a}{b}[[/math]]";
+    let answer = "
+<p>
+    <span class=\"math-error\" title=\"`frac` takes 2 arguments, but 1 was given (at 0)\">
+        <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"frac{a}\"><mi>frac</mi><mo>{</mo><mi>a</mi><mo>}</mo></math>
+    </span>
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"a/b\"><mfrac displaystyle=\"false\"><mi>a</mi><mi>b</mi></mfrac></math>
+</p>
+";
+    let result = render_to_html(md, RenderOption::default());
+
+    if remove_whitespaces(&into_v32(&result.content)) != remove_whitespaces(&into_v32(answer)) {
+        panic!("{}", result.content);
+    }
+
+    assert_eq!(result.diagnostics, vec![crate::Diagnostic::MathError {
+        formula: String::from("frac{a}"),
+        message: String::from("`frac` takes 2 arguments, but 1 was given"),
+        position: 0
+    }]);
+
+    let mut render_option = RenderOption::default();
+    render_option.set_math_error_span(false);
+    let result = render_to_html(md, render_option);
+
+    assert!(!result.content.contains("math-error"));
+    assert_eq!(result.diagnostics.len(), 1);
+
+    // malformed rows are errors, not panics
+    let result = render_to_html("[[math]] cases{x}{y} [[/math]]\n\n[[math]]\n\nmat{a}\n\n[[/math]]", RenderOption::default());
+
+    assert_eq!(result.diagnostics, vec![
+        crate::Diagnostic::MathError {
+            formula: String::from(" cases{x}{y} "),
+            message: String::from("`cases`: A row has to start with `{`: x"),
+            position: 6
+        },
+        crate::Diagnostic::MathError {
+            formula: String::from("\nmat{a}\n"),
+            message: String::from("`mat`: A row has to start with `{`: a"),
+            position: 4
+        },
+    ]);
+}
+
+#[test]
+fn chemistry_and_unit_test() {
+    let samples = vec![
+        // (formula, latex, plain text)
+        ("ce{H2SO4 -> 2H+ + SO4^2-}", false, "H_2SO_4 → 2H^+ + SO_4^2−"),
+        ("ce{CuSO4*5H2O}", false, "CuSO_4 ⋅ 5H_2O"),
+        ("ce{Ca(OH)2 + CO2}", false, "Ca(OH)_2 + CO_2"),
+        ("ce{N2 + 3H2 <=>[Fe][500 K] 2NH3}", false, "N_2 + 3H_2⇌_(500 K)^Fe 2NH_3"),
+        ("ce{Na+(aq) + Cl-(aq)}", false, "Na^+(aq) + Cl^−(aq)"),
+        ("unit{9.8}{m/s^2}", false, "9.8 m/s^2"),
+        ("unit{6.67}{N*m^2*kg^-2}", false, "6.67 N ⋅ m^2 ⋅ kg^(-2)"),
+        ("unit{90}{deg} + unit{20}{degC}", false, "90° + 20 °C"),
+        ("unit{kg  m}", false, "kg m"),
+        ("\\ce{2H2 + O2 -> 2H2O}", true, "2H_2 + O_2 → 2H_2O"),
+        ("\\qty{9.8}{m/s^{2}} + \\unit{ohm}", true, "9.8 m/s^2 + Ω"),
+    ];
+
+    for (formula, latex, plain_text) in samples.iter() {
+        assert_eq!(crate::math_to_plain_text(formula, *latex), *plain_text, "plain text of `{formula}`");
+        assert!(crate::find_math_errors(formula, *latex).is_empty(), "errors of `{formula}`");
+    }
+
+    // elements and units are upright, and a thin space is between a number and its unit
+    let md = "[[math]]ce{SO4^2-}[[/math]] [[math]]unit{9.8}{m/s^2}[[/math]]";
+    let answer = "
+<p>
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"SO_4^2−\">
+        <mrow><mtext>S</mtext><msubsup><mtext>O</mtext><mn>4</mn><mtext>2−</mtext></msubsup></mrow>
+    </math>
+    <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"9.8 m/s^2\">
+        <mrow><mn>9.8</mn><mspace width=\"0.1667em\"/><mtext>m</mtext><mo>/</mo><msup><mtext>s</mtext><mn>2</mn></msup></mrow>
+    </math>
+</p>
+";
+    let rendered = render_to_html(md, RenderOption::default()).content;
+
+    if remove_whitespaces(&into_v32(&rendered)) != remove_whitespaces(&into_v32(answer)) {
+        panic!("{rendered}");
+    }
+
+}
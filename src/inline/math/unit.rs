@@ -0,0 +1,142 @@
+use super::entity::Entity;
+use super::is_whitespace;
+use crate::utils::{get_curly_brace_end_index, is_alphabet, is_numeric};
+
+/*
+SI units: `unit{9.8}{m/s^2}` -> 9.8 m/s²
+
+- The units are upright: `<mtext>`.
+- The number and the units are separated by a thin space, except `deg`: `unit{90}{deg}` -> 90°
+- `^2`, `^-1` and `^{-1}` are exponents.
+- `*`, `.` and whitespaces multiply the units: `kg*m^2`, `N.m`, `kg m`. `*` and `.` are rendered to `⋅`, and whitespaces to thin spaces.
+- `/` divides them: `m/s`.
+- `deg`, `degC` and `ohm` are `°`, `°C` and `Ω`. The other non-ASCII units, like `μm`, can be written directly.
+*/
+
+// `unit{9.8}{m/s^2}`: `value` is the entities of `9.8`
+pub fn parse_quantity(value: Vec<Entity>, unit: &[u32]) -> Vec<Entity> {
+    let unit = parse_unit(unit);
+
+    // `90°`, not `90 °`
+    if let [Entity::RawString(symbol)] = &unit[..] {
+
+        if symbol == &[176] {  // '°'
+            return vec![value, unit].concat();
+        }
+
+    }
+
+    vec![value, vec![Entity::ThinSpace], unit].concat()
+}
+
+// `unit{m/s^2}`
+pub fn parse_unit(content: &[u32]) -> Vec<Entity> {
+    let mut result = vec![];
+    let mut index = 0;
+
+    while index < content.len() {
+        let c = content[index];
+
+        if is_alphabet(&c) || c > 127 {
+            let end_index = content[index..].iter().position(
+                |c| !is_alphabet(c) && *c <= 127
+            ).map(|i| index + i).unwrap_or(content.len());
+
+            result.push(Entity::RawString(unit_symbol(&content[index..end_index])));
+            index = end_index;
+        }
+
+        // `m^2`, `s^-1`, `s^{-1}`
+        else if c == '^' as u32 {
+            let (exponent, end_index) = read_exponent(content, index + 1);
+
+            match result.pop() {
+                Some(unit) => {
+                    result.push(Entity::new_script(vec![unit], vec![], exponent, vec![], vec![]));
+                },
+                None => {
+                    result.extend(exponent);
+                }
+            }
+
+            index = end_index;
+        }
+
+        else if c == '*' as u32 || c == '.' as u32 {
+            result.push(Entity::new_operator(vec![8901]));  // '⋅'
+            index += 1;
+        }
+
+        else if c == '/' as u32 {
+            result.push(Entity::new_operator(vec![47]));  // '/'
+            index += 1;
+        }
+
+        // `kg m`: consecutive whitespaces are a single thin space
+        else if is_whitespace(&c) {
+
+            if !result.is_empty() && !content[index..].iter().all(is_whitespace) {
+                result.push(Entity::ThinSpace);
+            }
+
+            while index < content.len() && is_whitespace(&content[index]) {
+                index += 1;
+            }
+
+        }
+
+        else if is_numeric(&c) {
+            let end_index = content[index..].iter().position(|c| !is_numeric(c)).map(|i| index + i).unwrap_or(content.len());
+            result.push(Entity::new_number(content[index..end_index].to_vec()));
+            index = end_index;
+        }
+
+        else {
+            result.push(Entity::new_operator(vec![c]));
+            index += 1;
+        }
+
+    }
+
+    result
+}
+
+// `2`, `-1`, `{-1}`: (exponent, end_index)
+fn read_exponent(content: &[u32], index: usize) -> (Vec<Entity>, usize) {
+    let (exponent, end_index) = if content.get(index) == Some(&('{' as u32)) {
+
+        match get_curly_brace_end_index(content, index) {
+            Some(end_index) => (&content[(index + 1)..end_index], end_index + 1),
+            None => (&content[index..index], index)
+        }
+
+    } else {
+        let sign = (content.get(index) == Some(&('-' as u32))) as usize;
+        let end_index = content[(index + sign)..].iter().position(|c| !is_numeric(c)).map(|i| index + sign + i).unwrap_or(content.len());
+
+        (&content[index..end_index], end_index)
+    };
+
+    let result = match exponent.split_first() {
+        Some((sign, number)) if *sign == '-' as u32 => vec![
+            Entity::new_operator(vec![45]),  // '-'
+            Entity::new_number(number.to_vec())
+        ],
+        Some(_) => vec![Entity::new_number(exponent.to_vec())],
+        None => vec![]
+    };
+
+    (result, end_index)
+}
+
+fn unit_symbol(name: &[u32]) -> Vec<u32> {
+
+    match name {
+        [100, 101, 103] => vec![176],  // `deg` -> `°`
+        [100, 101, 103, 67] => vec![176, 67],  // `degC` -> `°C`
+        [111, 104, 109] => vec![937],  // `ohm` -> `Ω`
+        _ => name.to_vec()
+    }
+
+}
+
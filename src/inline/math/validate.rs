@@ -1,18 +1,25 @@
-use super::{ZERO_ARG_FUNCTIONS, ONE_ARG_FUNCTIONS, TWO_ARG_FUNCTIONS, THREE_ARG_FUNCTIONS, FIVE_ARG_FUNCTIONS, parse::is_space};
+use super::{ZERO_ARG_FUNCTIONS, ONE_ARG_FUNCTIONS, TWO_ARG_FUNCTIONS, THREE_ARG_FUNCTIONS, FIVE_ARG_FUNCTIONS, NAMED_OPERATORS};
+use super::entity::matrix::try_parse_matrix;
+use super::parse::{get_argument_indices, is_space};
+use crate::inline::parse::{get_code_span_marker_end_index, is_code_span_marker_begin};
+use crate::utils::{from_v32, get_curly_brace_end_index, is_alphabet};
 use lazy_static::lazy_static;
 use std::collections::HashSet;
+use std::fmt;
 
 lazy_static! {
 
-    // it's only used by `is_valid`
+    // it's only used by `is_valid` and `find_errors`
     static ref FUNCTION_NAMES: HashSet<Vec<u32>> = {
         let mut result = ZERO_ARG_FUNCTIONS
             .union(&ONE_ARG_FUNCTIONS).map(|f| f.clone()).collect::<HashSet<Vec<u32>>>()
             .union(&TWO_ARG_FUNCTIONS).map(|f| f.clone()).collect::<HashSet<Vec<u32>>>()
             .union(&THREE_ARG_FUNCTIONS).map(|f| f.clone()).collect::<HashSet<Vec<u32>>>()
-            .union(&FIVE_ARG_FUNCTIONS).map(|f| f.clone()).collect::<HashSet<Vec<u32>>>();
+            .union(&FIVE_ARG_FUNCTIONS).map(|f| f.clone()).collect::<HashSet<Vec<u32>>>()
+            .union(&NAMED_OPERATORS).map(|f| f.clone()).collect::<HashSet<Vec<u32>>>();
 
         result.insert(vec![109, 97, 116]);  // "mat"
+        result.insert(vec![99, 97, 115, 101, 115]);  // "cases"
 
         result
     };
@@ -27,7 +34,169 @@ pub fn is_valid(word: &[u32], arguments: &Vec<Vec<u32>>) -> bool {
         || (TWO_ARG_FUNCTIONS.contains(word) && arguments.len() == 2)
         || (THREE_ARG_FUNCTIONS.contains(word) && arguments.len() == 3)
         || (FIVE_ARG_FUNCTIONS.contains(word) && arguments.len() == 5)
+        || (NAMED_OPERATORS.contains(word) && arguments.len() == 0)
         || word == &[109, 97, 116]  // "mat"
+        || word == &[99, 97, 115, 101, 115]  // "cases"
     )
 
-}
\ No newline at end of file
+}
+
+/// A problem in a formula. The formula is still rendered as well as the parser can.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MathError {
+    pub message: String,
+
+    /// The offset of the offending character, in characters from the start of the formula.
+    pub position: usize
+}
+
+impl MathError {
+
+    pub fn new(message: String, position: usize) -> Self {
+        MathError { message, position }
+    }
+
+}
+
+impl fmt::Display for MathError {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at {})", self.message, self.position)
+    }
+
+}
+
+// `content` is what `md_to_math` takes
+pub fn find_errors(content: &[u32]) -> Vec<MathError> {
+    let mut errors = find_unbalanced_braces(content);
+    find_argument_errors(content, 0, &mut errors);
+
+    errors.sort_by_key(|error| error.position);
+
+    errors
+}
+
+fn find_unbalanced_braces(content: &[u32]) -> Vec<MathError> {
+    let mut errors = vec![];
+    let mut opening_indices = vec![];
+    let mut index = 0;
+
+    while index < content.len() {
+
+        // `get_curly_brace_end_index` ignores braces inside code spans
+        if is_code_span_marker_begin(content, index) {
+            index = get_code_span_marker_end_index(content, index);
+        }
+
+        else if content[index] == '{' as u32 {
+            opening_indices.push(index);
+        }
+
+        else if content[index] == '}' as u32 && opening_indices.pop().is_none() {
+            errors.push(MathError::new(String::from("unexpected `}`"), index));
+        }
+
+        index += 1;
+    }
+
+    for index in opening_indices.into_iter() {
+        errors.push(MathError::new(String::from("`{` is not closed"), index));
+    }
+
+    errors
+}
+
+// `offset` is the position of `content` in the formula
+fn find_argument_errors(content: &[u32], offset: usize, errors: &mut Vec<MathError>) {
+    let mut index = 0;
+
+    while index < content.len() {
+
+        if is_alphabet(&content[index]) {
+            let word_end_index = content[index..].iter().position(|c| !is_alphabet(c)).map(|i| index + i).unwrap_or(content.len());
+            let word = &content[index..word_end_index];
+            let (argument_indices, end_index) = get_argument_indices(content, word_end_index);
+            let arguments = argument_indices.iter().map(
+                |(start_index, end_index)| content[*start_index..*end_index].to_vec()
+            ).collect::<Vec<Vec<u32>>>();
+
+            // `sqrt{x` is reported by `find_unbalanced_braces`
+            let unclosed_argument = content.get(end_index + 1) == Some(&('{' as u32));
+
+            if FUNCTION_NAMES.contains(word) && !is_valid(word, &arguments) && !unclosed_argument {
+                errors.push(MathError::new(wrong_argument_count(word, arguments.len()), offset + index));
+            }
+
+            // "mat", "cases"
+            else if word == [109, 97, 116] || word == [99, 97, 115, 101, 115] {
+
+                // it points to the `{` of the row
+                if let Err((row_index, e)) = try_parse_matrix(&arguments) {
+                    errors.push(MathError::new(format!("`{}`: {e}", from_v32(word)), offset + argument_indices[row_index].0 - 1));
+                }
+
+            }
+
+            // the argument of `text`, `ce` and the last argument of `unit` are not formulas
+            let formulas = match word {
+                [116, 101, 120, 116] | [99, 101] => 0,  // "text", "ce"
+                [117, 110, 105, 116] => argument_indices.len().saturating_sub(1),  // "unit"
+                _ => argument_indices.len()
+            };
+
+            for (start_index, end_index) in argument_indices[..formulas].iter() {
+                find_argument_errors(&content[*start_index..*end_index], offset + start_index, errors);
+            }
+
+            index = if argument_indices.is_empty() { word_end_index } else { end_index + 1 };
+        }
+
+        // `{...}` without a function name, like the rows of `mat`
+        else if content[index] == '{' as u32 {
+
+            match get_curly_brace_end_index(content, index) {
+                Some(end_index) => {
+                    find_argument_errors(&content[(index + 1)..end_index], offset + index + 1, errors);
+                    index = end_index + 1;
+                },
+                None => {
+                    index += 1;
+                }
+            }
+
+        }
+
+        else {
+            index += 1;
+        }
+
+    }
+
+}
+
+// `frac` takes 2 arguments, but 3 were given
+fn wrong_argument_count(word: &[u32], given: usize) -> String {
+    let counts = [
+        (0, ZERO_ARG_FUNCTIONS.contains(word) || NAMED_OPERATORS.contains(word)),
+        (1, ONE_ARG_FUNCTIONS.contains(word)),
+        (2, TWO_ARG_FUNCTIONS.contains(word)),
+        (3, THREE_ARG_FUNCTIONS.contains(word)),
+        (5, FIVE_ARG_FUNCTIONS.contains(word)),
+    ].iter().filter(
+        |(_, takes)| *takes
+    ).map(
+        |(count, _)| count.to_string()
+    ).collect::<Vec<String>>();
+
+    let expected = match &counts[..] {
+        [count] if count == "0" => String::from("no arguments"),
+        [count] if count == "1" => String::from("1 argument"),
+        _ => format!("{} arguments", counts.join(" or "))
+    };
+
+    format!(
+        "`{}` takes {expected}, but {given} {} given",
+        from_v32(word),
+        if given == 1 { "was" } else { "were" }
+    )
+}
@@ -1,19 +1,28 @@
 mod auto_url;
+pub mod citation;
 pub mod footnote;
 pub mod link;
 pub mod macros;
 pub mod math;
 pub mod parse;
 mod predicate;
+pub mod raw_html;
 
 #[cfg(test)]
 mod testbench;
 
 pub use auto_url::render_auto_urls;
-use crate::container::icon::get_icon;
+use crate::container::admonition::{AdmonitionType, inline_admonition_to_html};
+#[cfg(test)] use crate::container::icon::Icon;
+use crate::render::render_option::{CitationStyle, EmailObfuscation};
+use citation::{CitationItem, citation_to_html};
+#[cfg(test)] use citation::citation_to_mdxt;
+#[cfg(test)] use crate::container::admonition::admonition_to_mdxt;
 use crate::utils::{from_v32, into_v32};
 use crate::file_ext::{FileExt, read_file_extension};
+use crate::obfuscate::obfuscate_email;
 use link::is_youtube;
+use macros::cross_reference::cross_reference_placeholder;
 use math::render_math;
 
 #[derive(Clone)]
@@ -55,15 +64,42 @@ pub enum InlineNode {
     Raw(Vec<u32>),
     Complex(Vec<InlineNode>),
     CodeSpan(Vec<u32>),
+
+    // an html tag that `RenderOption.raw_html` allows, rendered already
+    RawHtml(Vec<u32>),
+
+    // `[[email = user@example.com]]`, `<user@example.com>` and `user@example.com`
+    Email {
+        address: Vec<u32>,
+        obfuscation: EmailObfuscation
+    },
     Footnote((usize, usize, Vec<u32>)),  // index, inverse_index, label
+
+    // a footnote reference inside a tooltip message: it doesn't have an id and it doesn't count as a reference
+    // it shows the number of the first reference to the footnote, or its label if there's none yet
+    FootnotePreview((usize, Option<usize>, Vec<u32>)),  // index, the first inverse_index of the footnote, label
+    Citation((usize, Vec<CitationItem>, CitationStyle)),  // inverse_index, items, style
     Link {
         text: Vec<InlineNode>,
-        destination: Vec<u32>
+        destination: Vec<u32>,
+
+        // `RenderOption.external_link_attributes`
+        external: bool
+    },
+    // `[[[Page Name|label]]]`, `destination` is `None` if the page is missing
+    WikiLink {
+        title: Vec<u32>,
+        text: Vec<InlineNode>,
+        destination: Option<Vec<u32>>
     },
     Image {
         media_type: MediaType,
         description: Vec<u32>,
-        address: Vec<u32>
+        address: Vec<u32>,
+
+        // `![description](address "title")`
+        // an image alone in a paragraph with a title becomes a figure
+        title: Option<Vec<u32>>
     },
     Decoration {
         deco_type: DecorationType,
@@ -89,7 +125,12 @@ pub enum InlineMacro {
     // `[[char = therefore]]` -> `there4` -> `&there4;`
     Char(Vec<u32>),
 
-    Math(Vec<u32>),
+    Math {
+        content: Vec<u32>,
+        latex: bool,
+        speech: bool,  // `RenderOption.math_speech`
+        error_span: bool  // `RenderOption.math_error_span`
+    },
     Box {
         border: bool,
         inline: bool,
@@ -110,16 +151,29 @@ pub enum InlineMacro {
         id: Vec<u32>
     },
     Icon {
-        name: Vec<u32>,
-        size: u32
-    }
+        #[cfg(test)]  // it's used for roundtrip tests
+        icon: Icon,
+        svg: Vec<u32>  // rendered at parse time, because the user's icons are in `RenderOption.icons`
+    },
+    Admonition {
+        admonition_type: AdmonitionType,
+        icon: Vec<u32>  // rendered at parse time, like `Icon`
+    },
+
+    // `[[id = fig-arch]]`: an anchor, or the label of the figure that it follows
+    Id(Vec<u32>),
+
+    // `[[ref = fig-arch]]` -> `Figure 3`
+    // labels may be defined after the reference, so it's rendered to a placeholder and resolved later
+    Ref(Vec<u32>)
 }
 
 impl InlineNode {
 
     pub fn to_html(&self, toc_rendered: &[u32], class_prefix: &str) -> Vec<u32> {
         match self {
-            InlineNode::Raw(content) => content.clone(),
+            InlineNode::Raw(content)
+            | InlineNode::RawHtml(content) => content.clone(),
 
             InlineNode::CodeSpan(content) => vec![
                 into_v32(&format!("<code class=\"{class_prefix}inline-code-span\">")),
@@ -131,13 +185,39 @@ impl InlineNode {
                 "<span class=\"{class_prefix}footnote-ref\" id=\"footnote-ref-{inverse_index}\"><a href=\"#footnote-cite-{index}\">[{inverse_index}]</a></span>",
             )),
 
+            InlineNode::FootnotePreview((index, first_inverse_index, label)) => into_v32(&format!(
+                "<span class=\"{class_prefix}footnote-ref\"><a href=\"#footnote-cite-{index}\">[{}]</a></span>",
+                match first_inverse_index {
+                    Some(inverse_index) => inverse_index.to_string(),
+                    None => from_v32(&label[1..])  // `^1` -> `1`
+                }
+            )),
+
+            InlineNode::Citation((inverse_index, items, style)) => citation_to_html(*inverse_index, items, *style, class_prefix),
+
+            InlineNode::Email { address, obfuscation } => obfuscate_email(address, *obfuscation, class_prefix),
+
             InlineNode::Complex(content) => content.iter().map(
                 |node| node.to_html(toc_rendered, class_prefix)
             ).collect::<Vec<Vec<u32>>>().concat(),
 
-            InlineNode::Link { text, destination } => vec![
+            InlineNode::Link { text, destination, external } => vec![
                 vec![60, 97, 32, 104, 114, 101, 102, 61, 34],  // into_v32("<a href=\""),
                 destination.clone(),
+                if *external {
+                    into_v32("\" rel=\"nofollow noopener\" target=\"_blank\">")
+                } else {
+                    vec![34, 62]  // into_v32("\">")
+                },
+                text.iter().map(
+                    |node| node.to_html(toc_rendered, class_prefix)
+                ).collect::<Vec<Vec<u32>>>().concat(),
+                vec![60, 47, 97, 62],  // into_v32("</a>")
+            ].concat(),
+
+            InlineNode::WikiLink { text, destination: Some(destination), .. } => [
+                into_v32(&format!("<a class=\"{class_prefix}wiki-link\" href=\"")),
+                destination.clone(),
                 vec![34, 62],  // into_v32("\">")
                 text.iter().map(
                     |node| node.to_html(toc_rendered, class_prefix)
@@ -145,12 +225,27 @@ impl InlineNode {
                 vec![60, 47, 97, 62],  // into_v32("</a>")
             ].concat(),
 
-            InlineNode::Image { description, address, media_type } => match media_type {
+            InlineNode::WikiLink { text, destination: None, .. } => [
+                into_v32(&format!("<span class=\"{class_prefix}missing-wiki-link\">")),
+                text.iter().map(
+                    |node| node.to_html(toc_rendered, class_prefix)
+                ).collect::<Vec<Vec<u32>>>().concat(),
+                vec![60, 47, 115, 112, 97, 110, 62],  // into_v32("</span>")
+            ].concat(),
+
+            InlineNode::Image { description, address, media_type, title } => match media_type {
                 MediaType::Image => vec![
                     vec![60, 105, 109, 103, 32, 115, 114, 99, 61, 34],  // into_v32("<img src=\""),
                     address.clone(),
                     vec![34, 32, 97, 108, 116, 61, 34],  // into_v32("\" alt=\""),
                     description.clone(),
+                    match title {
+                        Some(title) => vec![
+                            vec![34, 32, 116, 105, 116, 108, 101, 61, 34],  // into_v32("\" title=\"")
+                            title.clone()
+                        ].concat(),
+                        None => vec![]
+                    },
                     vec![34, 47, 62],  // into_v32("\"/>")
                 ].concat(),
                 MediaType::Video(ext) => vec![
@@ -384,9 +479,19 @@ impl InlineNode {
 
                     // into_v32("&nbsp;") -> [38, 110, 98, 115, 112, 59]
                     InlineMacro::Blank { repeat } => vec![vec![38, 110, 98, 115, 112, 59]; *repeat].concat(),
-                    InlineMacro::Math (content) => render_math(content),
+                    InlineMacro::Math { content, latex, speech, error_span } => render_math(content, *latex, *speech, *error_span, class_prefix),
                     InlineMacro::Toc => toc_rendered.to_vec(),
-                    InlineMacro::Icon { name, size } => get_icon(name, *size as usize, None, false).unwrap()
+                    InlineMacro::Icon { svg, .. } => svg.clone(),
+                    InlineMacro::Id(id) => into_v32(&format!("<span id=\"{}\"></span>", from_v32(id))),
+                    InlineMacro::Ref(label) => cross_reference_placeholder(label),
+                    InlineMacro::Admonition { admonition_type, icon } => inline_admonition_to_html(
+                        admonition_type,
+                        icon,
+                        content.iter().map(
+                            |node| node.to_html(toc_rendered, class_prefix)
+                        ).collect::<Vec<Vec<u32>>>().concat(),
+                        class_prefix
+                    )
                 }
             }
         }
@@ -395,7 +500,8 @@ impl InlineNode {
     #[cfg(test)]  // it's used for roundtrip tests
     pub fn to_mdxt(&self) -> Vec<u32> {
         match self {
-            InlineNode::Raw(content) => content.clone(),
+            InlineNode::Raw(content)
+            | InlineNode::RawHtml(content) => content.clone(),
 
             InlineNode::CodeSpan(content) => {
                 let backtick_count = content.iter().filter(
@@ -412,17 +518,26 @@ impl InlineNode {
                 ].concat()
             },
 
-            InlineNode::Footnote((_, _, label)) => vec![
+            InlineNode::Footnote((_, _, label))
+            | InlineNode::FootnotePreview((_, _, label)) => vec![
                 into_v32("["),
                 label.clone(),
                 into_v32("]")
             ].concat(),
 
+            InlineNode::Citation((_, items, _)) => citation_to_mdxt(items),
+
+            InlineNode::Email { address, .. } => [
+                into_v32("[[email = "),
+                address.clone(),
+                into_v32("]]")
+            ].concat(),
+
             InlineNode::Complex(content) => content.iter().map(
                 |node| node.to_mdxt()
             ).collect::<Vec<Vec<u32>>>().concat(),
 
-            InlineNode::Link {text, destination} => vec![
+            InlineNode::Link {text, destination, ..} => vec![
                 into_v32("["),
                 text.iter().map(
                     |node| node.to_mdxt()
@@ -432,11 +547,40 @@ impl InlineNode {
                 into_v32(")")
             ].concat(),
 
-            InlineNode::Image {description, address, ..} => vec![
+            InlineNode::WikiLink {title, text, ..} => {
+                let text = text.iter().map(
+                    |node| node.to_mdxt()
+                ).collect::<Vec<Vec<u32>>>().concat();
+
+                if &text == title {
+                    [
+                        vec![91, 91, 91],  // into_v32("[[[")
+                        title.clone(),
+                        vec![93, 93, 93],  // into_v32("]]]")
+                    ].concat()
+                }
+
+                else {
+                    [
+                        vec![91, 91, 91],  // into_v32("[[[")
+                        title.clone(),
+                        vec![124],  // into_v32("|")
+                        text,
+                        vec![93, 93, 93],  // into_v32("]]]")
+                    ].concat()
+                }
+
+            },
+
+            InlineNode::Image {description, address, title, ..} => vec![
                 into_v32("!["),
                 description.clone(),
                 into_v32("]("),
                 address.clone(),
+                match title {
+                    Some(title) => vec![into_v32(" \""), title.clone(), into_v32("\"")].concat(),
+                    None => vec![]
+                },
                 into_v32(")")
             ].concat(),
 
@@ -554,8 +698,8 @@ impl InlineNode {
                         content.iter().map(|node| node.to_mdxt()).collect::<Vec<Vec<u32>>>().concat(),
                         into_v32("[[/box]]"),
                     ].concat(),
-                    InlineMacro::Math(content) => vec![
-                        into_v32("[[math]]"),
+                    InlineMacro::Math { content, latex, .. } => vec![
+                        into_v32(if *latex { "[[math, latex]]" } else { "[[math]]" }),
                         content.clone(),
                         into_v32("[[/math]]"),
                     ].concat(),
@@ -610,11 +754,15 @@ impl InlineNode {
                         into_v32(&format!("[[blank={repeat}]]"))
                     },
                     InlineMacro::Toc => into_v32("[[toc]]"),
-                    InlineMacro::Icon { name, size } => vec![
-                        into_v32("[[icon="),
-                        name.clone(),
-                        into_v32(&format!(",size={size}]]"))
-                    ].concat(),
+                    InlineMacro::Icon { icon, .. } => icon.to_mdxt(),
+                    InlineMacro::Id(id) => vec![into_v32("[[id="), id.clone(), into_v32("]]")].concat(),
+                    InlineMacro::Ref(label) => vec![into_v32("[[ref="), label.clone(), into_v32("]]")].concat(),
+                    InlineMacro::Admonition { admonition_type, .. } => admonition_to_mdxt(
+                        admonition_type,
+                        content.iter().map(
+                            |node| node.to_mdxt()
+                        ).collect::<Vec<Vec<u32>>>().concat()
+                    ),
                 }
             }
         }
@@ -625,11 +773,16 @@ impl InlineNode {
             InlineNode::Raw(content) => content.clone(),
             InlineNode::Complex(nodes)
             | InlineNode::Link { text: nodes, .. }
+            | InlineNode::WikiLink { text: nodes, .. }
             | InlineNode::Decoration { content: nodes, .. } => nodes.iter().map(
                 |node| node.extract_text()
             ).collect::<Vec<Vec<u32>>>().concat(),
             InlineNode::CodeSpan(code) => code.clone(),
             InlineNode::Footnote(_)
+            | InlineNode::FootnotePreview(_)
+            | InlineNode::Citation(_)
+            | InlineNode::RawHtml(_)
+            | InlineNode::Email { .. }
             | InlineNode::Image { .. } => vec![],
         }
     }
@@ -3,18 +3,22 @@ use super::{
     INLINE_CODE_SPAN_MARKER1, INLINE_CODE_SPAN_MARKER2, INLINE_CODE_SPAN_MARKER3, INLINE_CODE_SPAN_MARKER4,
     render_auto_urls,
 };
-use super::auto_url::UrlOrNot;
+use super::auto_url::{UrlOrNot, get_url_destination};
+use super::citation::{CitationItem, predicate::read_citation};
 use super::footnote::predicate::read_footnote;
 use super::link::normalize_link_label;
+use super::link::wiki::{read_wiki_link, wiki_link_to_node};
 use super::link::predicate::{
     read_direct_link, read_reference_link, read_shortcut_reference_link
 };
 use super::macros::{predicate::check_and_parse_macro_inline, tooltip::load_tooltip_message};
-use super::math::escape_inside_math_blocks;
+use super::math::{dollar_signs_to_math_macros, escape_inside_math_blocks};
 use super::predicate::*;
+use super::raw_html::{balance_raw_html, read_raw_html};
 use crate::ast::doc_data::DocData;
-use crate::escape::{render_backslash_escapes, undo_backslash_escapes};
-use crate::render::render_option::RenderOption;
+use crate::escape::{render_backslash_escapes, undo_backslash_escapes, HTML_ESCAPE_OFFSET};
+use crate::obfuscate::{email_to_node, read_email_autolink, read_email_macro};
+use crate::render::render_option::{RawHtmlPolicy, RenderOption};
 use crate::utils::{from_v32, get_bracket_end_index, into_v32};
 
 impl InlineNode {
@@ -27,7 +31,12 @@ impl InlineNode {
 
         // otherwise, it's already parsed
         if let InlineNode::Raw(content) = self {
-            *self = Self::from_mdxt(&content, doc_data, render_option);
+            let node = Self::from_mdxt(&content, doc_data, render_option);
+
+            *self = match &render_option.raw_html {
+                RawHtmlPolicy::Sanitize(_) => InlineNode::Complex(balance_raw_html(vec![node])),
+                _ => node
+            };
         }
 
     }
@@ -36,6 +45,11 @@ impl InlineNode {
         // it prevents inline elements inside code spans from being rendered
         // code spans are rendered later
         let mut content = escape_code_spans(content);
+
+        if render_option.dollar_math {
+            content = dollar_signs_to_math_macros(&content);
+        }
+
         content = escape_inside_math_blocks(content);
 
         let mut index = 0;
@@ -47,6 +61,73 @@ impl InlineNode {
                 continue;
             }
 
+            // `<user@example.com>`, `[[email = user@example.com]]`
+            let email = if content[index] == '<' as u32 + HTML_ESCAPE_OFFSET {
+                read_email_autolink(&content, index)
+            } else if content[index] == '[' as u32 {
+                read_email_macro(&content, index)
+            } else {
+                None
+            };
+
+            if let Some((address, last_index)) = email {
+                let mut result = vec![];
+
+                if index > 0 {
+                    result.push(Self::from_mdxt(&content[0..index], doc_data, render_option));
+                }
+
+                result.push(email_to_node(address, doc_data, render_option));
+
+                if last_index + 1 < content.len() {
+                    result.push(Self::from_mdxt(&content[(last_index + 1)..], doc_data, render_option));
+                }
+
+                return InlineNode::Complex(result).render_code_spans();
+            }
+
+            // `[[[Page Name]]]`, `[[link = Page Name]]`, `[[Page Name|label]]`
+            if content[index] == '[' as u32 {
+
+                if let Some((title, label, last_index)) = read_wiki_link(&content, index, render_option) {
+                    let mut result = vec![];
+
+                    if index > 0 {
+                        result.push(Self::from_mdxt(&content[0..index], doc_data, render_option));
+                    }
+
+                    result.push(wiki_link_to_node(title, label, doc_data, render_option));
+
+                    if last_index + 1 < content.len() {
+                        result.push(Self::from_mdxt(&content[(last_index + 1)..], doc_data, render_option));
+                    }
+
+                    return InlineNode::Complex(result).render_code_spans();
+                }
+
+            }
+
+            if content[index] == '<' as u32 + HTML_ESCAPE_OFFSET && render_option.raw_html != RawHtmlPolicy::Escape {
+
+                if let Some((html, last_index)) = read_raw_html(&content, index, doc_data, render_option) {
+                    let mut result = vec![];
+
+                    // unlike the other elements, `content[0..index]` may have urls
+                    if index > 0 {
+                        result.push(Self::from_mdxt(&content[0..index], doc_data, render_option));
+                    }
+
+                    result.push(InlineNode::RawHtml(html));
+
+                    if last_index + 1 < content.len() {
+                        result.push(Self::from_mdxt(&content[(last_index + 1)..], doc_data, render_option));
+                    }
+
+                    return InlineNode::Complex(result).render_code_spans();
+                }
+
+            }
+
             // it continues if the current character is not a special character
             if content[index] < '*' as u32
                 || ('*' as u32) < content[index] && content[index] < '[' as u32
@@ -233,7 +314,7 @@ impl InlineNode {
                 return InlineNode::Complex(result).render_code_spans();
             }
 
-            if let Some((link_text, link_destination, last_index)) = read_direct_link(&content, index, &doc_data.link_references) {
+            if let Some((link_text, link_destination, link_title, last_index)) = read_direct_link(&content, index, &doc_data.link_references) {
                 let mut result = vec![];
                 let mut is_image = false;
 
@@ -252,13 +333,15 @@ impl InlineNode {
                     result.push(InlineNode::Image {
                         media_type: MediaType::from_url(&handled_link, render_option.enable_youtube),
                         description: undo_code_span_escapes(&link_text),
-                        address: handled_link
+                        address: handled_link,
+                        title: link_title
                     });
                 }
 
                 else {
                     result.push(InlineNode::Link {
-                        text: Self::from_mdxt(&link_text, doc_data, render_option).to_vec(),
+                        text: Self::from_link_text(&link_text, doc_data, render_option),
+                        external: render_option.is_external_link(&handled_link),
                         destination: handled_link
                     });
                 }
@@ -293,13 +376,15 @@ impl InlineNode {
                     result.push(InlineNode::Image {
                         media_type: MediaType::from_url(&handled_link, render_option.enable_youtube),
                         description: undo_code_span_escapes(&link_text),
-                        address: handled_link
+                        address: handled_link,
+                        title: None
                     });
                 }
 
                 else {
                     result.push(InlineNode::Link {
-                        text: Self::from_mdxt(&link_text, doc_data, render_option).to_vec(),
+                        text: Self::from_link_text(&link_text, doc_data, render_option),
+                        external: render_option.is_external_link(&handled_link),
                         destination: handled_link
                     });
                 }
@@ -316,13 +401,20 @@ impl InlineNode {
                 let footnote_label = normalize_link_label(&content[index + 1..bracket_end_index]);
                 let mut result = vec![];
 
-                let inverse_index = doc_data.add_footnote_inverse_index(&footnote_label);
-
                 if index > 0 {
                     result.push(InlineNode::Raw(render_backslash_escapes(&content[0..index])));
                 }
 
-                if render_option.footnote_tooltip {
+                // a footnote inside a tooltip message is a copy of another reference, so it doesn't have an id
+                if doc_data.in_tooltip_message {
+                    let first_inverse_index = doc_data.footnote_references[&footnote_label].inverse_index.first().copied();
+                    result.push(InlineNode::FootnotePreview((footnote_index, first_inverse_index, footnote_label)));
+                }
+
+                // a footnote inside a tooltip doesn't have its own tooltip
+                else if render_option.footnote_tooltip && doc_data.tooltip_enabled == 0 {
+                    let inverse_index = doc_data.add_footnote_inverse_index(&footnote_label);
+
                     result.push(InlineNode::Decoration {
                         deco_type: DecorationType::Macro(InlineMacro::Tooltip {
                             label: footnote_label[1..].to_vec(),
@@ -334,6 +426,7 @@ impl InlineNode {
                 }
 
                 else {
+                    let inverse_index = doc_data.add_footnote_inverse_index(&footnote_label);
                     result.push(InlineNode::Footnote((footnote_index, inverse_index, footnote_label)));
                 }
 
@@ -344,6 +437,29 @@ impl InlineNode {
                 return InlineNode::Complex(result).render_code_spans();
             }
 
+            if let Some((citations, bracket_end_index)) = read_citation(&content, index, &render_option.bibliography) {
+                let mut result = vec![];
+
+                let keys = citations.iter().map(|(key, _)| key.clone()).collect::<Vec<String>>();
+                let inverse_index = doc_data.add_citation_inverse_index(&keys);
+
+                if index > 0 {
+                    result.push(InlineNode::Raw(render_backslash_escapes(&content[0..index])));
+                }
+
+                let items = citations.into_iter().map(
+                    |(key, locator)| CitationItem::new(key, locator, doc_data, render_option)
+                ).collect();
+
+                result.push(InlineNode::Citation((inverse_index, items, render_option.citation_style)));
+
+                if bracket_end_index + 1 < content.len() {
+                    result.push(Self::from_mdxt(&content[(bracket_end_index + 1)..], doc_data, render_option));
+                }
+
+                return InlineNode::Complex(result).render_code_spans();
+            }
+
             if let Some((link_text, last_index)) = read_shortcut_reference_link(&content, index, &doc_data.link_references) {
                 let mut result = vec![];
                 let mut is_image = false;
@@ -367,13 +483,15 @@ impl InlineNode {
                     result.push(InlineNode::Image {
                         media_type: MediaType::from_url(&handled_link, render_option.enable_youtube),
                         description: undo_code_span_escapes(&link_text),
-                        address: handled_link
+                        address: handled_link,
+                        title: None
                     });
                 }
 
                 else {
                     result.push(InlineNode::Link {
-                        text: Self::from_mdxt(&link_text, doc_data, render_option).to_vec(),
+                        text: Self::from_link_text(&link_text, doc_data, render_option),
+                        external: render_option.is_external_link(&handled_link),
                         destination: handled_link
                     });
                 }
@@ -404,13 +522,38 @@ impl InlineNode {
             index += 1;
         }
 
+        // `[see www.github.com](/u)`: an autolink inside a link text would be an `<a>` inside another `<a>`
+        if doc_data.in_link_text > 0 {
+            return InlineNode::Raw(render_backslash_escapes(&content)).render_code_spans();
+        }
+
         // TODO: How about toggling auto_urls?
-        let auto_url_rendered = match render_auto_urls(&content) {
+        let auto_url_rendered = match render_auto_urls(&content, &render_option.autolink_schemes) {
             UrlOrNot::NoUrl(result) => {
                 #[cfg(test)] assert_eq!(content, result);
 
                 result
             },
+            UrlOrNot::HasEmail(prefix, email, suffix) => {
+                #[cfg(test)] assert_eq!(
+                    [prefix.clone(), email.clone(), suffix.clone()].concat(),
+                    content.clone(),
+                );
+
+                let mut result = vec![];
+
+                if !prefix.is_empty() {
+                    result.push(InlineNode::Raw(render_backslash_escapes(&prefix)));
+                }
+
+                result.push(email_to_node(email, doc_data, render_option));
+
+                if !suffix.is_empty() {
+                    result.push(Self::from_mdxt(&suffix, doc_data, render_option));
+                }
+
+                return InlineNode::Complex(result).render_code_spans();
+            },
             UrlOrNot::HasUrl(prefix, url, suffix) => {
                 #[cfg(test)] assert_eq!(
                     vec![prefix.clone(), url.clone(), suffix.clone()].concat(),
@@ -423,9 +566,12 @@ impl InlineNode {
                     result.push(InlineNode::Raw(render_backslash_escapes(&prefix)));
                 }
 
+                let destination = get_url_destination(&url, &render_option.autolink_schemes);
+
                 result.push(InlineNode::Link {
-                    text: vec![InlineNode::Raw(url.clone())],
-                    destination: url,
+                    text: vec![InlineNode::Raw(url)],
+                    external: render_option.is_external_link(&destination),
+                    destination
                 });
 
                 if !suffix.is_empty() {
@@ -440,6 +586,15 @@ impl InlineNode {
         InlineNode::Raw(render_backslash_escapes(&auto_url_rendered)).render_code_spans()
     }
 
+    // the text of a link or a wiki link, which doesn't have autolinks
+    pub fn from_link_text(content: &[u32], doc_data: &mut DocData, render_option: &RenderOption) -> Vec<Self> {
+        doc_data.in_link_text += 1;
+        let result = Self::from_mdxt(content, doc_data, render_option).to_vec();
+        doc_data.in_link_text -= 1;
+
+        result
+    }
+
     pub fn render_code_spans(self) -> Self {
         match self {
             InlineNode::Raw(content) => {
@@ -504,13 +659,23 @@ impl InlineNode {
                 deco_type,
                 content: content.into_iter().map(|node| node.render_code_spans()).collect()
             },
-            InlineNode::Link {text, destination} => InlineNode::Link {
+            InlineNode::Link {text, destination, external} => InlineNode::Link {
+                text: text.into_iter().map(|node| node.render_code_spans()).collect(),
+                destination,
+                external
+            },
+            InlineNode::WikiLink {title, text, destination} => InlineNode::WikiLink {
+                title,
                 text: text.into_iter().map(|node| node.render_code_spans()).collect(),
                 destination
             },
             InlineNode::Image { .. } => self,
             InlineNode::CodeSpan(_) => self,
-            InlineNode::Footnote(_) => self
+            InlineNode::RawHtml(_) => self,
+            InlineNode::Email { .. } => self,
+            InlineNode::Footnote(_) => self,
+            InlineNode::FootnotePreview(_) => self,
+            InlineNode::Citation(_) => self
         }
     }
 
@@ -0,0 +1,473 @@
+use crate::ast::doc_data::DocData;
+use crate::inline::InlineNode;
+use crate::escape::{escape_htmls, undo_backslash_escapes, undo_html_escapes, HTML_ESCAPE_OFFSET, META_CHARACTER_OFFSET};
+use crate::render::{diagnostic::Diagnostic, render_option::{RawHtmlPolicy, RenderOption}};
+use crate::utils::{from_v32, into_v32, lowercase};
+
+/*
+Raw html in documents: `RenderOption.raw_html`
+
+`preprocess` escapes every `<`, so the tags are found among the escaped characters.
+Only inline tags are recognized, like the inline raw html of CommonMark: `<span class="x">`, `</span>`, `<br/>` and `<!-- comment -->`.
+A tag is not parsed as markdown, but the texts between tags are.
+
+- `RawHtmlPolicy::Escape`: the tags are rendered as texts. It's the default.
+- `RawHtmlPolicy::Sanitize`: the tags and the attributes in `HtmlAllowList` are kept, the other tags are rendered as texts and the other attributes are removed. Comments are removed.
+  The tags are balanced in each inline scope, like a paragraph, a link text or a bold text. See `balance_raw_html`.
+- `RawHtmlPolicy::Trusted`: the tags are rendered as they are.
+
+The policy is applied to the tags in the document, not to the elements that the engine renders, so tooltips, tables and icons are not affected.
+*/
+
+/// Tags, attributes and url schemes that `RawHtmlPolicy::Sanitize` allows. The names are case-insensitive.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HtmlAllowList {
+    pub tags: Vec<String>,
+
+    /// Attributes allowed on all the tags in `tags`. Event handlers, like `onclick`, are never allowed.
+    pub attributes: Vec<String>,
+
+    /// Schemes of `href`, `src`, `cite`, `action`, `formaction` and `poster`. Relative urls are always allowed.
+    pub url_schemes: Vec<String>
+}
+
+impl Default for HtmlAllowList {
+
+    /// Tags for texts, lists and tables, without `style` and forms.
+    fn default() -> Self {
+        HtmlAllowList {
+            tags: [
+                "a", "abbr", "b", "bdi", "bdo", "blockquote", "br", "caption", "cite", "code", "col", "colgroup",
+                "dd", "del", "details", "dfn", "div", "dl", "dt", "em", "figcaption", "figure",
+                "h1", "h2", "h3", "h4", "h5", "h6", "hr", "i", "img", "ins", "kbd", "li", "mark", "ol", "p", "pre", "q",
+                "rp", "rt", "ruby", "s", "samp", "small", "span", "strong", "sub", "summary", "sup",
+                "table", "tbody", "td", "tfoot", "th", "thead", "time", "tr", "u", "ul", "var", "wbr",
+            ].iter().map(|tag| tag.to_string()).collect(),
+            attributes: [
+                "alt", "cite", "class", "colspan", "datetime", "dir", "height", "href",
+                "lang", "open", "rowspan", "src", "start", "title", "width",
+            ].iter().map(|attribute| attribute.to_string()).collect(),
+            url_schemes: ["http", "https", "mailto", "tel"].iter().map(|scheme| scheme.to_string()).collect()
+        }
+    }
+
+}
+
+impl HtmlAllowList {
+
+    fn allows_tag(&self, name: &[u32]) -> bool {
+        self.tags.iter().any(|tag| into_v32(&tag.to_lowercase()) == name)
+    }
+
+    fn allows_attribute(&self, name: &[u32]) -> bool {
+        !name.starts_with(&[111, 110])  // into_v32("on")
+        && self.attributes.iter().any(|attribute| into_v32(&attribute.to_lowercase()) == name)
+    }
+
+    fn allows_url(&self, url: &[u32]) -> bool {
+
+        match get_url_scheme(url) {
+            Some(scheme) => self.url_schemes.iter().any(|s| into_v32(&s.to_lowercase()) == scheme),
+            None => true
+        }
+
+    }
+
+}
+
+const LESS_THAN: u32 = '<' as u32 + HTML_ESCAPE_OFFSET;
+const GREATER_THAN: u32 = '>' as u32 + HTML_ESCAPE_OFFSET;
+const DOUBLE_QUOTE: u32 = '"' as u32 + HTML_ESCAPE_OFFSET;
+const SINGLE_QUOTE: u32 = '\'' as u32 + HTML_ESCAPE_OFFSET;
+
+enum RawHtml {
+    Comment,
+    Tag {
+        name: Vec<u32>,  // lowercase
+        attributes: Vec<(Vec<u32>, Option<Vec<u32>>)>,  // (lowercase name, unescaped value)
+        is_closing: bool,
+        is_self_closing: bool
+    }
+}
+
+// `content[index]` is an escaped `<`
+// it returns the rendered html and the index of its `>`, if there's a tag that the policy allows
+pub fn read_raw_html(content: &[u32], index: usize, doc_data: &mut DocData, render_option: &RenderOption) -> Option<(Vec<u32>, usize)> {
+    let (html, end_index) = parse_raw_html(content, index)?;
+
+    match &render_option.raw_html {
+        RawHtmlPolicy::Escape => None,
+        RawHtmlPolicy::Trusted => Some((
+            undo_backslash_escapes(&undo_html_escapes(&content[index..(end_index + 1)])),
+            end_index
+        )),
+        RawHtmlPolicy::Sanitize(allow_list) => match html {
+            RawHtml::Comment => Some((vec![], end_index)),
+            RawHtml::Tag { name, attributes, is_closing, is_self_closing } => {
+
+                if !allow_list.allows_tag(&name) {
+                    let diagnostic = Diagnostic::DisallowedHtml {
+                        html: from_v32(&undo_backslash_escapes(&undo_html_escapes(&content[index..(end_index + 1)])))
+                    };
+
+                    // `InlineNode::from_mdxt` may read the same tag more than once
+                    if !doc_data.diagnostics.contains(&diagnostic) {
+                        doc_data.diagnostics.push(diagnostic);
+                    }
+
+                    return None;
+                }
+
+                if is_closing {
+                    return Some((closing_tag(&name), end_index));
+                }
+
+                let mut result = vec![60];  // into_v32("<")
+                result.extend(name);
+
+                for (attribute, value) in attributes.into_iter() {
+
+                    if !allow_list.allows_attribute(&attribute) {
+                        continue;
+                    }
+
+                    match value {
+                        Some(value) => {
+                            // the browser sees the same url that `allows_url` has seen: the value is decoded here, and escaped again below
+                            let value = decode_entities(&value);
+
+                            if is_url_attribute(&attribute) && !allow_list.allows_url(&value) {
+                                continue;
+                            }
+
+                            result.push(32);  // into_v32(" ")
+                            result.extend(attribute);
+                            result.extend([61, 34]);  // into_v32("=\"")
+                            result.extend(escape_htmls(&value));
+                            result.push(34);  // into_v32("\"")
+                        },
+                        None => {
+                            result.push(32);  // into_v32(" ")
+                            result.extend(attribute);
+                        }
+                    }
+
+                }
+
+                if is_self_closing {
+                    result.push(47);  // into_v32("/")
+                }
+
+                result.push(62);  // into_v32(">")
+
+                Some((result, end_index))
+            }
+        }
+    }
+
+}
+
+// `RawHtmlPolicy::Sanitize` balances the tags of an inline scope, so that a document can't break the elements around it
+// a closing tag without its opening tag is rendered as a text, and the tags still open at the end of the scope are closed
+// `<b><i>a</b>` -> `<b><i>a</i></b>`, `a</b>` -> `a&lt;/b&gt;`
+pub fn balance_raw_html(nodes: Vec<InlineNode>) -> Vec<InlineNode> {
+    let mut result = Vec::with_capacity(nodes.len());
+    let mut open_tags = vec![];
+
+    for node in nodes.into_iter() {
+        balance_scope(node, &mut result, &mut open_tags);
+    }
+
+    while let Some(name) = open_tags.pop() {
+        result.push(InlineNode::RawHtml(closing_tag(&name)));
+    }
+
+    result
+}
+
+// the nodes in `InlineNode::Complex` are in the same scope, and the contents of the other nodes are in their own scopes
+fn balance_scope(node: InlineNode, result: &mut Vec<InlineNode>, open_tags: &mut Vec<Vec<u32>>) {
+
+    match node {
+        InlineNode::Complex(nodes) => {
+
+            for node in nodes.into_iter() {
+                balance_scope(node, result, open_tags);
+            }
+
+        },
+        InlineNode::RawHtml(html) => match read_rendered_tag(&html) {
+            Some((name, false)) => {
+                open_tags.push(name);
+                result.push(InlineNode::RawHtml(html));
+            },
+            Some((name, true)) => match open_tags.iter().rposition(|tag| *tag == name) {
+                Some(tag_index) => {
+
+                    for name in open_tags.drain(tag_index..).rev() {
+                        result.push(InlineNode::RawHtml(closing_tag(&name)));
+                    }
+
+                },
+                None => { result.push(InlineNode::Raw(escape_htmls(&html))); }
+            },
+            None => { result.push(InlineNode::RawHtml(html)); }
+        },
+        InlineNode::Link { text, destination, external } => {
+            result.push(InlineNode::Link { text: balance_raw_html(text), destination, external });
+        },
+        InlineNode::WikiLink { title, text, destination } => {
+            result.push(InlineNode::WikiLink { title, text: balance_raw_html(text), destination });
+        },
+        InlineNode::Decoration { deco_type, content } => {
+            result.push(InlineNode::Decoration { deco_type, content: balance_raw_html(content) });
+        },
+        _ => { result.push(node); }
+    }
+
+}
+
+// `read_raw_html` renders the sanitized tags in the same form: `<span class="x">`, `</span>` or `<br/>`
+// it returns the name of the tag and whether it's a closing tag, if the tag has to be balanced
+fn read_rendered_tag(html: &[u32]) -> Option<(Vec<u32>, bool)> {
+
+    if html.first() != Some(&('<' as u32)) || html.ends_with(&[47, 62]) {  // into_v32("/>")
+        return None;
+    }
+
+    let is_closing = html.get(1) == Some(&('/' as u32));
+    let name = html[(1 + is_closing as usize)..].iter().take_while(|c| is_tag_name_char(**c)).copied().collect::<Vec<u32>>();
+
+    if is_void_element(&name) {
+        None
+    }
+
+    else {
+        Some((name, is_closing))
+    }
+
+}
+
+fn closing_tag(name: &[u32]) -> Vec<u32> {
+    [vec![60, 47], name.to_vec(), vec![62]].concat()  // into_v32("</"), into_v32(">")
+}
+
+// elements without closing tags, like `<br>`
+fn is_void_element(name: &[u32]) -> bool {
+    [
+        "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track", "wbr"
+    ].iter().any(|tag| into_v32(tag) == name)
+}
+
+fn parse_raw_html(content: &[u32], index: usize) -> Option<(RawHtml, usize)> {
+    let mut index = index + 1;
+
+    // `<!-- comment -->`
+    if content[index..].starts_with(&[33, 45, 45]) {  // into_v32("!--")
+        index += 3;
+
+        while index + 2 < content.len() {
+
+            if content[index] == '-' as u32 && content[index + 1] == '-' as u32 && content[index + 2] == GREATER_THAN {
+                return Some((RawHtml::Comment, index + 2));
+            }
+
+            else if content[index] >= META_CHARACTER_OFFSET {
+                return None;
+            }
+
+            index += 1;
+        }
+
+        return None;
+    }
+
+    let is_closing = content.get(index) == Some(&('/' as u32));
+
+    if is_closing {
+        index += 1;
+    }
+
+    let name = read_name(content, &mut index, is_tag_name_char)?;
+    let mut attributes = vec![];
+
+    loop {
+        let has_whitespace = skip_whitespaces(content, &mut index);
+
+        match content.get(index) {
+            Some(c) if *c == GREATER_THAN => {
+                return Some((RawHtml::Tag { name, attributes, is_closing, is_self_closing: false }, index));
+            },
+            Some(c) if *c == '/' as u32 && !is_closing && content.get(index + 1) == Some(&GREATER_THAN) => {
+                return Some((RawHtml::Tag { name, attributes, is_closing, is_self_closing: true }, index + 1));
+            },
+            Some(_) if has_whitespace && !is_closing => {},
+            _ => { return None; }
+        }
+
+        let attribute = read_name(content, &mut index, is_attribute_name_char)?;
+        let mut index_after_name = index;
+        skip_whitespaces(content, &mut index_after_name);
+
+        if content.get(index_after_name) != Some(&('=' as u32)) {
+            attributes.push((attribute, None));
+            continue;
+        }
+
+        index = index_after_name + 1;
+        skip_whitespaces(content, &mut index);
+
+        let value_start_index;
+
+        match content.get(index) {
+            Some(quote) if *quote == DOUBLE_QUOTE || *quote == SINGLE_QUOTE => {
+                value_start_index = index + 1;
+                index = value_start_index + content[value_start_index..].iter().position(|c| c == quote)?;
+                attributes.push((attribute, Some(read_attribute_value(&content[value_start_index..index])?)));
+                index += 1;
+            },
+            Some(_) => {
+                value_start_index = index;
+
+                while index < content.len() && is_unquoted_value_char(content[index]) {
+                    index += 1;
+                }
+
+                if index == value_start_index {
+                    return None;
+                }
+
+                attributes.push((attribute, Some(read_attribute_value(&content[value_start_index..index])?)));
+            },
+            None => { return None; }
+        }
+
+    }
+
+}
+
+fn read_name(content: &[u32], index: &mut usize, is_name_char: fn(u32) -> bool) -> Option<Vec<u32>> {
+    let start_index = *index;
+
+    // names don't start with digits, `-` or `.`
+    match content.get(start_index) {
+        Some(c) if is_name_char(*c) && !(*c as u8).is_ascii_digit() && *c != '-' as u32 && *c != '.' as u32 => {},
+        _ => { return None; }
+    }
+
+    while *index < content.len() && is_name_char(content[*index]) {
+        *index += 1;
+    }
+
+    Some(content[start_index..*index].iter().map(lowercase).collect())
+}
+
+// code spans and math blocks are escaped with meta characters: a tag doesn't overlap with them
+fn read_attribute_value(value: &[u32]) -> Option<Vec<u32>> {
+
+    if value.iter().any(|c| *c >= META_CHARACTER_OFFSET) {
+        None
+    }
+
+    else {
+        Some(undo_backslash_escapes(&undo_html_escapes(value)))
+    }
+
+}
+
+fn skip_whitespaces(content: &[u32], index: &mut usize) -> bool {
+    let start_index = *index;
+
+    while *index < content.len() && is_whitespace(content[*index]) {
+        *index += 1;
+    }
+
+    *index > start_index
+}
+
+fn is_tag_name_char(c: u32) -> bool {
+    c < 128 && ((c as u8).is_ascii_alphanumeric() || c == '-' as u32)
+}
+
+fn is_attribute_name_char(c: u32) -> bool {
+    c < 128 && ((c as u8).is_ascii_alphanumeric() || c == '-' as u32 || c == '_' as u32 || c == ':' as u32 || c == '.' as u32)
+}
+
+fn is_unquoted_value_char(c: u32) -> bool {
+    !is_whitespace(c) && c != '=' as u32 && c != '`' as u32
+    && c != LESS_THAN && c != GREATER_THAN && c != DOUBLE_QUOTE && c != SINGLE_QUOTE
+    && c < META_CHARACTER_OFFSET
+}
+
+fn is_whitespace(c: u32) -> bool {
+    c == ' ' as u32 || c == '\n' as u32 || c == '\t' as u32
+}
+
+fn is_url_attribute(name: &[u32]) -> bool {
+    [
+        &[104, 114, 101, 102][..],  // into_v32("href")
+        &[115, 114, 99][..],        // into_v32("src")
+        &[99, 105, 116, 101][..],   // into_v32("cite")
+        &[97, 99, 116, 105, 111, 110][..],  // into_v32("action")
+        &[102, 111, 114, 109, 97, 99, 116, 105, 111, 110][..],  // into_v32("formaction")
+        &[112, 111, 115, 116, 101, 114][..],  // into_v32("poster")
+    ].contains(&name)
+}
+
+// `JavaScript:alert(1)` -> `javascript`, `/docs/a:b` -> None
+// browsers ignore whitespaces and control characters in schemes: `java\tscript:` is `javascript:`
+fn get_url_scheme(url: &[u32]) -> Option<Vec<u32>> {
+    let url = url.iter().filter(|c| **c > 32 && **c != 127).copied().collect::<Vec<u32>>();
+    let colon_index = url.iter().position(|c| *c == ':' as u32)?;
+
+    if url[..colon_index].iter().any(|c| *c == '/' as u32 || *c == '?' as u32 || *c == '#' as u32) {
+        None
+    }
+
+    else {
+        Some(url[..colon_index].iter().map(lowercase).collect())
+    }
+
+}
+
+// `&#115;`, `&#x73;`, `&amp;`, `&lt;`, `&gt;`, `&quot;`, `&apos;`
+// the other named entities are not decoded, so they're rendered as texts
+fn decode_entities(value: &[u32]) -> Vec<u32> {
+    let mut result = Vec::with_capacity(value.len());
+    let mut index = 0;
+
+    while index < value.len() {
+
+        if value[index] == '&' as u32 {
+
+            if let Some(length) = value[index..].iter().take(12).position(|c| *c == ';' as u32) {
+                let entity = from_v32(&value[(index + 1)..(index + length)]);
+
+                let decoded = match entity.as_str() {
+                    "amp" => Some('&' as u32),
+                    "lt" => Some('<' as u32),
+                    "gt" => Some('>' as u32),
+                    "quot" => Some('"' as u32),
+                    "apos" => Some('\'' as u32),
+                    _ => match entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => entity.strip_prefix('#').and_then(|dec| dec.parse::<u32>().ok())
+                    }
+                };
+
+                if let Some(c) = decoded.filter(|c| *c > 0 && char::from_u32(*c).is_some()) {
+                    result.push(c);
+                    index += length + 1;
+                    continue;
+                }
+
+            }
+
+        }
+
+        result.push(value[index]);
+        index += 1;
+    }
+
+    result
+}
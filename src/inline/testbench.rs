@@ -2,7 +2,9 @@ use super::predicate::*;
 use crate::inline::InlineNode;
 use crate::utils::{into_v32, from_v32};
 use crate::escape::escape_backslashes;
-use crate::render::render_option::RenderOption;
+use crate::render::render_option::{RawHtmlPolicy, RenderOption};
+use crate::render::diagnostic::Diagnostic;
+use crate::{render_to_html, HtmlAllowList};
 use crate::ast::doc_data::DocData;
 
 fn samples() -> Vec<(String, String)> {  // (test_case, answer)
@@ -91,14 +93,14 @@ fn samples() -> Vec<(String, String)> {  // (test_case, answer)
         ("[[char = won]], [[char = euro]], [[char = therefore]]", "&#8361;, &euro;, &there4;"),
         ("[[red]][[center]]**This text is bold, center aligned and red.**[[/center]][[/red]]", "<span class=\"color-red\"><span class=\"align-center\"><strong>This text is bold, center aligned and red.</strong></span></span>"),
         ("`[[red]]red in a code span[[/red]]`, [[red]]`a code span in red`[[/red]]", "<code class=\"inline-code-span\">[[red]]red in a code span[[/red]]</code>, <span class=\"color-red\"><code class=\"inline-code-span\">a code span in red</code></span>"),
-        ("[[math]] `codespan` [[/math]] `[[math]] codespan [[/math]]`", "<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mo>`</mo><mi>codespan</mi><mo>`</mo></math> <code class=\"inline-code-span\">[[math]] codespan [[/math]]</code>"),
-        ("`[[math]] codespan` [[/math]] [[math]] `codespan [[/math]]`", "<code class=\"inline-code-span\">[[math]] codespan</code> [[/math]] <math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mo>`</mo><mi>codespan</mi></math>`"),
-        ("[[math]] `codespan [[/math]]` `[[math]] codespan` [[/math]]", "<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mo>`</mo><mi>codespan</mi></math>` <code class=\"inline-code-span\">[[math]] codespan</code> [[/math]]"),
-        ("[[math]] a * b * c = abc [[/math]]", "<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mi>a</mi><mo>*</mo><mi>b</mi><mo>*</mo><mi>c</mi><mo>=</mo><mi>abc</mi></math>"),
-        ("[[math]] \\\\a + b [[/math]]", "<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mo>\\</mo><mi>a</mi><mo>+</mo><mi>b</mi></math>"),
-        ("[[math]]sup{X}{*} = sup{X}{**}[[/math]]", "<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><msup><mi>X</mi><mo>*</mo></msup><mo>=</mo><msup><mi>X</mi><mrow><mo>*</mo><mo>*</mo></mrow></msup></math>"),
+        ("[[math]] `codespan` [[/math]] `[[math]] codespan [[/math]]`", "<math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"`codespan`\"><mo>`</mo><mi>codespan</mi><mo>`</mo></math> <code class=\"inline-code-span\">[[math]] codespan [[/math]]</code>"),
+        ("`[[math]] codespan` [[/math]] [[math]] `codespan [[/math]]`", "<code class=\"inline-code-span\">[[math]] codespan</code> [[/math]] <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"`codespan\"><mo>`</mo><mi>codespan</mi></math>`"),
+        ("[[math]] `codespan [[/math]]` `[[math]] codespan` [[/math]]", "<math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"`codespan\"><mo>`</mo><mi>codespan</mi></math>` <code class=\"inline-code-span\">[[math]] codespan</code> [[/math]]"),
+        ("[[math]] a * b * c = abc [[/math]]", "<math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"a * b * c = abc\"><mi>a</mi><mo>*</mo><mi>b</mi><mo>*</mo><mi>c</mi><mo>=</mo><mi>abc</mi></math>"),
+        ("[[math]] \\\\a + b [[/math]]", "<math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"\\a + b\"><mo>\\</mo><mi>a</mi><mo>+</mo><mi>b</mi></math>"),
+        ("[[math]]sup{X}{*} = sup{X}{**}[[/math]]", "<math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"X^* = X^(**)\"><msup><mi>X</mi><mo>*</mo></msup><mo>=</mo><msup><mi>X</mi><mrow><mo>*</mo><mo>*</mo></mrow></msup></math>"),
         ("[[highlight = red]] This text is highlighted! [[/highlight]]", "<span class=\"highlight-red\"> This text is highlighted! </span>"),
-        ("*inter-math inline element [[math]] F * G [[/math]]", "*inter-math inline element <math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mi>F</mi><mo>*</mo><mi>G</mi></math>"),
+        ("*inter-math inline element [[math]] F * G [[/math]]", "*inter-math inline element <math xmlns=\"http://www.w3.org/1998/Math/MathML\" alttext=\"F * G\"><mi>F</mi><mo>*</mo><mi>G</mi></math>"),
         ("[[highlight]] [[highlight = red]] [[/highlight]] [[highlight = invalid_color]] [[/highlight]]", "[[highlight]] <span class=\"highlight-red\"> </span> [[highlight = invalid_color]] [[/highlight]]"),
         ("[[red]] [[big]] error [[/red]] [[/big]]", "<span class=\"color-red\"> [[big]] error </span> [[/big]]"),
         ("[[div, class = foo]] abc [[/div]]", "<div class=\"foo\"> abc </div>"),
@@ -211,4 +213,89 @@ fn predicate_test() {
         }
 
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn raw_html_test() {
+    let md = "Hello <span class=\"x\" onclick=\"evil()\">*world*</span> <!-- comment -->
+
+<a href=\"java&#115;cript:alert(1)\">a</a> <a href=\"/docs/a:b\" title='\"q\"'>b</a> <script>alert(1)</script>
+
+`<b>code</b>` 1 < 2 <br/>
+
+[[tooltip = message]]hover[[/tooltip]]
+
+[^message]: Tooltip
+";
+
+    let escaped = render_to_html(md, RenderOption::default());
+    assert!(escaped.content.starts_with("<p>Hello &lt;span class=&quot;x&quot; onclick=&quot;evil()&quot;&gt;<em>world</em>&lt;/span&gt; &lt;!-- comment --&gt;</p>"));
+    assert!(escaped.diagnostics.is_empty());
+
+    let mut render_option = RenderOption::default();
+    render_option.set_raw_html(RawHtmlPolicy::Sanitize(HtmlAllowList::default()));
+    let sanitized = render_to_html(md, render_option);
+
+    assert!(sanitized.content.starts_with("<p>Hello <span class=\"x\"><em>world</em></span> </p>"));
+    assert!(sanitized.content.contains("<a>a</a> <a href=\"/docs/a:b\" title=\"&quot;q&quot;\">b</a> &lt;script&gt;alert(1)&lt;/script&gt;"));
+    assert!(sanitized.content.contains("<code class=\"inline-code-span\">&lt;b&gt;code&lt;/b&gt;</code> 1 &lt; 2 <br/>"));
+    assert!(sanitized.content.contains("<span class=\"tooltip-container\" id=\"tooltip-container-0\">hover<span class=\"tooltip-message\" id=\"tooltip-message-0\">Tooltip</span></span>"));
+    assert_eq!(sanitized.diagnostics, vec![
+        Diagnostic::DisallowedHtml { html: String::from("<script>") },
+        Diagnostic::DisallowedHtml { html: String::from("</script>") },
+    ]);
+
+    let mut render_option = RenderOption::default();
+    render_option.set_raw_html(RawHtmlPolicy::Trusted);
+    let trusted = render_to_html(md, render_option);
+
+    assert!(trusted.content.starts_with("<p>Hello <span class=\"x\" onclick=\"evil()\"><em>world</em></span> <!-- comment --></p>"));
+    assert!(trusted.content.contains("<script>alert(1)</script>"));
+    assert!(trusted.content.contains("<code class=\"inline-code-span\">&lt;b&gt;code&lt;/b&gt;</code>"));
+
+    // the allow list is configurable
+    let mut allow_list = HtmlAllowList::default();
+    allow_list.tags.push(String::from("Button"));
+    allow_list.url_schemes = vec![String::from("https")];
+
+    let mut render_option = RenderOption::default();
+    render_option.set_raw_html(RawHtmlPolicy::Sanitize(allow_list));
+    let rendered = render_to_html("<button>A</button> <a href=\"http://a.com\">B</a> <a href=\"HTTPS://a.com\">C</a>", render_option).content;
+
+    assert_eq!(rendered, "<p><button>A</button> <a>B</a> <a href=\"HTTPS://a.com\">C</a></p>");
+
+    // the tags are balanced in each inline scope
+    let mut render_option = RenderOption::default();
+    render_option.set_raw_html(RawHtmlPolicy::Sanitize(HtmlAllowList::default()));
+    let rendered = render_to_html("<b><i>a</b> c</i> </span>d <span id=\"x\">e<br>
+
+**<b>a** b</b> [<u>link](/a)", render_option).content;
+
+    assert_eq!(rendered, "<p><b><i>a</i></b> c&lt;/i&gt; &lt;/span&gt;d <span>e<br></span></p><p><strong><b>a</b></strong> b&lt;/b&gt; <a href=\"/a\"><u>link</u></a></p>");
+}
+
+#[test]
+fn extended_autolink_test() {
+    let md = "Visit www.github.com, https://github.com/a_(b)) or ftp://example.com.
+
+Call tel:+82-10-1234-5678.
+
+See [docs](/docs/a), [home](https://example.com) or [link](/r?u=http://x).
+
+[see www.github.com](/u) and [[[Home|see https://example.com]]]";
+
+    let rendered = render_to_html(md, RenderOption::default());
+
+    assert_eq!(rendered.content, "<p>Visit <a href=\"http://www.github.com\">www.github.com</a>, <a href=\"https://github.com/a_(b)\">https://github.com/a_(b)</a>) or <a href=\"ftp://example.com\">ftp://example.com</a>.</p><p>Call <a href=\"tel:+82-10-1234-5678\">tel:+82-10-1234-5678</a>.</p><p>See <a href=\"/docs/a\">docs</a>, <a href=\"https://example.com\">home</a> or <a href=\"/r?u=http://x\">link</a>.</p><p><a href=\"/u\">see www.github.com</a> and <a class=\"wiki-link\" href=\"home\">see https://example.com</a></p>");
+
+    let mut render_option = RenderOption::default();
+    render_option.set_autolink_schemes(vec![String::from("https")]).set_external_link_attributes(true);
+    let rendered = render_to_html(md, render_option);
+
+    assert!(rendered.content.contains("<a href=\"https://github.com/a_(b)\" rel=\"nofollow noopener\" target=\"_blank\">"));
+    assert!(rendered.content.contains("<a href=\"http://www.github.com\" rel=\"nofollow noopener\" target=\"_blank\">"));
+    assert!(rendered.content.contains("<p>Call tel:+82-10-1234-5678.</p>"));
+    assert!(rendered.content.contains("<a href=\"/docs/a\">docs</a>"));
+    assert!(rendered.content.contains("<a href=\"https://example.com\" rel=\"nofollow noopener\" target=\"_blank\">home</a>"));
+    assert!(rendered.content.contains("<a href=\"/r?u=http://x\">link</a>"));
+}
@@ -4,7 +4,7 @@ mod color;
 mod escape;
 mod file_ext;
 mod inline;
-// mod obfuscate;  // WIP
+mod obfuscate;
 mod render;
 mod utils;
 
@@ -15,17 +15,29 @@ mod testbench;
 pub const PRINT_TEST_PAGES: bool = false;  // use it only when you need `.md` files
 
 pub use render::{
+    render_page,
     render_to_html,
     render_to_html_with_default_options,
-    render_option::RenderOption,
+    page::DEFAULT_PAGE_TEMPLATE,
+    render_option::{RenderOption, CitationStyle, EmailObfuscation, FootnoteLayout, IconMode, RawHtmlPolicy, TextDirection},
+    diagnostic::Diagnostic,
     render_result::RenderResult
 };
 
+pub use container::icon::IconSet;
+pub use render::asset::{Assets, stylesheet};
+pub use render::csp::csp_javascript;
+pub use inline::citation::bibliography::{Bibliography, BibEntry, Author};
+pub use inline::raw_html::HtmlAllowList;
+pub use inline::link::wiki::{WikiLink, WikiLinkResolver};
+pub use inline::math::{find_math_errors, math_to_plain_text, math_to_speech, MathError};
+
 // Don't hide these: see commit 4a01db5
-pub use color::{Color, colors};
+pub use color::{Color, colors, colors_to_css};
 
 pub use container::table::macros::collapsible_table_javascript;
 pub use inline::macros::tooltip::tooltip_javascript;
+pub use obfuscate::email_javascript;
 
 /// `Reference.md` is an example mdxt file.
 /// See how it works.
@@ -37,7 +49,7 @@ pub fn render_reference() -> String {
     let mut f = File::open("reference.md").unwrap();
     f.read_to_string(&mut s).unwrap();
 
-    let raw_html = render_to_html_with_default_options(&s);
+    let options = RenderOption::default();
 
-    utils::add_styles_to_html(&raw_html)
+    render_to_html(&s, options.clone()).to_page(&options, "MDxt Reference")
 }
\ No newline at end of file
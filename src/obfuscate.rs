@@ -0,0 +1,204 @@
+use crate::ast::doc_data::DocData;
+use crate::escape::HTML_ESCAPE_OFFSET;
+use crate::inline::InlineNode;
+use crate::inline::macros::normalize_macro;
+use crate::render::render_option::{EmailObfuscation, RenderOption};
+use crate::utils::{from_v32, get_bracket_end_index, into_v32, is_alpha_numeric, lowercase, strip_whitespaces};
+
+/*
+Email addresses are rendered in forms that scrapers can't read easily. See `RenderOption.email_obfuscation`.
+
+- `[[email = user@example.com]]`
+- `<user@example.com>`
+- `user@example.com`, found by `inline::auto_url`
+
+`EmailObfuscation::Entities` renders each character of the address to a numeric character reference.
+`EmailObfuscation::Javascript` doesn't render the address at all. The element has the address in reverse,
+and `email_javascript` assembles the link when the page is loaded.
+*/
+
+// `content[index]` is an escaped `<`: `<user@example.com>`
+pub fn read_email_autolink(content: &[u32], index: usize) -> Option<(Vec<u32>, usize)> {  // (address, last_index)
+    let end_index = index + 1 + content[(index + 1)..].iter().position(|c| *c == '>' as u32 + HTML_ESCAPE_OFFSET)?;
+    let address = &content[(index + 1)..end_index];
+
+    if is_valid_email(address) {
+        Some((address.to_vec(), end_index))
+    }
+
+    else {
+        None
+    }
+
+}
+
+// `[[email = user@example.com]]`
+// it's not a normal macro: `read_macro` doesn't allow `@` and `.`, and it normalizes the arguments
+pub fn read_email_macro(content: &[u32], index: usize) -> Option<(Vec<u32>, usize)> {  // (address, last_index)
+
+    if content.get(index + 1) != Some(&('[' as u32)) {
+        return None;
+    }
+
+    let end_index = get_bracket_end_index(content, index)?;
+
+    if get_bracket_end_index(content, index + 1)? + 1 != end_index {
+        return None;
+    }
+
+    let mut key_and_value = content[(index + 2)..(end_index - 1)].splitn(2, |c| *c == '=' as u32);
+
+    if normalize_macro(key_and_value.next()?) != [101, 109, 97, 105, 108] {  // into_v32("email")
+        return None;
+    }
+
+    let address = strip_whitespaces(key_and_value.next()?);
+
+    if is_valid_email(&address) {
+        Some((address, end_index))
+    }
+
+    else {
+        None
+    }
+
+}
+
+// `mailto:` of an autolink is removed
+pub fn email_to_node(address: Vec<u32>, doc_data: &mut DocData, render_option: &RenderOption) -> InlineNode {
+    let address = if address.len() > 7 && address[..7].iter().map(lowercase).eq([109, 97, 105, 108, 116, 111, 58]) {  // into_v32("mailto:")
+        address[7..].to_vec()
+    } else {
+        address
+    };
+
+    if render_option.email_obfuscation == EmailObfuscation::Javascript {
+        doc_data.has_javascript_email = true;
+    }
+
+    InlineNode::Email { address, obfuscation: render_option.email_obfuscation }
+}
+
+pub fn obfuscate_email(address: &[u32], obfuscation: EmailObfuscation, class_prefix: &str) -> Vec<u32> {
+
+    match obfuscation {
+        EmailObfuscation::Entities => [
+            vec![60, 97, 32, 104, 114, 101, 102, 61, 34],  // into_v32("<a href=\"")
+            to_entities(&[&[109, 97, 105, 108, 116, 111, 58], address].concat()),  // into_v32("mailto:")
+            vec![34, 62],  // into_v32("\">")
+            to_entities(address),
+            vec![60, 47, 97, 62],  // into_v32("</a>")
+        ].concat(),
+
+        // `user@example.com` -> `moc.elpmaxe|resu`
+        EmailObfuscation::Javascript => {
+            let at_index = address.iter().position(|c| *c == '@' as u32).unwrap();
+            let mut user = address[..at_index].to_vec();
+            let mut domain = address[(at_index + 1)..].to_vec();
+            user.reverse();
+            domain.reverse();
+
+            into_v32(&format!(
+                "<a class=\"{class_prefix}email\" data-mdxt-email=\"{}|{}\"></a>",
+                from_v32(&domain),
+                from_v32(&user)
+            ))
+        }
+    }
+
+}
+
+// `a` -> `&#97;`, `b` -> `&#x62;`
+// it mixes decimal and hexadecimal references, so that a simple search and replace doesn't decode them
+fn to_entities(content: &[u32]) -> Vec<u32> {
+    content.iter().enumerate().map(
+        |(index, c)| if index % 2 == 0 {
+            into_v32(&format!("&#{c};"))
+        } else {
+            into_v32(&format!("&#x{c:x};"))
+        }
+    ).collect::<Vec<Vec<u32>>>().concat()
+}
+
+// `user.name+tag@mail.example.com`
+// the local part has alphanumeric characters, `.`, `-`, `_` and `+`, and the domain has at least two labels
+pub fn is_valid_email(address: &[u32]) -> bool {
+    let at_index = match address.iter().position(|c| *c == '@' as u32) {
+        Some(at_index) => at_index,
+        None => { return false; }
+    };
+
+    let local = &address[..at_index];
+    let domain = &address[(at_index + 1)..];
+
+    !local.is_empty()
+    && local.iter().all(is_email_local_char)
+    && local[0] != '.' as u32 && local[local.len() - 1] != '.' as u32
+    && domain.split(|c| *c == '.' as u32).count() > 1
+    && domain.split(|c| *c == '.' as u32).all(
+        |label| !label.is_empty()
+        && label.iter().all(|c| is_alpha_numeric(c) || *c == '-' as u32)
+        && label[0] != '-' as u32 && label[label.len() - 1] != '-' as u32
+    )
+}
+
+pub fn is_email_local_char(c: &u32) -> bool {
+    is_alpha_numeric(c) || *c == '.' as u32 || *c == '-' as u32 || *c == '_' as u32 || *c == '+' as u32
+}
+
+/// `EmailObfuscation::Javascript` renders each email address to `<a data-mdxt-email="moc.elpmaxe|resu"></a>`.
+/// This script turns them into `<a href="mailto:user@example.com">user@example.com</a>`.
+///
+/// ```javascript
+/// document.querySelectorAll("[data-mdxt-email]").forEach(e => {
+///     const [domain, user] = e.dataset.mdxtEmail.split("|").map(s => [...s].reverse().join(""));
+///     const address = user + "@" + domain;
+///
+///     e.href = "mailto:" + address;
+///     e.textContent = address;
+/// });
+/// ```
+pub fn email_javascript() -> String {
+"document.querySelectorAll(\"[data-mdxt-email]\").forEach(e => {
+    const [domain, user] = e.dataset.mdxtEmail.split(\"|\").map(s => [...s].reverse().join(\"\"));
+    const address = user + \"@\" + domain;
+
+    e.href = \"mailto:\" + address;
+    e.textContent = address;
+});".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{render_to_html, EmailObfuscation, RenderOption};
+
+    #[test]
+    fn email_test() {
+        let md = "[[email = a_b@example.com]], <a_b@example.com> and a_b@example.com.
+
+`a_b@example.com`, a@b, [[email = not an email]]";
+
+        let rendered = render_to_html(md, RenderOption::default());
+        let link = "<a href=\"&#109;&#x61;&#105;&#x6c;&#116;&#x6f;&#58;&#x61;&#95;&#x62;&#64;&#x65;&#120;&#x61;&#109;&#x70;&#108;&#x65;&#46;&#x63;&#111;&#x6d;\">&#97;&#x5f;&#98;&#x40;&#101;&#x78;&#97;&#x6d;&#112;&#x6c;&#101;&#x2e;&#99;&#x6f;&#109;</a>";
+
+        assert_eq!(rendered.content, format!(
+            "<p>{link}, {link} and {link}.</p><p><code class=\"inline-code-span\">a_b@example.com</code>, a@b, [[email = not an email]]</p>"
+        ));
+        assert!(!rendered.has_javascript_email);
+
+        let mut options = RenderOption::default();
+        options.set_email_obfuscation(EmailObfuscation::Javascript).set_class_prefix(String::from("mdxt-"));
+        let rendered = render_to_html("<a_b@example.com>", options.clone());
+
+        assert!(rendered.content.starts_with("<p><a class=\"mdxt-email\" data-mdxt-email=\"moc.elpmaxe|b_a\"></a></p><script>"));
+        assert!(!rendered.content.contains("a_b@example.com"));
+        assert!(rendered.has_javascript_email);
+
+        options.embed_js_for_emails(false);
+        let rendered = render_to_html("<a_b@example.com>", options.clone());
+
+        assert!(!rendered.content.contains("<script>"));
+        assert!(rendered.assets(&options).javascript.contains("data-mdxt-email"));
+    }
+
+}
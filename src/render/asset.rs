@@ -0,0 +1,415 @@
+use super::csp::{collapsible_table_listener, copy_button_listener, sidebar_listener};
+use super::render_option::RenderOption;
+use super::render_result::RenderResult;
+use crate::container::{
+    codefence::html::copy_button_javascript,
+    sidebar::sidebar_javascript,
+    table::macros::prefixed_collapsible_table_javascript
+};
+use crate::inline::macros::tooltip::prefixed_tooltip_javascript;
+use crate::obfuscate::email_javascript;
+use crate::utils::into_v32;
+use std::collections::HashMap;
+
+/*
+The styles and the scripts of a rendered document
+
+`styles/markdown.css` is embedded in the crate. Before it's handed to the user,
+- the rules of the features that the document doesn't use are removed
+- the class names are prefixed with `RenderOption.class_prefix`, except `.markdown`, which is the container of the document, not a part of the output
+- the comments and the unnecessary whitespaces are removed
+*/
+
+const MARKDOWN_CSS: &str = include_str!("../../styles/markdown.css");
+
+// classes and ids that only a feature uses
+// a selector is removed if it has a class or an id that is one of these, or starts with one of these and `-`
+const COLLAPSIBLE_TABLE_SELECTORS: [&str; 3] = [".collapsible", ".collapsed", ".invisible"];
+const TOOLTIP_SELECTORS: [&str; 2] = [".tooltip-container", ".tooltip-message"];
+const SIDEBAR_SELECTORS: [&str; 1] = ["#mdxt-sidebar"];
+const COPY_BUTTON_SELECTORS: [&str; 1] = [".copy-fenced-code"];
+const MISSING_WIKI_LINK_SELECTORS: [&str; 1] = [".missing-wiki-link"];
+
+/// The styles and the scripts that a rendered document needs. See `RenderResult::assets`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Assets {
+    /// Minified css, without the rules of the features that the document doesn't use.
+    pub css: String,
+
+    /// Minified javascript. Scripts that are already embedded in `RenderResult.content` are not included.
+    /// It has to run after the document is loaded: put it at the end of `<body>`.
+    pub javascript: String
+}
+
+/// The whole stylesheet, minified. It has the styles of all the features, so it can be shared by many documents.
+/// `class_prefix` has to be the same as `RenderOption.class_prefix`.
+pub fn stylesheet(class_prefix: &str) -> String {
+    rules_to_css(&prefix_rules(parse_rules(&minify_css(MARKDOWN_CSS)), class_prefix))
+}
+
+pub fn get_assets(result: &RenderResult, render_option: &RenderOption) -> Assets {
+    let class_prefix = &render_option.class_prefix;
+    let mut unused_selectors = vec![];
+    let mut scripts = vec![];
+
+    if result.has_collapsible_table {
+
+        if !render_option.javascript_for_collapsible_tables {
+            scripts.push(prefixed_collapsible_table_javascript(class_prefix));
+
+            if render_option.csp {
+                scripts.push(collapsible_table_listener());
+            }
+
+        }
+
+    }
+
+    else {
+        unused_selectors.extend(COLLAPSIBLE_TABLE_SELECTORS);
+    }
+
+    if !result.fenced_code_contents.is_empty() {
+
+        if !render_option.javascript_for_copy_buttons {
+            let codes = result.fenced_code_contents.iter().map(
+                |(index, code)| (*index, into_v32(code))
+            ).collect::<HashMap<usize, Vec<u32>>>();
+
+            scripts.push(copy_button_javascript(&codes));
+
+            if render_option.csp {
+                scripts.push(copy_button_listener());
+            }
+
+        }
+
+    }
+
+    else {
+        unused_selectors.extend(COPY_BUTTON_SELECTORS);
+    }
+
+    if result.has_tooltip {
+
+        if !render_option.javascript_for_tooltips {
+            scripts.push(prefixed_tooltip_javascript(class_prefix));
+        }
+
+    }
+
+    else {
+        unused_selectors.extend(TOOLTIP_SELECTORS);
+    }
+
+    if result.has_sidebar {
+
+        if !render_option.javascript_for_sidebar {
+            scripts.push(sidebar_javascript());
+
+            if render_option.csp {
+                scripts.push(sidebar_listener());
+            }
+
+        }
+
+    }
+
+    else {
+        unused_selectors.extend(SIDEBAR_SELECTORS);
+    }
+
+    if result.wiki_links.iter().all(|link| link.destination.is_some()) {
+        unused_selectors.extend(MISSING_WIKI_LINK_SELECTORS);
+    }
+
+    if result.has_javascript_email && !render_option.javascript_for_emails {
+        scripts.push(email_javascript());
+    }
+
+    let rules = remove_unused_rules(parse_rules(&minify_css(MARKDOWN_CSS)), &unused_selectors);
+
+    Assets {
+        css: rules_to_css(&prefix_rules(rules, class_prefix)),
+        javascript: minify_javascript(&scripts.join("\n"))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Rule {
+    // `.markdown p{margin:0}`
+    Style { selectors: String, declarations: String },
+
+    // `@media (max-width: 60rem){...}`
+    Block { prelude: String, rules: Vec<Rule> },
+
+    // `@charset "utf-8";`, and `@font-face{...}`, whose contents are not rules
+    Raw(String)
+}
+
+// removes comments, and whitespaces that don't change the meaning
+pub(crate) fn minify_css(css: &str) -> String {
+    let mut result = String::with_capacity(css.len());
+    let mut chars = css.chars().peekable();
+    let mut has_whitespace = false;
+
+    while let Some(c) = chars.next() {
+
+        if c == '/' && chars.peek() == Some(&'*') {
+            chars.next();
+
+            while let Some(c) = chars.next() {
+
+                if c == '*' && chars.peek() == Some(&'/') {
+                    chars.next();
+                    break;
+                }
+
+            }
+
+            has_whitespace = true;
+            continue;
+        }
+
+        if c.is_whitespace() {
+            has_whitespace = true;
+            continue;
+        }
+
+        if has_whitespace {
+
+            match result.chars().last() {
+                Some(last) if !is_css_punctuation(last) && !is_css_punctuation(c) => { result.push(' '); },
+                _ => {}
+            }
+
+            has_whitespace = false;
+        }
+
+        // `{color:red;}` -> `{color:red}`
+        if c == '}' && result.ends_with(';') {
+            result.pop();
+        }
+
+        result.push(c);
+
+        // strings are kept as they are
+        if c == '"' || c == '\'' {
+
+            while let Some(s) = chars.next() {
+                result.push(s);
+
+                if s == '\\' {
+
+                    if let Some(escaped) = chars.next() {
+                        result.push(escaped);
+                    }
+
+                }
+
+                else if s == c {
+                    break;
+                }
+
+            }
+
+        }
+
+    }
+
+    result
+}
+
+fn is_css_punctuation(c: char) -> bool {
+    c == '{' || c == '}' || c == ';' || c == ',' || c == '>'
+}
+
+// `css` is minified
+fn parse_rules(css: &str) -> Vec<Rule> {
+    let chars = css.chars().collect::<Vec<char>>();
+    let mut index = 0;
+
+    parse_rules_until_close(&chars, &mut index)
+}
+
+fn parse_rules_until_close(css: &[char], index: &mut usize) -> Vec<Rule> {
+    let mut result = vec![];
+
+    while *index < css.len() {
+
+        if css[*index] == '}' {
+            *index += 1;
+            break;
+        }
+
+        let prelude = read_css_until(css, index, &['{', ';', '}']);
+
+        match css.get(*index) {
+            Some('{') if prelude.starts_with("@media") || prelude.starts_with("@supports") => {
+                *index += 1;
+                let rules = parse_rules_until_close(css, index);
+
+                result.push(Rule::Block { prelude, rules });
+            },
+            Some('{') => {
+                *index += 1;
+                let declarations = read_css_until(css, index, &['}']);
+                *index += 1;
+
+                if prelude.starts_with('@') {
+                    result.push(Rule::Raw(format!("{prelude}{{{declarations}}}")));
+                }
+
+                else {
+                    result.push(Rule::Style { selectors: prelude, declarations });
+                }
+
+            },
+            Some(';') => {
+                *index += 1;
+                result.push(Rule::Raw(format!("{prelude};")));
+            },
+
+            // `}` is handled by the next iteration
+            _ => {}
+        }
+
+    }
+
+    result
+}
+
+// it doesn't consume `ends`
+fn read_css_until(css: &[char], index: &mut usize, ends: &[char]) -> String {
+    let mut result = String::new();
+    let mut quote = None;
+
+    while *index < css.len() {
+        let c = css[*index];
+
+        match quote {
+            Some(q) if c == q => { quote = None; },
+            Some(_) => {},
+            None if c == '"' || c == '\'' => { quote = Some(c); },
+            None if ends.contains(&c) => { break; },
+            None => {}
+        }
+
+        result.push(c);
+        *index += 1;
+    }
+
+    result
+}
+
+fn rules_to_css(rules: &[Rule]) -> String {
+    rules.iter().map(
+        |rule| match rule {
+            Rule::Style { selectors, declarations } => format!("{selectors}{{{declarations}}}"),
+            Rule::Block { prelude, rules } => format!("{prelude}{{{}}}", rules_to_css(rules)),
+            Rule::Raw(rule) => rule.to_string()
+        }
+    ).collect()
+}
+
+fn remove_unused_rules(rules: Vec<Rule>, unused_selectors: &[&str]) -> Vec<Rule> {
+    rules.into_iter().filter_map(
+        |rule| match rule {
+            Rule::Style { selectors, declarations } => {
+                let selectors = selectors.split(',').filter(
+                    |selector| !unused_selectors.iter().any(|unused| has_selector(selector, unused))
+                ).collect::<Vec<&str>>().join(",");
+
+                if selectors.is_empty() {
+                    None
+                }
+
+                else {
+                    Some(Rule::Style { selectors, declarations })
+                }
+
+            },
+            Rule::Block { prelude, rules } => {
+                let rules = remove_unused_rules(rules, unused_selectors);
+
+                if rules.is_empty() {
+                    None
+                }
+
+                else {
+                    Some(Rule::Block { prelude, rules })
+                }
+
+            },
+            rule => Some(rule)
+        }
+    ).collect()
+}
+
+// `has_selector(".markdown .tooltip-container:hover", ".tooltip-container")` -> true
+// `has_selector("#mdxt-sidebar-toggle", "#mdxt-sidebar")` -> true
+fn has_selector(selector: &str, name: &str) -> bool {
+    selector.match_indices(name).any(
+        |(index, _)| match selector[(index + name.len())..].chars().next() {
+            Some(c) => c == '-' || !is_css_identifier(c),
+            None => true
+        }
+    )
+}
+
+fn prefix_rules(rules: Vec<Rule>, class_prefix: &str) -> Vec<Rule> {
+
+    if class_prefix.is_empty() {
+        return rules;
+    }
+
+    rules.into_iter().map(
+        |rule| match rule {
+            Rule::Style { selectors, declarations } => Rule::Style { selectors: prefix_classes(&selectors, class_prefix), declarations },
+            Rule::Block { prelude, rules } => Rule::Block { prelude, rules: prefix_rules(rules, class_prefix) },
+            rule => rule
+        }
+    ).collect()
+}
+
+// `.markdown .box` -> `.markdown .mdxt-box`
+pub(crate) fn prefix_classes(selectors: &str, class_prefix: &str) -> String {
+    let chars = selectors.chars().collect::<Vec<char>>();
+    let mut result = String::with_capacity(selectors.len() + class_prefix.len() * 4);
+    let mut quote = None;
+
+    for (index, c) in chars.iter().enumerate() {
+        result.push(*c);
+
+        match quote {
+            Some(q) if *c == q => { quote = None; },
+            Some(_) => {},
+            None if *c == '"' || *c == '\'' => { quote = Some(*c); },
+            None if *c == '.' && matches!(chars.get(index + 1), Some(c) if is_css_identifier(*c) && !c.is_ascii_digit()) => {
+                let class_name = chars[(index + 1)..].iter().take_while(|c| is_css_identifier(**c)).collect::<String>();
+
+                if class_name != "markdown" {
+                    result.push_str(class_prefix);
+                }
+
+            },
+            None => {}
+        }
+
+    }
+
+    result
+}
+
+fn is_css_identifier(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii()
+}
+
+// the engine's scripts don't have comments or multiline strings, so it only removes indentations and empty lines
+fn minify_javascript(javascript: &str) -> String {
+    javascript.lines().map(
+        |line| line.trim()
+    ).filter(
+        |line| !line.is_empty()
+    ).collect::<Vec<&str>>().join("\n")
+}
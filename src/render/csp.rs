@@ -0,0 +1,51 @@
+/*
+`RenderOption.csp`: a strict Content-Security-Policy blocks inline event handlers like `onclick="collapse_table('0')"`.
+
+Instead, the elements have `data-*` attributes, and these scripts listen to them.
+- `<thead data-mdxt-collapse="0">`: `collapse_table('0')`
+- `<button data-mdxt-copy="0">`: `copy_code_to_clipboard(0)`
+- `<div data-mdxt-sidebar="toggle">`, `<div data-mdxt-sidebar="close">`: `mdxt_toggle_sidebar()`, `mdxt_close_sidebar()`
+
+Each listener follows the script of its feature, so that the listeners are not registered twice,
+even if some scripts are embedded and the others are not.
+*/
+
+pub(crate) fn collapsible_table_listener() -> String {
+"document.querySelectorAll(\"[data-mdxt-collapse]\").forEach(e => {
+    e.addEventListener(\"click\", () => collapse_table(e.dataset.mdxtCollapse));
+});".to_string()
+}
+
+pub(crate) fn copy_button_listener() -> String {
+"document.querySelectorAll(\"[data-mdxt-copy]\").forEach(e => {
+    e.addEventListener(\"click\", () => copy_code_to_clipboard(Number(e.dataset.mdxtCopy)));
+});".to_string()
+}
+
+pub(crate) fn sidebar_listener() -> String {
+"document.querySelectorAll(\"[data-mdxt-sidebar]\").forEach(e => {
+    e.addEventListener(\"click\", () => e.dataset.mdxtSidebar === \"close\" ? mdxt_close_sidebar() : mdxt_toggle_sidebar());
+});".to_string()
+}
+
+/// With `RenderOption::set_csp`, the elements don't have inline event handlers. This script registers the handlers.
+/// You need it only when you write your own scripts: the engine's scripts, either embedded or from `RenderResult::assets`, already have it.
+///
+/// ```javascript
+/// document.querySelectorAll("[data-mdxt-collapse]").forEach(e => {
+///     e.addEventListener("click", () => collapse_table(e.dataset.mdxtCollapse));
+/// });
+/// document.querySelectorAll("[data-mdxt-copy]").forEach(e => {
+///     e.addEventListener("click", () => copy_code_to_clipboard(Number(e.dataset.mdxtCopy)));
+/// });
+/// document.querySelectorAll("[data-mdxt-sidebar]").forEach(e => {
+///     e.addEventListener("click", () => e.dataset.mdxtSidebar === "close" ? mdxt_close_sidebar() : mdxt_toggle_sidebar());
+/// });
+/// ```
+pub fn csp_javascript() -> String {
+    [
+        collapsible_table_listener(),
+        copy_button_listener(),
+        sidebar_listener(),
+    ].join("\n")
+}
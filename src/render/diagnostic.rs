@@ -0,0 +1,43 @@
+use std::fmt;
+
+/// Problems found while rendering a document.
+/// They don't stop the rendering: the engine renders the document as well as it can, and reports them in `RenderResult`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Diagnostic {
+    /// `[[ref = label]]` whose label is not defined anywhere in the document.
+    DanglingReference { label: String },
+
+    /// The same label is defined more than once. References point to the first one.
+    DuplicateLabel { label: String },
+
+    /// `[[icon = name]]` or `icon = name` of an admonition whose icon is neither built-in nor in `RenderOption.icons`.
+    /// The macro is rendered as a plain text.
+    UnknownIcon { name: String },
+
+    /// A math formula that the parser doesn't understand. `position` is in characters from the start of `formula`.
+    MathError { formula: String, message: String, position: usize },
+
+    /// An html tag that `RawHtmlPolicy::Sanitize` doesn't allow. It's rendered as a text.
+    DisallowedHtml { html: String },
+
+    /// A wiki link, like `[[[Page Name]]]`, whose title `RenderOption.wiki_link_resolver` doesn't know.
+    /// It's reported once for each title.
+    MissingWikiLink { title: String }
+}
+
+impl fmt::Display for Diagnostic {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+
+        match self {
+            Diagnostic::DanglingReference { label } => write!(f, "`[[ref = {label}]]` refers to an undefined label"),
+            Diagnostic::DuplicateLabel { label } => write!(f, "the label `{label}` is defined more than once"),
+            Diagnostic::UnknownIcon { name } => write!(f, "there's no icon named `{name}`"),
+            Diagnostic::MathError { formula, message, position } => write!(f, "math error in `{formula}` at {position}: {message}"),
+            Diagnostic::DisallowedHtml { html } => write!(f, "`{html}` is not allowed"),
+            Diagnostic::MissingWikiLink { title } => write!(f, "there's no page titled `{title}`"),
+        }
+
+    }
+
+}
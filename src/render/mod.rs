@@ -1,10 +1,20 @@
+pub mod asset;
+pub mod csp;
+pub mod diagnostic;
+pub mod page;
 pub mod render_option;
 pub mod render_result;
 
+#[cfg(test)]
+mod testbench;
+
 use crate::ast::{AST, line::code_to_lines};
+use crate::container::icon::render_sprite;
 use crate::container::metadata::parse_metadata;
+use crate::inline::citation::load_bibliography_from_metadata;
 use crate::escape::{escape_backslashes, preprocess, render_html_escapes};
 use crate::utils::from_v32;
+use page::fill_template;
 use render_option::RenderOption;
 use render_result::RenderResult;
 
@@ -12,7 +22,21 @@ pub fn render_to_html_with_default_options(content: &str) -> String {
     render_to_html(content, RenderOption::default()).content
 }
 
-pub fn render_to_html(content: &str, mut options: RenderOption) -> RenderResult {
+pub fn render_to_html(content: &str, options: RenderOption) -> RenderResult {
+    render(content, options, false).0
+}
+
+/// A whole html page: `RenderOption.page_template` filled with the document, its toc, its sidebar and its assets.
+/// The title, the description, the language and the direction of the page come from the metadata. See `RenderOption::set_page_template`.
+pub fn render_page(content: &str, options: RenderOption) -> String {
+    let (result, options, toc, sidebar) = render(content, options, true);
+
+    fill_template(&result, &options, None, &from_v32(&toc), &from_v32(&sidebar))
+}
+
+// `render_to_html` and `render_page`
+// with `page`, the toc and the sidebar are rendered to their own slots, if the template has them
+fn render(content: &str, mut options: RenderOption, page: bool) -> (RenderResult, RenderOption, Vec<u32>, Vec<u32>) {  // (result, options, toc, sidebar)
     let mut u32_content = preprocess(content);
     u32_content = escape_backslashes(&u32_content);
 
@@ -23,6 +47,7 @@ pub fn render_to_html(content: &str, mut options: RenderOption) -> RenderResult
     if options.parse_metadata {
 
         if let Some((parsed_metadata, end_index)) = parse_metadata(&lines) {
+            load_bibliography_from_metadata(&parsed_metadata, &mut options);
             metadata = Some(parsed_metadata);
             lines = lines[end_index + 1..].to_vec();
         }
@@ -31,7 +56,14 @@ pub fn render_to_html(content: &str, mut options: RenderOption) -> RenderResult
 
     let mut ast = AST::from_lines(lines, &mut options);
 
-    let html = ast.to_html();
+    let toc_slot = page && options.page_template.contains("{{toc}}");
+    let sidebar_slot = page && options.page_template.contains("{{sidebar}}");
+
+    if toc_slot {
+        ast.doc_data.has_toc = true;
+    }
+
+    let (html, toc, sidebar) = ast.to_html(toc_slot, sidebar_slot);
 
     let clean_html = render_html_escapes(&html);
 
@@ -39,13 +71,22 @@ pub fn render_to_html(content: &str, mut options: RenderOption) -> RenderResult
         |(index, content)| (*index, from_v32(content))
     ).collect();
 
-    RenderResult {
+    let result = RenderResult {
         content: from_v32(&clean_html),
         has_collapsible_table: ast.doc_data.has_collapsible_table,
         has_tooltip: ast.doc_data.tooltip_count > 0,
         has_sidebar: !ast.sidebar.is_empty(),
+        has_javascript_email: ast.doc_data.has_javascript_email,
         metadata,
+        figure_count: ast.doc_data.figure_count,
+        table_count: ast.doc_data.table_count,
+        listing_count: ast.doc_data.listing_count,
+        equation_count: ast.doc_data.equation_count,
+        diagnostics: ast.doc_data.diagnostics.clone(),
+        wiki_links: ast.doc_data.wiki_links.clone(),
+        icon_sprite: from_v32(&render_sprite(&ast.doc_data.icon_symbols, &options.icon_mode)),
         fenced_code_contents
-    }
+    };
 
+    (result, options, render_html_escapes(&toc), render_html_escapes(&sidebar))
 }
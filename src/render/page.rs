@@ -0,0 +1,155 @@
+use super::asset::{Assets, get_assets};
+use super::render_option::{IconMode, RenderOption};
+use super::render_result::RenderResult;
+use crate::escape::{escape_htmls, render_html_escapes};
+use crate::utils::{from_v32, into_v32};
+use yaml_rust::Yaml;
+
+/*
+`render_page` fills a template with a rendered document.
+
+- `{{content}}`: `RenderResult.content`
+- `{{toc}}`: the table of contents, even if the document doesn't have `[[toc]]`
+- `{{sidebar}}`: the sidebar. If the template doesn't have it, the sidebar is a part of `{{content}}`, like `render_to_html`.
+- `{{title}}`, `{{description}}`: `title` and `description` of the metadata
+- `{{lang}}`, `{{dir}}`: `lang` and `dir` of the metadata, or `RenderOption.lang` and `RenderOption.text_direction`
+- `{{head}}`: `<meta>`s and the `<style>` that the document needs
+- `{{scripts}}`: the `<script>` that the document needs
+- `{{icon_sprite}}`: `RenderResult.icon_sprite` with `IconMode::Sprite`
+- `{{metadata.KEY}}`: a field of the metadata, `{{metadata.author.name}}` for a nested one
+
+The values from the metadata are escaped. Placeholders that are not listed above are kept as they are,
+so that the page can go through another template engine.
+*/
+
+/// The template of `render_page`, unless `RenderOption::set_page_template` sets another one.
+pub const DEFAULT_PAGE_TEMPLATE: &str = "<!DOCTYPE html>
+<html lang=\"{{lang}}\" dir=\"{{dir}}\">
+<head>
+{{head}}
+<title>{{title}}</title>
+</head>
+<body>
+{{icon_sprite}}<article class=\"markdown\">{{content}}</article>
+{{scripts}}
+</body>
+</html>
+";
+
+// `RenderResult::to_page`: `title` replaces the one in the metadata
+pub fn result_to_page(result: &RenderResult, render_option: &RenderOption, title: &str) -> String {
+    fill_template(result, render_option, Some(title), "", "")
+}
+
+// `toc` and `sidebar` are empty unless the template has their slots
+pub fn fill_template(
+    result: &RenderResult,
+    render_option: &RenderOption,
+    title: Option<&str>,
+    toc: &str,
+    sidebar: &str
+) -> String {
+    let template = &render_option.page_template;
+    let metadata = result.metadata.as_ref().unwrap_or(&Yaml::BadValue);
+    let title = match title {
+        Some(title) => escape(title),
+        None => escape(&yaml_to_string(&metadata["title"]))
+    };
+    let description = escape(&yaml_to_string(&metadata["description"]));
+    let lang = match &metadata["lang"] {
+        Yaml::String(lang) => escape(lang),
+        _ => escape(&render_option.lang)
+    };
+    let dir = match &metadata["dir"] {
+        Yaml::String(dir) if dir == "ltr" || dir == "rtl" || dir == "auto" => dir.to_string(),
+        _ => render_option.text_direction.to_string()
+    };
+
+    let Assets { css, javascript } = get_assets(result, render_option);
+    let void_end = if render_option.xml { "/>" } else { ">" };
+    let mut head = vec![
+        format!("<meta charset=\"utf-8\"{void_end}"),
+        format!("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\"{void_end}")
+    ];
+
+    if !description.is_empty() {
+        head.push(format!("<meta name=\"description\" content=\"{description}\"{void_end}"));
+    }
+
+    head.push(format!("{}{css}</style>", render_option.style_open_tag()));
+
+    let scripts = if javascript.is_empty() {
+        String::new()
+    }
+
+    else if render_option.xml {
+        format!("{}/*<![CDATA[*/{javascript}/*]]>*/</script>", render_option.script_open_tag())
+    }
+
+    else {
+        format!("{}{javascript}</script>", render_option.script_open_tag())
+    };
+
+    // the sprite sheet has to be outside `.markdown`. See `IconMode::Sprite`.
+    let icon_sprite = if render_option.icon_mode == IconMode::Sprite {
+        result.icon_sprite.as_str()
+    } else {
+        ""
+    };
+
+    let mut page = String::with_capacity(template.len() + result.content.len() + css.len() + javascript.len());
+    let mut rest = template.as_str();
+
+    while let Some(start_index) = rest.find("{{") {
+        page.push_str(&rest[..start_index]);
+        rest = &rest[start_index..];
+
+        let end_index = match rest.find("}}") {
+            Some(end_index) => end_index,
+            None => { break; }
+        };
+
+        let placeholder = &rest[2..end_index];
+
+        match placeholder {
+            "content" => { page.push_str(&result.content); },
+            "toc" => { page.push_str(toc); },
+            "sidebar" => { page.push_str(sidebar); },
+            "title" => { page.push_str(&title); },
+            "description" => { page.push_str(&description); },
+            "lang" => { page.push_str(&lang); },
+            "dir" => { page.push_str(&dir); },
+            "head" => { page.push_str(&head.join("\n")); },
+            "scripts" => { page.push_str(&scripts); },
+            "icon_sprite" => { page.push_str(icon_sprite); },
+            key => match key.strip_prefix("metadata.") {
+                Some(key) => {
+                    let value = key.split('.').fold(metadata, |yaml, key| &yaml[key]);
+                    page.push_str(&escape(&yaml_to_string(value)));
+                },
+                None => { page.push_str(&rest[..(end_index + 2)]); }
+            }
+        }
+
+        rest = &rest[(end_index + 2)..];
+    }
+
+    page.push_str(rest);
+
+    page
+}
+
+fn yaml_to_string(yaml: &Yaml) -> String {
+
+    match yaml {
+        Yaml::String(s) | Yaml::Real(s) => s.clone(),
+        Yaml::Integer(n) => n.to_string(),
+        Yaml::Boolean(b) => b.to_string(),
+        _ => String::new()
+    }
+
+}
+
+fn escape(s: &str) -> String {
+    from_v32(&render_html_escapes(&escape_htmls(&into_v32(s))))
+}
@@ -1,36 +1,174 @@
+use super::page::DEFAULT_PAGE_TEMPLATE;
+use crate::color::Color;
 use crate::container::header::normalize_header;
+use crate::container::icon::IconSet;
+use crate::escape::{escape_htmls, render_html_escapes};
+use crate::inline::citation::bibliography::Bibliography;
 use crate::inline::link::predicate::is_valid_link_destination;
+use crate::inline::link::wiki::WikiLinkResolver;
+use crate::inline::macros::normalize_macro;
+use crate::inline::raw_html::HtmlAllowList;
 use crate::utils::{from_v32, into_v32};
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct RenderOption {
+    pub autolink_schemes: Vec<String>,
+    pub bibliography: Bibliography,
+    pub bibliography_files: bool,
+    pub citation_style: CitationStyle,
     pub class_prefix: String,
+    pub colors: Vec<Color>,
+    pub csp: bool,
+    pub dollar_math: bool,
+    pub email_obfuscation: EmailObfuscation,
     pub enable_youtube: bool,
+    pub equation_numbers: bool,
+    pub external_link_attributes: bool,
     pub footnote_tooltip: bool,
+    pub footnote_layout: FootnoteLayout,
     pub header_anchor: bool,
+    pub icons: IconSet,
+    pub icon_mode: IconMode,
     pub javascript_for_collapsible_tables: bool,
     pub javascript_for_copy_buttons: bool,
     pub javascript_for_tooltips: bool,
     pub javascript_for_sidebar: bool,
+    pub javascript_for_emails: bool,
+    pub lang: String,
     pub link_handler: fn(&str) -> String,
+    pub math_error_span: bool,
+    pub math_speech: bool,
+    pub page_template: String,
     pub parse_metadata: bool,
+    pub raw_html: RawHtmlPolicy,
+    pub script_nonce: Option<String>,
+    pub text_direction: TextDirection,
+    pub wiki_link_resolver: Option<WikiLinkResolver>,  // `None` for the default resolver
     pub xml: bool
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FootnoteLayout {
+    /// All the footnotes are rendered at the end of the document.
+    End,
+
+    /// Each footnote is rendered in an `<aside>`, right after the block that first references it.
+    /// With the default stylesheet, it's floated to the margin on wide screens.
+    Sidenote,
+
+    /// Footnotes are rendered at the end of each top-level section.
+    /// The top-level sections are divided by the headers with the smallest level in the document.
+    Section
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum IconMode {
+    /// Each icon is rendered to a whole `<svg>`.
+    Inline,
+
+    /// Each icon is rendered to `<svg><use href="#mdxt-icon-NAME"/></svg>`,
+    /// and `RenderResult.icon_sprite` has a `<symbol>` for each icon that the document uses.
+    /// The sprite sheet has to be in the same page.
+    Sprite,
+
+    /// Same as `Sprite`, but `href` points to an external file: `<use href="icons.svg#mdxt-icon-NAME"/>`.
+    /// Write `RenderResult.icon_sprite` to the file.
+    ExternalSprite(String)
+}
+
+/// What to do with the html tags in documents, like `<span class="x">`.
+/// Only inline tags and comments are recognized: a tag alone in a line is still in a paragraph.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RawHtmlPolicy {
+    /// The tags are rendered as texts: `&lt;span class=&quot;x&quot;&gt;`.
+    Escape,
+
+    /// The tags and the attributes in the list are kept, and the urls are checked.
+    /// The other tags are rendered as texts and reported once in `RenderResult.diagnostics`, and the other attributes are removed.
+    /// The elements that the engine renders are not affected.
+    Sanitize(HtmlAllowList),
+
+    /// The tags are rendered as they are. Use it only with documents that you trust.
+    Trusted
+}
+
+/// How email addresses are rendered: `[[email = user@example.com]]`, `<user@example.com>` and `user@example.com`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EmailObfuscation {
+    /// `<a href="mailto:...">`, with each character of the address rendered to a numeric character reference.
+    Entities,
+
+    /// `<a data-mdxt-email="...">`, without the address in a readable form. `email_javascript` assembles the link in the browser.
+    /// The link is empty if the browser doesn't run javascript.
+    Javascript
+}
+
+/// `dir` attribute of the pages that `render_page` renders
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextDirection {
+    LeftToRight,
+    RightToLeft,
+    Auto
+}
+
+impl std::fmt::Display for TextDirection {
+
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            TextDirection::LeftToRight => "ltr",
+            TextDirection::RightToLeft => "rtl",
+            TextDirection::Auto => "auto"
+        })
+    }
+
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CitationStyle {
+    /// `[1]`, `[1, p. 12]`. The references are numbered in the order of their first citations.
+    Numeric,
+
+    /// `(Knuth 1984)`, `(Knuth 1984, p. 12)`. The references are sorted by their authors.
+    AuthorYear
+}
+
 impl Default for RenderOption {
 
     fn default() -> Self {
         RenderOption {
+            autolink_schemes: ["http", "https", "ftp", "mailto", "tel"].iter().map(|scheme| scheme.to_string()).collect(),
+            bibliography: Bibliography::default(),
+            bibliography_files: false,
+            citation_style: CitationStyle::Numeric,
             class_prefix: String::new(),
+            colors: vec![],
+            csp: false,
+            dollar_math: false,
+            email_obfuscation: EmailObfuscation::Entities,
             enable_youtube: true,
+            equation_numbers: false,
+            external_link_attributes: false,
             footnote_tooltip: false,
+            footnote_layout: FootnoteLayout::End,
             header_anchor: true,
+            icons: IconSet::default(),
+            icon_mode: IconMode::Inline,
             javascript_for_collapsible_tables: true,
             javascript_for_copy_buttons: true,
             javascript_for_tooltips: true,
             javascript_for_sidebar: true,
+            javascript_for_emails: true,
+            lang: String::from("en"),
             link_handler: |s| s.to_string(),
+            math_error_span: true,
+            math_speech: false,
+            page_template: DEFAULT_PAGE_TEMPLATE.to_string(),
             parse_metadata: true,
+            raw_html: RawHtmlPolicy::Escape,
+            script_nonce: None,
+            text_direction: TextDirection::LeftToRight,
+            wiki_link_resolver: None,
             xml: false
         }
     }
@@ -52,6 +190,16 @@ impl RenderOption {
         self
     }
 
+    /// It maps the titles of wiki links, like `[[[Page Name]]]`, to urls. If it returns `None`, the page is missing:
+    /// the link is rendered to `<span class="missing-wiki-link">` and reported in `RenderResult.diagnostics`.
+    /// By default, all the pages exist and `Page Name#Section` is mapped to `page-name#section`.
+    /// The resolver may capture its environment, like the list of the pages of a site.
+    pub fn set_wiki_link_resolver<F: Fn(&str) -> Option<String> + Send + Sync + 'static>(&mut self, wiki_link_resolver: F) -> &mut Self {
+        self.wiki_link_resolver = Some(Arc::new(wiki_link_resolver));
+
+        self
+    }
+
     /// give `id` attributes to header tags
     pub fn set_header_anchor(&mut self, header_anchor: bool) -> &mut Self {
         self.header_anchor = header_anchor;
@@ -100,15 +248,59 @@ impl RenderOption {
         self
     }
 
+    /// It embeds javascript for email addresses in a `<script>`. See `EmailObfuscation::Javascript`.
+    pub fn embed_js_for_emails(&mut self, javascript: bool) -> &mut Self {
+        self.javascript_for_emails = javascript;
+
+        self
+    }
+
     pub fn embed_js_all(&mut self, javascript: bool) -> &mut Self {
         self.javascript_for_collapsible_tables = javascript;
         self.javascript_for_copy_buttons = javascript;
         self.javascript_for_tooltips = javascript;
         self.javascript_for_sidebar = javascript;
+        self.javascript_for_emails = javascript;
+
+        self
+    }
+
+    /// For a strict Content-Security-Policy: the output doesn't have inline event handlers like `onclick`.
+    /// The elements have `data-*` attributes instead, and the engine's scripts register the handlers with `addEventListener`. See `csp_javascript`.
+    /// The embedded `<script>` is still blocked unless it has a nonce. Either set `script_nonce`, or turn off `embed_js_all` and serve `RenderResult::assets` as a file.
+    /// The output doesn't have `style` attributes either, so `style-src` only has to allow the `<style>` of `render_page`, which also gets `script_nonce`.
+    pub fn set_csp(&mut self, csp: bool) -> &mut Self {
+        self.csp = csp;
+
+        self
+    }
+
+    /// `nonce` attribute of the `<script>`s that the engine embeds, and of the `<style>` of `render_page`.
+    pub fn set_script_nonce(&mut self, script_nonce: String) -> &mut Self {
+        self.script_nonce = Some(script_nonce);
 
         self
     }
 
+    // `<script>` or `<script nonce="...">`
+    pub(crate) fn script_open_tag(&self) -> String {
+        format!("<script{}>", self.nonce_attribute())
+    }
+
+    // `<style>` or `<style nonce="...">`
+    pub(crate) fn style_open_tag(&self) -> String {
+        format!("<style{}>", self.nonce_attribute())
+    }
+
+    fn nonce_attribute(&self) -> String {
+
+        match &self.script_nonce {
+            Some(nonce) => format!(" nonce=\"{}\"", from_v32(&render_html_escapes(&escape_htmls(&into_v32(nonce))))),
+            None => String::new()
+        }
+
+    }
+
     /// Shows a tooltip message when hovering over a footnote ref.
     pub fn set_footnote_tooltip(&mut self, footnote_tooltip: bool) -> &mut Self {
         self.footnote_tooltip = footnote_tooltip;
@@ -116,6 +308,162 @@ impl RenderOption {
         self
     }
 
+    /// Renders `$...$` and `$$...$$` as LaTeX math, like `[[math, latex]]`.
+    /// `$$` has to be on its own line, and `\$` is a dollar sign.
+    pub fn set_dollar_math(&mut self, dollar_math: bool) -> &mut Self {
+        self.dollar_math = dollar_math;
+
+        self
+    }
+
+    /// Numbers all the block math, unless it has `nonumber` argument.
+    /// Block math with an `id` argument is numbered regardless of this option.
+    pub fn set_equation_numbers(&mut self, equation_numbers: bool) -> &mut Self {
+        self.equation_numbers = equation_numbers;
+
+        self
+    }
+
+    /// Adds a visually hidden English reading of each math, like `a squared plus b squared`, for screen readers.
+    /// The MathML is hidden from the screen readers when it's on. `alttext` is rendered regardless of this option.
+    pub fn set_math_speech(&mut self, math_speech: bool) -> &mut Self {
+        self.math_speech = math_speech;
+
+        self
+    }
+
+    /// Wraps a math that has errors in `<span class="math-error">`, whose `title` tells what's wrong.
+    /// The errors are reported in `RenderResult.diagnostics` regardless of this option.
+    pub fn set_math_error_span(&mut self, math_error_span: bool) -> &mut Self {
+        self.math_error_span = math_error_span;
+
+        self
+    }
+
+    /// Schemes of autolinks: `ftp://example.com`, `tel:+1-201-555-0123`. It's `http`, `https`, `ftp`, `mailto` and `tel` by default.
+    /// An autolink without a scheme, like `www.example.com`, is an `http` link.
+    pub fn set_autolink_schemes(&mut self, autolink_schemes: Vec<String>) -> &mut Self {
+        self.autolink_schemes = autolink_schemes;
+
+        self
+    }
+
+    /// Adds `rel="nofollow noopener"` and `target="_blank"` to the links to other sites: the ones whose destinations have `://` or start with `//`, like `https://example.com`.
+    pub fn set_external_link_attributes(&mut self, external_link_attributes: bool) -> &mut Self {
+        self.external_link_attributes = external_link_attributes;
+
+        self
+    }
+
+    /// `RawHtmlPolicy::Escape` by default.
+    pub fn set_raw_html(&mut self, raw_html: RawHtmlPolicy) -> &mut Self {
+        self.raw_html = raw_html;
+
+        self
+    }
+
+    /// `EmailObfuscation::Entities` by default.
+    pub fn set_email_obfuscation(&mut self, email_obfuscation: EmailObfuscation) -> &mut Self {
+        self.email_obfuscation = email_obfuscation;
+
+        self
+    }
+
+    /// Where footnotes are rendered. See `FootnoteLayout`.
+    pub fn set_footnote_layout(&mut self, footnote_layout: FootnoteLayout) -> &mut Self {
+        self.footnote_layout = footnote_layout;
+
+        self
+    }
+
+    /// `[@key]` citations are resolved against this bibliography.
+    /// The `bibliography` and `references` fields of the metadata are merged into it.
+    pub fn set_bibliography(&mut self, bibliography: Bibliography) -> &mut Self {
+        self.bibliography = bibliography;
+
+        self
+    }
+
+    /// Lets the metadata's `bibliography` field read files. It's off by default: the field can name any file
+    /// that the process can read, so turn it on only with documents that you trust.
+    pub fn set_bibliography_files(&mut self, bibliography_files: bool) -> &mut Self {
+        self.bibliography_files = bibliography_files;
+
+        self
+    }
+
+    /// The metadata's `citation-style` field (`numeric` or `author-year`) overrides it.
+    pub fn set_citation_style(&mut self, citation_style: CitationStyle) -> &mut Self {
+        self.citation_style = citation_style;
+
+        self
+    }
+
+    /// `[[icon = name]]` and `icon = name` of admonitions look up these icons before the built-in ones.
+    pub fn set_icons(&mut self, icons: IconSet) -> &mut Self {
+        self.icons = icons;
+
+        self
+    }
+
+    /// How `[[icon]]` macros and the icons of admonitions are rendered. See `IconMode`.
+    pub fn set_icon_mode(&mut self, icon_mode: IconMode) -> &mut Self {
+        self.icon_mode = icon_mode;
+
+        self
+    }
+
+    /// Colors that can be used like the built-in ones: `[[brand]]`, `[[highlight = brand]]` and `[[icon = github, color = brand]]`.
+    /// Their names are case-insensitive, and spaces and underscores are ignored, like the other macros.
+    /// The engine only renders `color-brand` and `highlight-brand` classes. Use `colors_to_css` for their styles.
+    pub fn set_colors(&mut self, colors: Vec<Color>) -> &mut Self {
+        self.colors = colors;
+
+        self
+    }
+
+    // `name` is normalized by `read_macro`
+    pub(crate) fn has_color(&self, name: &[u32]) -> bool {
+        self.colors.iter().any(|color| normalize_macro(&into_v32(&color.name)) == name)
+    }
+
+    /// The template of `render_page` and `RenderResult::to_page`. See `DEFAULT_PAGE_TEMPLATE`.
+    /// `{{content}}`, `{{toc}}`, `{{sidebar}}`, `{{title}}`, `{{description}}`, `{{lang}}`, `{{dir}}`, `{{head}}`, `{{scripts}}`,
+    /// `{{icon_sprite}}` and `{{metadata.KEY}}` are filled by the engine, and the other placeholders are kept as they are.
+    /// If the template has `{{sidebar}}`, the sidebar goes there instead of `{{content}}`. Wrap it in `class="markdown"` for the stylesheet.
+    pub fn set_page_template(&mut self, page_template: String) -> &mut Self {
+        self.page_template = page_template;
+
+        self
+    }
+
+    /// `lang` attribute of the pages. `lang` of the metadata overrides it.
+    pub fn set_lang(&mut self, lang: String) -> &mut Self {
+        self.lang = lang;
+
+        self
+    }
+
+    /// `dir` attribute of the pages. `dir` of the metadata (`ltr`, `rtl` or `auto`) overrides it.
+    pub fn set_text_direction(&mut self, text_direction: TextDirection) -> &mut Self {
+        self.text_direction = text_direction;
+
+        self
+    }
+
+    // `//example.com` or `scheme://...`, but not `/r?u=http://x`
+    pub(crate) fn is_external_link(&self, destination: &[u32]) -> bool {
+        self.external_link_attributes && (
+            destination.starts_with(&[47, 47])  // into_v32("//")
+            || match destination.iter().position(|c| *c == ':' as u32) {
+                Some(colon_index) => colon_index > 0
+                    && !destination[..colon_index].iter().any(|c| *c == '/' as u32 || *c == '?' as u32 || *c == '#' as u32)
+                    && destination[colon_index + 1..].starts_with(&[47, 47]),  // into_v32("//")
+                None => false,
+            }
+        )
+    }
+
     /// it's used internally by the engine
     pub fn handle_link(&self, link: &str) -> String {
         (self.link_handler)(&default_link_handler(link))
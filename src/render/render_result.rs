@@ -1,3 +1,8 @@
+use super::asset::{Assets, get_assets};
+use super::diagnostic::Diagnostic;
+use super::page::result_to_page;
+use super::render_option::RenderOption;
+use crate::inline::link::wiki::WikiLink;
 use std::collections::HashMap;
 use yaml_rust::Yaml;
 
@@ -13,11 +18,51 @@ pub struct RenderResult {
     /// This flag tells you if the document has a sidebar.
     pub has_sidebar: bool,
 
+    /// This flag tells you if the document has email addresses that need `email_javascript`. See `EmailObfuscation::Javascript`.
+    pub has_javascript_email: bool,
+
     pub metadata: Option<Yaml>,
 
+    /// Images with titles, tables with `caption` macros and fenced code blocks with `caption(...)` are numbered automatically.
+    /// These are the numbers of them.
+    pub figure_count: usize,
+    pub table_count: usize,
+    pub listing_count: usize,
+
+    /// The number of numbered block math.
+    pub equation_count: usize,
+
+    /// Problems found while rendering, like references to undefined labels.
+    pub diagnostics: Vec<Diagnostic>,
+
+    /// Wiki links in the document, like `[[[Page Name]]]`, one for each title. Use it to build a backlink graph.
+    pub wiki_links: Vec<WikiLink>,
+
+    /// With `IconMode::Sprite` or `IconMode::ExternalSprite`, it's an `<svg>` with a `<symbol>` for each icon that the document uses.
+    /// Otherwise, it's empty.
+    pub icon_sprite: String,
+
     /// Some fenced codes have a `copy` button with them.
     /// Each button has an index of the fenced code.
     /// This hashmap maps the index and the content of the fenced code.
     /// Use this data when writing javascript for the copy buttons.
     pub fenced_code_contents: HashMap<usize, String>
-}
\ No newline at end of file
+}
+
+impl RenderResult {
+
+    /// The css and the javascript that `content` needs: the embedded `styles/markdown.css` without the rules of the unused features,
+    /// and the scripts that are not embedded in `content`. Both are minified, and `class_prefix` is applied to both.
+    /// `render_option` has to be the one that rendered this result.
+    pub fn assets(&self, render_option: &RenderOption) -> Assets {
+        get_assets(self, render_option)
+    }
+
+    /// A whole html document, with the assets of `content` in it. It fills `RenderOption.page_template`, like `render_page`,
+    /// but `{{toc}}` is empty and the sidebar is a part of `{{content}}`. `title` replaces the one in the metadata.
+    /// `render_option` has to be the one that rendered this result.
+    pub fn to_page(&self, render_option: &RenderOption, title: &str) -> String {
+        result_to_page(self, render_option, title)
+    }
+
+}
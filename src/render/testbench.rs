@@ -0,0 +1,162 @@
+use super::asset::{minify_css, prefix_classes};
+use crate::{csp_javascript, render_page, render_to_html, stylesheet, RenderOption, TextDirection};
+
+#[test]
+fn minify_css_test() {
+    assert_eq!(
+        minify_css("/* comment */\n.markdown p,\n.markdown li {\n  margin: 0;\n  font-family: \"Fira  Code\";\n}\n\n@media (max-width: 60rem) {\n  .markdown > div { float: none; }\n}\n"),
+        String::from(".markdown p,.markdown li{margin: 0;font-family: \"Fira  Code\"}@media (max-width: 60rem){.markdown>div{float: none}}")
+    );
+}
+
+#[test]
+fn prefix_classes_test() {
+    assert_eq!(
+        prefix_classes(".markdown table thead.collapsed th,.markdown .box:hover", "mdxt-"),
+        String::from(".markdown table thead.mdxt-collapsed th,.markdown .mdxt-box:hover")
+    );
+    assert_eq!(
+        prefix_classes(".markdown-body .x,a[href$=\".pdf\"],#mdxt-sidebar", "mdxt-"),
+        String::from(".mdxt-markdown-body .mdxt-x,a[href$=\".pdf\"],#mdxt-sidebar")
+    );
+}
+
+#[test]
+fn asset_test() {
+    let mut options = RenderOption::default();
+    options.set_class_prefix(String::from("mdxt-")).embed_js_all(false);
+
+    let plain = render_to_html("Hello", options.clone());
+    let assets = plain.assets(&options);
+
+    assert!(assets.css.contains(".markdown div.mdxt-box{"));
+    assert!(!assets.css.contains(".markdown div.box{"));
+    assert!(!assets.css.contains("tooltip"));
+    assert!(!assets.css.contains("#mdxt-sidebar"));
+    assert!(!assets.css.contains("collapsed"));
+    assert!(!assets.css.contains("/*"));
+    assert!(assets.javascript.is_empty());
+
+    let tooltip = render_to_html("[[tooltip=abc]] Hover over me! [[/tooltip]]\n\n[^abc]: Hi, there!", options.clone());
+    let assets = tooltip.assets(&options);
+
+    assert!(assets.css.contains(".markdown .mdxt-tooltip-container:hover .mdxt-tooltip-message{"));
+    assert!(assets.javascript.starts_with("let tooltips = document.querySelectorAll(\".mdxt-tooltip-container\");\nfor ("));
+    assert!(!tooltip.content.contains("<script>"));
+
+    // the scripts in `content` are not duplicated
+    let embedded_options = RenderOption::default();
+    let embedded = render_to_html("[[tooltip=abc]] Hover over me! [[/tooltip]]\n\n[^abc]: Hi, there!", embedded_options.clone());
+
+    assert!(embedded.content.contains("<script>"));
+    assert!(embedded.assets(&embedded_options).javascript.is_empty());
+
+    let page = tooltip.to_page(&options, "<Tooltips>");
+
+    assert!(page.contains("<title>&lt;Tooltips&gt;</title>"));
+    assert!(page.contains(&format!("<article class=\"markdown\">{}</article>", tooltip.content)));
+    assert!(page.contains(&format!("<script>{}</script>", assets.javascript)));
+
+    assert!(stylesheet("").contains("#mdxt-sidebar{"));
+    assert!(stylesheet("").contains(".markdown .tooltip-message{"));
+}
+
+#[test]
+fn page_test() {
+    let md = "---
+title: A <Page>
+description: Pages & templates
+author:
+  name: Baehyunsol
+dir: rtl
+---
+
+# Header
+
+Content
+
+[[sidebar]]
+
+Sidebar
+
+[[/sidebar]]
+";
+    let mut options = RenderOption::default();
+    options.set_page_template(String::from(
+        "<html lang=\"{{lang}}\" dir=\"{{dir}}\"><title>{{title}}</title><nav>{{toc}}</nav><aside>{{sidebar}}</aside><main>{{content}}</main><footer>{{metadata.author.name}}{{metadata.nope}}{{unknown}}</footer></html>"
+    )).set_lang(String::from("ko"));
+
+    let page = render_page(md, options.clone());
+
+    assert!(page.starts_with("<html lang=\"ko\" dir=\"rtl\"><title>A &lt;Page&gt;</title><nav><div class=\"toc\">"));
+    assert!(page.contains("<aside><div id=\"mdxt-sidebar-toggle\""));
+    assert!(page.ends_with("</main><footer>Baehyunsol{{unknown}}</footer></html>"));
+
+    let main = &page[page.find("<main>").unwrap()..];
+    assert!(main.contains("<p>Content</p>"));
+    assert!(!main.contains("mdxt-sidebar-content"));
+
+    // the default template doesn't have `{{sidebar}}` and `{{toc}}`
+    let mut options = RenderOption::default();
+    options.set_text_direction(TextDirection::Auto);
+
+    let page = render_page("# Header\n\nContent\n\n[[sidebar]]\n\nSidebar\n\n[[/sidebar]]", options.clone());
+    let result = render_to_html("# Header\n\nContent\n\n[[sidebar]]\n\nSidebar\n\n[[/sidebar]]", options.clone());
+
+    assert!(page.starts_with("<!DOCTYPE html>\n<html lang=\"en\" dir=\"auto\">\n<head>\n<meta charset=\"utf-8\">"));
+    assert!(page.contains("<title></title>"));
+    assert!(!page.contains("class=\"toc\""));
+    assert!(page.contains(&format!("<article class=\"markdown\">{}</article>", result.content)));
+    assert_eq!(page, result.to_page(&options, ""));
+
+    let page = render_page(md, RenderOption::default());
+    assert!(page.contains("<meta name=\"description\" content=\"Pages &amp; templates\">"));
+}
+
+#[test]
+fn csp_test() {
+    let md = "
+|Head|
+|-|
+|!![[collapsible]]|
+|Body|
+
+```rust, line_num
+fn main() {}
+```
+
+[[sidebar]]
+
+Sidebar
+
+[[/sidebar]]
+";
+
+    let result = render_to_html(md, RenderOption::default());
+    assert!(result.content.contains("onclick"));
+    assert!(!result.content.contains("data-mdxt-"));
+
+    let mut options = RenderOption::default();
+    options.set_csp(true).set_script_nonce(String::from("a\"b"));
+    let result = render_to_html(md, options.clone());
+
+    assert!(!result.content.contains("onclick"));
+    assert!(result.content.contains("data-mdxt-collapse=\"0\""));
+    assert!(result.content.contains("data-mdxt-copy=\"0\""));
+    assert!(result.content.contains("data-mdxt-sidebar=\"toggle\""));
+    assert!(result.content.contains("data-mdxt-sidebar=\"close\""));
+    assert!(result.content.contains("<script nonce=\"a&quot;b\">"));
+    assert!(!result.content.contains("<script>"));
+    assert_eq!(result.content.matches("addEventListener").count(), 3);
+
+    // the listeners go to the assets when the scripts are not embedded
+    options.embed_js_all(false);
+    let result = render_to_html(md, options.clone());
+
+    assert!(!result.content.contains("<script"));
+    assert_eq!(result.assets(&options).javascript.matches("addEventListener").count(), 3);
+    assert!(result.to_page(&options, "").contains("<script nonce=\"a&quot;b\">"));
+    assert!(result.to_page(&options, "").contains("<style nonce=\"a&quot;b\">"));
+
+    assert_eq!(csp_javascript().matches("addEventListener").count(), 3);
+}
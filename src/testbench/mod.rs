@@ -1,7 +1,6 @@
 use std::fs::File;
 use std::io::{Read, Write};
 use crate::{render_to_html, RenderOption, RenderResult};
-use crate::utils::add_styles_to_html;
 
 // hxml is not thread-safe
 pub static mut HXML_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
@@ -16,32 +15,31 @@ fn render_reference_test() {
     let mut f = File::open("./reference.md").unwrap();
     f.read_to_string(&mut reference).unwrap();
 
+    let options = RenderOption {
+        xml: true,
+        ..RenderOption::default()
+    };
+    let result = render_to_html(&reference, options.clone());
     let RenderResult {
         content: reference,
         has_collapsible_table,
         has_tooltip,
         has_sidebar,
         ..
-    } = render_to_html(
-        &reference,
-        RenderOption {
-            xml: true,
-            ..RenderOption::default()
-        }
-    );
+    } = &result;
 
     hxml::into_dom(reference.clone()).unwrap();
 
-    assert!(has_collapsible_table);
-    assert!(has_tooltip);
-    assert!(has_sidebar);
+    assert!(*has_collapsible_table);
+    assert!(*has_tooltip);
+    assert!(*has_sidebar);
 
     // TODO: more queries
     // TODO: same stuff for math_test.html, icon_test.html, and character_test.html
 
     drop(lock);
 
-    let reference = add_styles_to_html(&reference);
+    let reference = result.to_page(&options, "MDxt Reference");
 
     let mut f = File::create("./reference.html").unwrap();
     f.write_all(reference.as_bytes()).unwrap();
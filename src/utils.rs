@@ -363,30 +363,6 @@ pub fn log10(n: usize) -> usize {
 
 }
 
-pub fn add_styles_to_html(html: &str) -> String {
-    use std::fs::File;
-    use std::io::Read;
-
-    let mut f = File::open("./styles/markdown.css").unwrap();
-    let mut css = String::new();
-    f.read_to_string(&mut css).unwrap();
-
-    format!(
-"
-<!DOCTYPE html>
-<html>
-<head>
-    <title>MDxt Reference</title>
-    <style>{css}</style>
-</head>
-<body style=\"padding-left: 36px\">
-    <article class=\"markdown\">{html}</article>
-</body>
-</html>
-",
-    )
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;